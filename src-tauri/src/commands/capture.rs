@@ -0,0 +1,109 @@
+use crate::models::{
+    CaptureConfig, CaptureFilter, CaptureSession, CapturedExchange, ProjectInfo, Workspace,
+};
+use crate::services::WorkspaceService;
+use crate::state::AppState;
+use tauri::State;
+
+/// 更新项目的抓包配置；项目正在运行时立即开启或关闭抓包代理
+#[tauri::command]
+pub async fn update_project_capture(
+    mut workspace: Workspace,
+    project_id: String,
+    capture: Option<CaptureConfig>,
+    state: State<'_, AppState>,
+) -> Result<Workspace, String> {
+    // 查找并更新项目的抓包配置
+    let project = workspace
+        .projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or("未找到指定的项目".to_string())?;
+    project.capture = capture;
+    let project = project.clone();
+
+    let is_running = state
+        .running_processes
+        .lock()
+        .await
+        .values()
+        .any(|p| p.project_id == project_id);
+    if is_running {
+        if project.capture.is_some() {
+            start_capture(&project, &state).await?;
+        } else {
+            stop_capture(&project_id, &state).await;
+        }
+    }
+
+    // 保存工作区
+    WorkspaceService::save_workspace(&mut workspace)?;
+
+    Ok(workspace)
+}
+
+#[tauri::command]
+pub async fn get_capture_sessions(
+    state: State<'_, AppState>,
+) -> Result<Vec<CaptureSession>, String> {
+    Ok(state.capture.sessions().await)
+}
+
+#[tauri::command]
+pub async fn query_captures(
+    filter: Option<CaptureFilter>,
+    state: State<'_, AppState>,
+) -> Result<Vec<CapturedExchange>, String> {
+    Ok(state.capture.query(&filter.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub async fn clear_captures(
+    project_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.capture.clear(project_id.as_deref());
+    Ok(())
+}
+
+/// 按条件导出 HAR 1.2 文件，返回导出的记录数
+#[tauri::command]
+pub async fn export_captures_har(
+    filter: Option<CaptureFilter>,
+    dest_path: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let har = state.capture.export_har(&filter.unwrap_or_default());
+    let count = har["log"]["entries"].as_array().map_or(0, |e| e.len());
+
+    let content =
+        serde_json::to_string_pretty(&har).map_err(|e| format!("序列化 HAR 失败: {}", e))?;
+    std::fs::write(&dest_path, content).map_err(|e| format!("写入 HAR 文件失败: {}", e))?;
+
+    Ok(count)
+}
+
+/// 项目开启了抓包时启动代理，并让网关经由代理转发
+pub(crate) async fn start_capture(
+    project: &ProjectInfo,
+    state: &State<'_, AppState>,
+) -> Result<(), String> {
+    let Some(config) = project.capture.as_ref() else {
+        return Ok(());
+    };
+
+    let session = state.capture.start(project, config).await?;
+    state
+        .gateway
+        .set_route_target(&project.id, session.listen_port);
+
+    Ok(())
+}
+
+pub(crate) async fn stop_capture(project_id: &str, state: &State<'_, AppState>) {
+    if let Some(session) = state.capture.stop(project_id).await {
+        state
+            .gateway
+            .set_route_target(project_id, session.target_port);
+    }
+}
@@ -1,5 +1,7 @@
+use crate::models::ZebrasVersion;
+use crate::services::ConfigLayers;
+use crate::utils::config_edit::{self, ConfigSyntax};
 use crate::utils::ts_parser;
-use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -9,18 +11,19 @@ pub async fn update_debug_config(
     project_path: String,
     project_version: String,
     debug_map: HashMap<String, String>,
+    strict_json: Option<bool>,
 ) -> Result<(), String> {
     let path = PathBuf::from(&project_path);
 
     match project_version.as_str() {
         "v3" => update_v3_debug(&path, &debug_map),
-        "v2" => update_v2_debug(&path, &debug_map),
+        "v2" => update_v2_debug(&path, &debug_map, strict_json.unwrap_or(false)),
         _ => Err("不支持的项目版本".to_string()),
     }
 }
 
 fn update_v3_debug(project_path: &Path, debug_map: &HashMap<String, String>) -> Result<(), String> {
-    let local_config_path = project_path.join("zebras.config.local.ts");
+    let local_config_path = ConfigLayers::local_file(project_path, &ZebrasVersion::V3);
 
     let content = if local_config_path.exists() {
         fs::read_to_string(&local_config_path).map_err(|e| e.to_string())?
@@ -29,40 +32,34 @@ fn update_v3_debug(project_path: &Path, debug_map: &HashMap<String, String>) ->
         "export default {\n};\n".to_string()
     };
 
-    let updated = ts_parser::update_debug_in_ts(&content, debug_map);
+    let updated = ts_parser::update_debug_in_ts(&content, debug_map)?;
 
     fs::write(&local_config_path, updated).map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
-fn update_v2_debug(project_path: &Path, debug_map: &HashMap<String, String>) -> Result<(), String> {
-    let local_config_path = project_path.join("zebra.local.json");
+/// 只改动 debug 字段，保留 zebra.local.json 原有的键顺序、注释和格式；
+/// strict_json 为 true 时写成严格 JSON
+fn update_v2_debug(
+    project_path: &Path,
+    debug_map: &HashMap<String, String>,
+    strict_json: bool,
+) -> Result<(), String> {
+    let local_config_path = ConfigLayers::local_file(project_path, &ZebrasVersion::V2);
 
-    let mut config: Value = if local_config_path.exists() {
-        let json_str = fs::read_to_string(&local_config_path).map_err(|e| e.to_string())?;
-        serde_json::from_str(&json_str).map_err(|e| e.to_string())?
+    let content = if local_config_path.exists() {
+        fs::read_to_string(&local_config_path).map_err(|e| e.to_string())?
     } else {
-        serde_json::json!({})
+        "{\n}\n".to_string()
     };
 
-    if debug_map.is_empty() {
-        // 删除 debug 字段
-        if let Value::Object(ref mut obj) = config {
-            obj.remove("debug");
-        }
-    } else {
-        // 更新 debug 字段
-        let debug_obj: serde_json::Map<String, Value> = debug_map
-            .iter()
-            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
-            .collect();
-
-        config["debug"] = Value::Object(debug_obj);
+    let mut updated =
+        config_edit::sync_string_map(&content, ConfigSyntax::Json, "debug", debug_map)?;
+    if strict_json {
+        updated = config_edit::to_strict_json(&updated)?;
     }
-
-    let json_str = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
-    fs::write(&local_config_path, json_str).map_err(|e| e.to_string())?;
+    fs::write(&local_config_path, updated).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -0,0 +1,67 @@
+use crate::commands::port::load_all_workspaces;
+use crate::models::GatewayStatus;
+use crate::services::{gateway_host, CertManager, GatewayTlsOptions};
+use crate::state::AppState;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::State;
+
+/// 启动本地开发网关，路由由运行中的项目自动生成
+/// 传入 https_port 时同时提供 HTTPS，证书由本地 CA 为各工作区项目的域名签发
+#[tauri::command]
+pub async fn start_gateway(
+    port: u16,
+    https_port: Option<u16>,
+    state: State<'_, AppState>,
+) -> Result<GatewayStatus, String> {
+    if port == 0 || https_port == Some(0) {
+        return Err("网关端口无效".to_string());
+    }
+    if https_port == Some(port) {
+        return Err("HTTPS 端口不能与 HTTP 端口相同".to_string());
+    }
+
+    let tls = match https_port {
+        Some(https_port) => {
+            let mut hosts: Vec<String> = load_all_workspaces()
+                .iter()
+                .flat_map(|workspace| workspace.projects.iter().map(gateway_host))
+                .collect();
+            hosts.sort();
+            hosts.dedup();
+
+            Some(GatewayTlsOptions {
+                port: https_port,
+                certs: CertManager::new()?,
+                hosts,
+            })
+        }
+        None => None,
+    };
+
+    let status = state.gateway.start(port, tls).await?;
+    state
+        .app_registry
+        .set_gateway_urls(&state.gateway.entry_urls().await);
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn stop_gateway(state: State<'_, AppState>) -> Result<GatewayStatus, String> {
+    state.gateway.stop().await;
+    state.app_registry.set_gateway_urls(&HashMap::new());
+    Ok(state.gateway.status().await)
+}
+
+#[tauri::command]
+pub async fn get_gateway_status(state: State<'_, AppState>) -> Result<GatewayStatus, String> {
+    Ok(state.gateway.status().await)
+}
+
+/// 导出本地 CA 证书，导入系统或浏览器信任后 HTTPS 访问不再告警
+#[tauri::command]
+pub async fn export_gateway_ca(dest_path: String) -> Result<String, String> {
+    let dest = PathBuf::from(&dest_path);
+    CertManager::new()?.export_ca(&dest)?;
+    Ok(dest_path)
+}
@@ -1,3 +1,4 @@
+use crate::commands::port::sync_port_leases;
 use crate::models::{CreateProjectInstanceInput, ValidationResult, Workspace, WorkspaceSourceType};
 use crate::services::{ManagedProjectService, WorkspaceList, WorkspaceService};
 use std::path::PathBuf;
@@ -13,8 +14,9 @@ pub async fn validate_project_instance(
 pub async fn create_project_instance(
     input: CreateProjectInstanceInput,
 ) -> Result<Workspace, String> {
-    let workspace = ManagedProjectService::create_project_instance(input)?;
-    WorkspaceService::save_workspace(&workspace)?;
+    let mut workspace = ManagedProjectService::create_project_instance(input)?;
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
 
     let mut list = WorkspaceList::load().unwrap_or_else(|_| WorkspaceList {
         workspaces: Vec::new(),
@@ -29,10 +31,10 @@ pub async fn load_project_instance(root_path: String) -> Result<Workspace, Strin
     let root = PathBuf::from(root_path);
     let base = find_existing_workspace(&root)?;
     let manifest = ManagedProjectService::load_manifest(&root)?;
-    let workspace = ManagedProjectService::workspace_from_manifest(&manifest, base.as_ref());
+    let mut workspace = ManagedProjectService::workspace_from_manifest(&manifest, base.as_ref());
 
     if base.is_some() {
-        WorkspaceService::save_workspace(&workspace)?;
+        WorkspaceService::save_workspace(&mut workspace)?;
     }
 
     Ok(workspace)
@@ -42,8 +44,8 @@ pub async fn load_project_instance(root_path: String) -> Result<Workspace, Strin
 pub async fn repair_project_instance(root_path: String) -> Result<Workspace, String> {
     let root = PathBuf::from(root_path);
     let base = find_existing_workspace(&root)?;
-    let workspace = ManagedProjectService::repair_project_instance(&root, base.as_ref())?;
-    persist_existing_workspace(&workspace)?;
+    let mut workspace = ManagedProjectService::repair_project_instance(&root, base.as_ref())?;
+    persist_existing_workspace(&mut workspace)?;
     Ok(workspace)
 }
 
@@ -51,13 +53,14 @@ pub async fn repair_project_instance(root_path: String) -> Result<Workspace, Str
 pub async fn rebuild_project_links(root_path: String) -> Result<Workspace, String> {
     let root = PathBuf::from(root_path);
     let base = find_existing_workspace(&root)?;
-    let workspace = ManagedProjectService::rebuild_project_links(&root, base.as_ref())?;
-    persist_existing_workspace(&workspace)?;
+    let mut workspace = ManagedProjectService::rebuild_project_links(&root, base.as_ref())?;
+    persist_existing_workspace(&mut workspace)?;
     Ok(workspace)
 }
 
-fn persist_existing_workspace(workspace: &Workspace) -> Result<(), String> {
+fn persist_existing_workspace(workspace: &mut Workspace) -> Result<(), String> {
     WorkspaceService::save_workspace(workspace)?;
+    sync_port_leases(workspace);
 
     let mut list = WorkspaceList::load().unwrap_or_else(|_| WorkspaceList {
         workspaces: Vec::new(),
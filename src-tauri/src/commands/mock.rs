@@ -0,0 +1,41 @@
+use crate::models::{MockRuleSet, MockServerStatus, Workspace};
+use crate::services::MockRuleStore;
+use crate::state::AppState;
+use tauri::State;
+
+/// 读取工作区的 Mock 规则（托管实例从 .zebras/mocks 读取）
+#[tauri::command]
+pub async fn get_mock_rules(workspace: Workspace) -> Result<Vec<MockRuleSet>, String> {
+    MockRuleStore::load(&workspace)
+}
+
+/// 保存 Mock 规则，Mock 服务运行中时会自动重新加载
+#[tauri::command]
+pub async fn update_mock_rules(
+    mut workspace: Workspace,
+    rule_sets: Vec<MockRuleSet>,
+) -> Result<Workspace, String> {
+    MockRuleStore::save(&mut workspace, rule_sets)?;
+    Ok(workspace)
+}
+
+#[tauri::command]
+pub async fn start_mock_server(
+    workspace: Workspace,
+    state: State<'_, AppState>,
+) -> Result<MockServerStatus, String> {
+    state.mock_server.start(&workspace).await
+}
+
+#[tauri::command]
+pub async fn stop_mock_server(state: State<'_, AppState>) -> Result<MockServerStatus, String> {
+    state.mock_server.stop().await;
+    Ok(state.mock_server.status().await)
+}
+
+#[tauri::command]
+pub async fn get_mock_server_status(
+    state: State<'_, AppState>,
+) -> Result<MockServerStatus, String> {
+    Ok(state.mock_server.status().await)
+}
@@ -1,17 +1,27 @@
+pub mod capture;
 pub mod debug;
+pub mod gateway;
 pub mod git;
 pub mod managed_project;
+pub mod mock;
 pub mod port;
 pub mod process;
 pub mod project;
+pub mod registry;
+pub mod template;
 pub mod terminal;
 pub mod workspace;
 
+pub use capture::*;
 pub use debug::*;
+pub use gateway::*;
 pub use git::*;
 pub use managed_project::*;
+pub use mock::*;
 pub use port::*;
 pub use process::*;
 pub use project::*;
+pub use registry::*;
+pub use template::*;
 pub use terminal::*;
 pub use workspace::*;
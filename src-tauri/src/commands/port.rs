@@ -1,88 +1,286 @@
-use crate::models::{PortChange, ProjectInfo};
-use crate::services::{PortManager, WorkspaceList, WorkspaceService};
-use crate::utils::port_checker::is_port_available;
-use std::collections::HashSet;
+use crate::commands::process::stop_running_process;
+use crate::commands::workspace::rescan_workspace_project;
+use crate::models::{
+    PortAvailability, PortChange, PortChangeBackup, PortCheckMode, PortInspection,
+    PortOverrideBackup, PortOwnership, PortStrategy, ProjectInfo, Workspace,
+};
+use crate::services::config_parser::ConfigParser;
+use crate::services::{
+    PortBackupStore, PortInspector, PortManager, PortRegistry, WorkspaceList, WorkspaceService,
+};
+use crate::state::AppState;
+use crate::utils::kill_process_tree;
+use crate::utils::port_checker::{check_port, is_port_available};
+use tauri::State;
 
 #[tauri::command]
 pub async fn check_port_available(port: u16) -> Result<bool, String> {
     Ok(is_port_available(port))
 }
 
+/// 返回端口在各个地址上的详细检测结果
 #[tauri::command]
-pub async fn resolve_port_conflicts(
+pub async fn check_port_availability(
+    port: u16,
+    mode: Option<PortCheckMode>,
+) -> Result<PortAvailability, String> {
+    Ok(check_port(port, mode.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub async fn inspect_port(port: u16, state: State<'_, AppState>) -> Result<PortInspection, String> {
+    let running: Vec<_> = state
+        .running_processes
+        .lock()
+        .await
+        .values()
+        .cloned()
+        .collect();
+    let workspaces = load_all_workspaces();
+    Ok(PortInspector::inspect(port, &running, &workspaces))
+}
+
+/// 结束占用端口的进程
+/// expected_pid: 用户在界面上确认过的占用进程，若占用者已变化则拒绝操作
+#[tauri::command]
+pub async fn free_port(
+    port: u16,
+    expected_pid: u32,
+    state: State<'_, AppState>,
+) -> Result<PortInspection, String> {
+    let running: Vec<_> = state
+        .running_processes
+        .lock()
+        .await
+        .values()
+        .cloned()
+        .collect();
+    let workspaces = load_all_workspaces();
+    let inspection = PortInspector::inspect(port, &running, &workspaces);
+
+    let owner = inspection
+        .owners
+        .iter()
+        .find(|owner| owner.pid == expected_pid)
+        .ok_or_else(|| format!("端口 {} 的占用进程已变化，请重新确认", port))?;
+
+    match &owner.ownership {
+        PortOwnership::LauncherProcess { process_id, .. } => {
+            stop_running_process(process_id, &state).await?;
+        }
+        _ => kill_process_tree(owner.pid)?,
+    }
+
+    // 等待系统回收端口
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let running: Vec<_> = state
+        .running_processes
+        .lock()
+        .await
+        .values()
+        .cloned()
+        .collect();
+    Ok(PortInspector::inspect(port, &running, &workspaces))
+}
+
+/// 生成端口调整方案，只返回调整后的项目和变更列表，不修改配置文件
+#[tauri::command]
+pub async fn plan_port_changes(
     current_workspace_id: String,
     mut projects: Vec<ProjectInfo>,
     port_range_start: u16,
     port_range_end: u16,
+    check_mode: Option<PortCheckMode>,
+    port_strategy: Option<PortStrategy>,
 ) -> Result<(Vec<ProjectInfo>, Vec<PortChange>), String> {
     let mut manager = PortManager::new(port_range_start, port_range_end);
+    manager.set_check_mode(check_mode.unwrap_or_default());
+    manager.set_strategy(port_strategy.unwrap_or(PortStrategy::Sequential));
 
-    // 收集其他工作区的端口使用情况（排除当前工作区）
-    let global_used_ports = collect_global_used_ports(&current_workspace_id)?;
+    // 清理已删除工作区的租约
+    let workspace_ids: Vec<String> = WorkspaceList::load()
+        .map(|list| list.workspaces.into_iter().map(|w| w.id).collect())
+        .unwrap_or_default();
 
-    println!(
-        "[PortManager] 检测到其他工作区已使用端口: {:?}",
-        global_used_ports
-    );
+    let changes = PortRegistry::update(|registry| {
+        registry.retain_workspaces(&workspace_ids, &current_workspace_id);
+        manager.resolve_conflicts(&mut projects, registry, &current_workspace_id)
+    })?;
 
-    let changes = manager.resolve_conflicts(&mut projects, &global_used_ports)?;
+    Ok((projects, changes))
+}
 
-    // 应用端口变更到配置文件
-    if !changes.is_empty() {
-        PortManager::apply_port_changes(&changes, &projects)?;
-    }
+/// 应用端口调整方案：备份原本地配置文件后写入新端口
+/// projects 为方案中调整后的项目列表
+#[tauri::command]
+pub async fn apply_port_changes(
+    current_workspace_id: String,
+    projects: Vec<ProjectInfo>,
+    changes: Vec<PortChange>,
+    strict_json: Option<bool>,
+) -> Result<PortChangeBackup, String> {
+    PortRegistry::update(|registry| {
+        // 生成方案后端口状态可能已变化
+        for change in changes.iter() {
+            if registry.is_claimed_by_others(&current_workspace_id, change.new_port)
+                || !is_port_available(change.new_port)
+            {
+                return Err(format!(
+                    "端口 {} 已不可用，请重新生成端口调整方案",
+                    change.new_port
+                ));
+            }
+        }
 
-    Ok((projects, changes))
+        let files =
+            PortManager::apply_port_changes(&changes, &projects, strict_json.unwrap_or(false))?;
+        // 自动识别的项目没有配置文件可写，新端口记入工作区设置
+        let overrides = match save_port_overrides(&current_workspace_id, &changes, &projects) {
+            Ok(overrides) => overrides,
+            Err(e) => {
+                let _ = PortManager::restore_backups(&files);
+                return Err(e);
+            }
+        };
+
+        let backup = PortChangeBackup {
+            id: uuid::Uuid::new_v4().to_string(),
+            workspace_id: current_workspace_id.clone(),
+            created_at: chrono::Utc::now(),
+            changes,
+            files,
+            overrides,
+        };
+        if let Err(e) = PortBackupStore::save(&backup) {
+            // 没有备份就无法回滚，撤销本次修改
+            let _ = PortManager::restore_backups(&backup.files);
+            let _ = restore_port_overrides(&backup);
+            return Err(e);
+        }
+
+        registry.sync_configured(&current_workspace_id, &projects);
+        Ok(backup)
+    })
 }
 
-/// 收集其他工作区的端口使用情况（排除当前工作区）
-fn collect_global_used_ports(current_workspace_id: &str) -> Result<HashSet<u16>, String> {
-    let mut used_ports = HashSet::new();
+/// 回滚一次端口变更，将本地配置文件和端口覆盖恢复为应用前的内容，并重新解析涉及的项目
+#[tauri::command]
+pub async fn rollback_port_changes(backup_id: String) -> Result<PortChangeBackup, String> {
+    let backup = PortBackupStore::load(&backup_id)?;
 
-    // 加载所有工作区列表
-    let workspace_list = WorkspaceList::load().unwrap_or_else(|_| WorkspaceList {
-        workspaces: Vec::new(),
+    PortManager::restore_backups(&backup.files)?;
+    restore_port_overrides(&backup)?;
+    PortBackupStore::delete(&backup_id)?;
+
+    // 端口登记表中的配置端口随项目一起恢复（失败只记录日志，配置文件已恢复）
+    if let Err(e) = rescan_rolled_back_projects(&backup) {
+        eprintln!("[PortRegistry] 回滚后同步工作区失败: {}", e);
+    }
+
+    Ok(backup)
+}
+
+/// 把没有本地配置文件的项目的新端口写入工作区设置，没有这类项目时不修改工作区
+fn save_port_overrides(
+    workspace_id: &str,
+    changes: &[PortChange],
+    projects: &[ProjectInfo],
+) -> Result<Vec<PortOverrideBackup>, String> {
+    let needs_override = changes.iter().any(|change| {
+        projects
+            .iter()
+            .any(|p| p.id == change.project_id && ConfigParser::local_config_path(p).is_none())
     });
+    if !needs_override {
+        return Ok(Vec::new());
+    }
 
-    println!(
-        "[PortManager] 找到 {} 个工作区",
-        workspace_list.workspaces.len()
-    );
-
-    // 遍历每个工作区
-    for workspace_ref in workspace_list.workspaces.iter() {
-        // 跳过当前工作区
-        if workspace_ref.id == current_workspace_id {
-            println!("[PortManager] 跳过当前工作区 '{}'", workspace_ref.name);
-            continue;
-        }
+    let overrides = WorkspaceService::update(workspace_id, |workspace| {
+        Ok(Some(PortManager::apply_port_overrides(
+            changes,
+            projects,
+            &mut workspace.settings,
+        )))
+    })?;
+    Ok(overrides.unwrap_or_default())
+}
 
-        // 加载工作区配置
-        match WorkspaceService::load_workspace(&workspace_ref.config_path) {
-            Ok(workspace) => {
-                println!(
-                    "[PortManager] 加载其他工作区 '{}': {} 个项目",
-                    workspace.name,
-                    workspace.projects.len()
-                );
-
-                // 收集该工作区所有项目的端口
-                for project in workspace.projects.iter() {
-                    if project.is_valid {
-                        used_ports.insert(project.port);
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!(
-                    "[PortManager] 加载工作区 '{}' 失败: {}",
-                    workspace_ref.name, e
-                );
-                // 继续处理其他工作区
-                continue;
+fn restore_port_overrides(backup: &PortChangeBackup) -> Result<(), String> {
+    if backup.overrides.is_empty() {
+        return Ok(());
+    }
+    WorkspaceService::update(&backup.workspace_id, |workspace| {
+        PortManager::restore_port_overrides(&backup.overrides, &mut workspace.settings);
+        Ok(Some(()))
+    })?;
+    Ok(())
+}
+
+/// 按恢复后的配置文件重新解析变更涉及的项目，保存工作区并同步端口登记表
+fn rescan_rolled_back_projects(backup: &PortChangeBackup) -> Result<(), String> {
+    let workspace = WorkspaceService::update(&backup.workspace_id, |workspace| {
+        for change in backup.changes.iter() {
+            if workspace.projects.iter().any(|p| p.id == change.project_id) {
+                rescan_workspace_project(workspace, &change.project_id)?;
             }
         }
+        workspace.last_modified = chrono::Utc::now();
+        Ok(Some(workspace.clone()))
+    })?;
+    if let Some(workspace) = workspace {
+        sync_port_leases(&workspace);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_port_registry() -> Result<PortRegistry, String> {
+    PortRegistry::update(|registry| Ok(registry.clone()))
+}
+
+/// 为工作区预留端口段，之后该工作区只在此段内分配新端口
+#[tauri::command]
+pub async fn reserve_port_range(
+    workspace_id: String,
+    start: u16,
+    end: u16,
+) -> Result<PortRegistry, String> {
+    PortRegistry::update(|registry| {
+        registry.reserve_range(&workspace_id, start, end)?;
+        Ok(registry.clone())
+    })
+}
+
+#[tauri::command]
+pub async fn release_port_range(workspace_id: String) -> Result<PortRegistry, String> {
+    PortRegistry::update(|registry| {
+        registry.release_range(&workspace_id);
+        Ok(registry.clone())
+    })
+}
+
+/// 同步工作区的配置端口到登记表（失败只记录日志，不影响主流程）
+pub(crate) fn sync_port_leases(workspace: &Workspace) {
+    if let Err(e) = PortRegistry::update(|registry| {
+        registry.sync_workspace(workspace);
+        Ok(())
+    }) {
+        eprintln!("[PortRegistry] 同步工作区 '{}' 失败: {}", workspace.name, e);
     }
+}
+
+/// 加载所有工作区（加载失败的跳过）
+pub(crate) fn load_all_workspaces() -> Vec<Workspace> {
+    let workspace_list = WorkspaceList::load().unwrap_or_else(|_| WorkspaceList {
+        workspaces: Vec::new(),
+    });
 
-    Ok(used_ports)
+    workspace_list
+        .workspaces
+        .iter()
+        .filter_map(|workspace_ref| {
+            WorkspaceService::load_workspace(&workspace_ref.config_path).ok()
+        })
+        .collect()
 }
@@ -1,19 +1,33 @@
-use crate::models::{ProcessInfo, Workspace};
+use crate::commands::capture::{start_capture, stop_capture};
+use crate::models::{HealthStatus, ProcessInfo, ProjectInfo, StartCommand, Workspace};
+use crate::services::{HealthChecker, PortRegistry};
 use crate::state::AppState;
 use tauri::State;
 
 #[tauri::command]
 pub async fn start_project(
-    project_id: String,
-    project_name: String,
-    project_path: String,
+    project: ProjectInfo,
+    workspace_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<ProcessInfo, String> {
     let process_info = state
         .process_manager
-        .start_project(project_id, project_name, project_path)
+        .start_project(
+            project.id.clone(),
+            project.name.clone(),
+            project.path.to_string_lossy().to_string(),
+            project
+                .start_command
+                .clone()
+                .unwrap_or_else(StartCommand::npm_start)
+                .with_port(project.port),
+        )
         .await?;
 
+    if let Some(workspace_id) = workspace_id {
+        lease_running_port(&workspace_id, &project, &process_info);
+    }
+
     // 保存到全局状态
     state
         .running_processes
@@ -21,17 +35,14 @@ pub async fn start_project(
         .await
         .insert(process_info.process_id.clone(), process_info.clone());
 
+    attach_running_services(&project, &process_info, &state).await;
+
     Ok(process_info)
 }
 
 #[tauri::command]
 pub async fn stop_project(process_id: String, state: State<'_, AppState>) -> Result<(), String> {
-    state.process_manager.stop_project(&process_id).await?;
-
-    // 从全局状态移除
-    state.running_processes.lock().await.remove(&process_id);
-
-    Ok(())
+    stop_running_process(&process_id, &state).await
 }
 
 #[tauri::command]
@@ -43,9 +54,20 @@ pub async fn get_running_processes(state: State<'_, AppState>) -> Result<Vec<Pro
 #[tauri::command]
 pub async fn stop_all_projects(state: State<'_, AppState>) -> Result<(), String> {
     state.process_manager.stop_all().await?;
+    state.health_monitor.stop_all().await;
+    state.gateway.clear_routes();
+    state.capture.stop_all().await;
+    state.app_registry.clear();
 
     // 清空全局状态
-    state.running_processes.lock().await.clear();
+    let project_ids: Vec<String> = state
+        .running_processes
+        .lock()
+        .await
+        .drain()
+        .map(|(_, process_info)| process_info.project_id)
+        .collect();
+    release_running_ports(&project_ids);
 
     Ok(())
 }
@@ -74,16 +96,24 @@ pub async fn start_all_projects(
                 project.id.clone(),
                 project.name.clone(),
                 project.path.to_string_lossy().to_string(),
+                project
+                    .start_command
+                    .clone()
+                    .unwrap_or_else(StartCommand::npm_start)
+                    .with_port(project.port),
             )
             .await
         {
             Ok(process_info) => {
+                lease_running_port(&workspace.id, project, &process_info);
+
                 // 保存到全局状态
                 state
                     .running_processes
                     .lock()
                     .await
                     .insert(process_info.process_id.clone(), process_info.clone());
+                attach_running_services(project, &process_info, &state).await;
                 started_processes.push(process_info);
             }
             Err(e) => {
@@ -97,6 +127,107 @@ pub async fn start_all_projects(
     Ok(started_processes)
 }
 
+/// 立即对项目执行一次健康检查
+#[tauri::command]
+pub async fn check_project_health(project: ProjectInfo) -> Result<HealthStatus, String> {
+    let config = project
+        .health_check
+        .ok_or_else(|| format!("项目 {} 未配置健康检查", project.name))?;
+
+    Ok(HealthChecker::probe(&config, project.port).await)
+}
+
+/// 项目启动后接入健康检查、开发网关、抓包代理和子应用注册表
+async fn attach_running_services(
+    project: &ProjectInfo,
+    process_info: &ProcessInfo,
+    state: &State<'_, AppState>,
+) {
+    start_health_monitor(project, process_info, state).await;
+    state.gateway.add_route(process_info, project);
+    if let Err(e) = start_capture(project, state).await {
+        eprintln!("[CaptureProxy] {}", e);
+    }
+    let gateway_url = state.gateway.entry_urls().await.remove(&project.id);
+    state
+        .app_registry
+        .register(process_info, project, gateway_url);
+}
+
+/// 停止进程并撤下 attach_running_services 接入的服务，释放运行端口
+pub(crate) async fn stop_running_process(
+    process_id: &str,
+    state: &State<'_, AppState>,
+) -> Result<(), String> {
+    state.process_manager.stop_project(process_id).await?;
+    state.health_monitor.stop(process_id).await;
+    state.gateway.remove_route(process_id);
+    state.app_registry.unregister(process_id);
+
+    // 从全局状态移除
+    let removed = state.running_processes.lock().await.remove(process_id);
+    if let Some(process_info) = removed {
+        stop_capture(&process_info.project_id, state).await;
+        release_running_ports(&[process_info.project_id]);
+    }
+
+    Ok(())
+}
+
+/// 项目配置了健康检查时开始定时检查
+async fn start_health_monitor(
+    project: &ProjectInfo,
+    process_info: &ProcessInfo,
+    state: &State<'_, AppState>,
+) {
+    let Some(config) = project.health_check.clone() else {
+        return;
+    };
+
+    // 没有端口且未指定地址时无从检查
+    if project.port == 0 && config.base_url.is_none() {
+        return;
+    }
+
+    state
+        .health_monitor
+        .start(
+            process_info,
+            config,
+            project.port,
+            state.running_processes.clone(),
+        )
+        .await;
+}
+
+/// 在端口登记表中记录运行中的端口（失败只记录日志，不影响启动）
+fn lease_running_port(workspace_id: &str, project: &ProjectInfo, process_info: &ProcessInfo) {
+    if let Err(e) = PortRegistry::update(|registry| {
+        registry.lease_running(workspace_id, project, process_info.pid);
+        Ok(())
+    }) {
+        eprintln!(
+            "[PortRegistry] 记录项目 {} 的运行端口失败: {}",
+            project.name, e
+        );
+    }
+}
+
+fn release_running_ports(project_ids: &[String]) {
+    if project_ids.is_empty() {
+        return;
+    }
+
+    if let Err(e) = PortRegistry::update(|registry| {
+        for project_id in project_ids {
+            registry.release_running(project_id);
+        }
+        Ok(())
+    }) {
+        eprintln!("[PortRegistry] 释放运行端口失败: {}", e);
+    }
+}
+
 #[tauri::command]
 pub async fn run_project_task(
     project_id: String,
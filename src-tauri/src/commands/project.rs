@@ -1,17 +1,20 @@
-use crate::models::ProjectInfo;
-use crate::services::ProjectScanner;
+use crate::models::{MigrationPlan, ProjectConfig, ProjectInfo, WorkspaceSettings, ZebrasVersion};
+use crate::services::{ConfigLayers, ConfigMigration, ConfigTreeBuilder, ProjectScanner};
 use std::path::PathBuf;
 
 #[tauri::command]
 pub async fn get_project_details(project_path: String) -> Result<ProjectInfo, String> {
     let path = PathBuf::from(project_path);
-    ProjectScanner::rescan_project(&path)
+    ProjectScanner::rescan_project(&path, &WorkspaceSettings::default())
 }
 
 #[tauri::command]
-pub async fn rescan_project(project_path: String) -> Result<ProjectInfo, String> {
+pub async fn rescan_project(
+    project_path: String,
+    settings: Option<WorkspaceSettings>,
+) -> Result<ProjectInfo, String> {
     let path = PathBuf::from(project_path);
-    ProjectScanner::rescan_project(&path)
+    ProjectScanner::rescan_project(&path, &settings.unwrap_or_default())
 }
 
 #[tauri::command]
@@ -19,3 +22,35 @@ pub async fn is_zebras_project(project_path: String) -> Result<bool, String> {
     let path = PathBuf::from(project_path);
     Ok(ProjectScanner::is_zebras_project(&path))
 }
+
+/// 读取 main、环境覆盖层与 local 合并后的完整配置，每个值标注来源文件和行号
+#[tauri::command]
+pub async fn get_project_config(
+    project_path: String,
+    project_version: ZebrasVersion,
+    config_env: Option<String>,
+) -> Result<ProjectConfig, String> {
+    ConfigTreeBuilder::load(
+        &PathBuf::from(project_path),
+        &project_version,
+        config_env.as_deref(),
+    )
+}
+
+/// 列出项目目录中可选的环境覆盖层
+#[tauri::command]
+pub async fn list_project_config_envs(
+    project_path: String,
+    project_version: ZebrasVersion,
+) -> Result<Vec<String>, String> {
+    Ok(ConfigLayers::available_envs(
+        &PathBuf::from(project_path),
+        &project_version,
+    ))
+}
+
+/// 预览 v2 → v3 迁移将生成、修改和删除的文件，不写入任何内容
+#[tauri::command]
+pub async fn preview_v2_migration(project_path: String) -> Result<MigrationPlan, String> {
+    ConfigMigration::plan(&PathBuf::from(project_path))
+}
@@ -0,0 +1,29 @@
+use crate::models::AppRegistryStatus;
+use crate::state::AppState;
+use tauri::State;
+
+/// 启动子应用注册表服务：GET /registry.json 返回运行中的子应用，GET /events 推送变化
+#[tauri::command]
+pub async fn start_app_registry(
+    port: u16,
+    state: State<'_, AppState>,
+) -> Result<AppRegistryStatus, String> {
+    if port == 0 {
+        return Err("注册表端口无效".to_string());
+    }
+
+    state.app_registry.start(port).await
+}
+
+#[tauri::command]
+pub async fn stop_app_registry(state: State<'_, AppState>) -> Result<AppRegistryStatus, String> {
+    state.app_registry.stop().await;
+    Ok(state.app_registry.status().await)
+}
+
+#[tauri::command]
+pub async fn get_app_registry_status(
+    state: State<'_, AppState>,
+) -> Result<AppRegistryStatus, String> {
+    Ok(state.app_registry.status().await)
+}
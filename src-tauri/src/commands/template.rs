@@ -0,0 +1,58 @@
+use crate::commands::port::sync_port_leases;
+use crate::models::{
+    ProjectTemplate, ScaffoldRequest, ScaffoldResult, Workspace, WorkspaceSourceType,
+};
+use crate::services::{
+    GitManager, PortManager, PortRegistry, ProjectScanner, TemplateService, WorkspaceService,
+};
+
+#[tauri::command]
+pub async fn list_project_templates() -> Result<Vec<ProjectTemplate>, String> {
+    Ok(TemplateService::list())
+}
+
+/// 从模板创建项目：分配空闲端口、渲染模板、可选 git init，然后加入当前工作区；
+/// git init 失败不影响项目加入工作区，作为警告返回
+#[tauri::command]
+pub async fn create_project_from_template(
+    mut workspace: Workspace,
+    request: ScaffoldRequest,
+) -> Result<ScaffoldResult, String> {
+    if workspace.source_type == WorkspaceSourceType::ManagedProject {
+        return Err("受管项目实例不支持从模板创建项目".to_string());
+    }
+    if !workspace.folders.contains(&request.folder) {
+        return Err("只能在工作区的文件夹中创建项目".to_string());
+    }
+    TemplateService::validate_name(&request.name)?;
+    TemplateService::validate_domain(request.domain.as_deref())?;
+    let template = TemplateService::find(&request.template_id, request.source)?;
+
+    let mut manager = PortManager::new(
+        workspace.settings.port_range_start,
+        workspace.settings.port_range_end,
+    );
+    let port = PortRegistry::update(|registry| {
+        manager
+            .allocate_port(&workspace.projects, registry, &workspace.id)
+            .ok_or("端口段内没有可用端口".to_string())
+    })?;
+
+    let path = TemplateService::scaffold(&template, &request, port)?;
+    let warning = if request.git_init {
+        GitManager::init_repo(&path.to_string_lossy())
+            .err()
+            .map(|e| format!("项目已创建在 {}，但 git init 失败: {}", path.display(), e))
+    } else {
+        None
+    };
+
+    let project = ProjectScanner::rescan_project(&path, &workspace.settings)?;
+    workspace.projects.push(project);
+
+    // 保存工作区
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
+
+    Ok(ScaffoldResult { workspace, warning })
+}
@@ -1,8 +1,15 @@
-use crate::models::{Workspace, WorkspaceSourceType};
+use crate::commands::port::sync_port_leases;
+use crate::models::{
+    HealthCheckConfig, MigrationPlan, ProjectSourceType, RescanResult, ScanSettings, Workspace,
+    WorkspaceSettings, WorkspaceSourceType, ZebrasVersion,
+};
 use crate::services::{
-    ManagedProjectService, ProjectScanner, WorkspaceList, WorkspaceRef, WorkspaceService,
+    ConfigLayers, ConfigMigration, ManagedProjectService, PortRegistry, ProjectScanner, ScanFilter,
+    WorkspaceList, WorkspaceRef, WorkspaceService,
 };
+use crate::state::AppState;
 use std::path::PathBuf;
+use tauri::State;
 
 #[tauri::command]
 pub async fn create_workspace(name: String, folders: Vec<String>) -> Result<Workspace, String> {
@@ -28,10 +35,12 @@ pub async fn create_workspace(name: String, folders: Vec<String>) -> Result<Work
     }
 
     // 自动扫描所有文件夹中的项目
-    workspace.projects = ProjectScanner::scan_folders(&workspace.folders, 3);
+    let (projects, _) = ProjectScanner::scan_folders(&workspace.folders, &workspace.settings);
+    workspace.projects = projects;
 
     // 保存工作区到用户目录
-    WorkspaceService::save_workspace(&workspace)?;
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
 
     // 添加到工作区列表
     let mut list = WorkspaceList::load().unwrap_or_else(|_| WorkspaceList {
@@ -50,8 +59,8 @@ pub async fn load_workspace(workspace_path: String) -> Result<Workspace, String>
     match WorkspaceService::load_workspace(&path) {
         Ok(workspace) => {
             if workspace.source_type == WorkspaceSourceType::ManagedProject {
-                let refreshed = ManagedProjectService::refresh_workspace(&workspace)?;
-                WorkspaceService::save_workspace(&refreshed)?;
+                let mut refreshed = ManagedProjectService::refresh_workspace(&workspace)?;
+                WorkspaceService::save_workspace(&mut refreshed)?;
                 Ok(refreshed)
             } else {
                 Ok(workspace)
@@ -68,8 +77,8 @@ pub async fn load_workspace(workspace_path: String) -> Result<Workspace, String>
                     let new_path = WorkspaceService::get_config_path(&ws_ref.id)?;
                     let workspace = WorkspaceService::load_workspace(&new_path)?;
                     if workspace.source_type == WorkspaceSourceType::ManagedProject {
-                        let refreshed = ManagedProjectService::refresh_workspace(&workspace)?;
-                        WorkspaceService::save_workspace(&refreshed)?;
+                        let mut refreshed = ManagedProjectService::refresh_workspace(&workspace)?;
+                        WorkspaceService::save_workspace(&mut refreshed)?;
                         return Ok(refreshed);
                     }
                     return Ok(workspace);
@@ -84,12 +93,45 @@ pub async fn load_workspace(workspace_path: String) -> Result<Workspace, String>
 #[tauri::command]
 pub async fn scan_workspace_projects(
     folders: Vec<String>,
+    settings: Option<WorkspaceSettings>,
 ) -> Result<Vec<crate::models::ProjectInfo>, String> {
     if folders.is_empty() {
         return Err("工作区文件夹列表为空".to_string());
     }
 
-    Ok(ProjectScanner::scan_folders(&folders, 3))
+    let (projects, _) = ProjectScanner::scan_folders(&folders, &settings.unwrap_or_default());
+    Ok(projects)
+}
+
+/// 重新扫描工作区文件夹并合并到已有项目，返回新增、移除和配置有变化的项目
+#[tauri::command]
+pub async fn rescan_workspace(mut workspace: Workspace) -> Result<RescanResult, String> {
+    if workspace.source_type == WorkspaceSourceType::ManagedProject {
+        return Err("受管项目实例请使用刷新实例".to_string());
+    }
+
+    let (scanned, stats) = ProjectScanner::scan_folders(&workspace.folders, &workspace.settings);
+    let (projects, mut report) = ProjectScanner::merge(&workspace.projects, scanned);
+    report.stats = stats;
+    workspace.projects = projects;
+    workspace.last_modified = chrono::Utc::now();
+
+    // 保存工作区
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
+
+    Ok(RescanResult { workspace, report })
+}
+
+/// 修改扫描设置后按新规则重新扫描
+#[tauri::command]
+pub async fn update_scan_settings(
+    mut workspace: Workspace,
+    scan: ScanSettings,
+) -> Result<RescanResult, String> {
+    ScanFilter::validate(&scan)?;
+    workspace.settings.scan = scan;
+    rescan_workspace(workspace).await
 }
 
 #[tauri::command]
@@ -109,11 +151,14 @@ pub async fn add_workspace_folder(
 
     workspace.add_folder(folder_path);
 
-    // 重新扫描所有文件夹
-    workspace.projects = ProjectScanner::scan_folders(&workspace.folders, 3);
+    // 重新扫描所有文件夹，合并到已有项目中；未变化的项目来自扫描缓存
+    let (scanned, _) = ProjectScanner::scan_folders(&workspace.folders, &workspace.settings);
+    let (projects, _) = ProjectScanner::merge(&workspace.projects, scanned);
+    workspace.projects = projects;
 
     // 保存工作区
-    WorkspaceService::save_workspace(&workspace)?;
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
 
     Ok(workspace)
 }
@@ -129,18 +174,27 @@ pub async fn remove_workspace_folder(
 
     workspace.remove_folder(&folder_path);
 
-    // 重新扫描所有文件夹
-    workspace.projects = ProjectScanner::scan_folders(&workspace.folders, 3);
+    // 只移除不再属于任何文件夹的项目，无需重新扫描
+    let folders = workspace.folders.clone();
+    workspace.projects.retain(|project| {
+        folders
+            .iter()
+            .any(|folder| project.path.starts_with(folder))
+    });
 
     // 保存工作区
-    WorkspaceService::save_workspace(&workspace)?;
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
 
     Ok(workspace)
 }
 
+/// 保存前端修改后的工作区，返回实际写入的内容（期间被文件监听更新过时会合并项目列表）
 #[tauri::command]
-pub async fn save_workspace(workspace: Workspace) -> Result<(), String> {
-    WorkspaceService::save_workspace(&workspace)
+pub async fn save_workspace(mut workspace: Workspace) -> Result<Workspace, String> {
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
+    Ok(workspace)
 }
 
 #[tauri::command]
@@ -154,6 +208,31 @@ pub async fn delete_workspace(workspace_id: String, _root_path: String) -> Resul
     });
     list.remove_workspace(&workspace_id)?;
 
+    // 释放该工作区占用的端口
+    if let Err(e) = PortRegistry::update(|registry| {
+        registry.remove_workspace(&workspace_id);
+        Ok(())
+    }) {
+        eprintln!("[PortRegistry] 释放工作区端口失败: {}", e);
+    }
+
+    Ok(())
+}
+
+/// 监听工作区文件夹，配置文件变化时自动重新扫描受影响的项目，替换之前的监听
+#[tauri::command]
+pub async fn watch_workspace(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let path = WorkspaceService::get_config_path(&workspace_id)?;
+    let workspace = WorkspaceService::load_workspace(&path)?;
+    state.workspace_watcher.watch(&workspace)
+}
+
+#[tauri::command]
+pub async fn unwatch_workspace(state: State<'_, AppState>) -> Result<(), String> {
+    state.workspace_watcher.unwatch();
     Ok(())
 }
 
@@ -184,7 +263,153 @@ pub async fn update_project_enabled(
     }
 
     // 保存工作区
-    WorkspaceService::save_workspace(&workspace)?;
+    WorkspaceService::save_workspace(&mut workspace)?;
+
+    Ok(workspace)
+}
+
+#[tauri::command]
+pub async fn update_project_health_check(
+    mut workspace: Workspace,
+    project_id: String,
+    health_check: Option<HealthCheckConfig>,
+) -> Result<Workspace, String> {
+    if let Some(config) = health_check.as_ref() {
+        if let Some(pattern) = config.body_pattern.as_deref() {
+            regex::Regex::new(pattern).map_err(|e| format!("响应体正则无效: {}", e))?;
+        }
+    }
+
+    // 查找并更新项目的健康检查配置
+    if let Some(project) = workspace.projects.iter_mut().find(|p| p.id == project_id) {
+        project.health_check = health_check;
+    } else {
+        return Err("未找到指定的项目".to_string());
+    }
+
+    // 保存工作区
+    WorkspaceService::save_workspace(&mut workspace)?;
+
+    Ok(workspace)
+}
+
+/// 切换项目的配置环境并重新解析该项目，None 表示不使用环境覆盖层
+#[tauri::command]
+pub async fn update_project_config_env(
+    mut workspace: Workspace,
+    project_id: String,
+    config_env: Option<String>,
+) -> Result<Workspace, String> {
+    if let Some(env) = config_env.as_deref() {
+        if !ConfigLayers::is_valid_env(env) {
+            return Err(format!("无效的环境名: {}", env));
+        }
+    }
+
+    let project = workspace
+        .projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .ok_or("未找到指定的项目".to_string())?;
+    if project.source_type != ProjectSourceType::Zebras {
+        return Err("只有 Zebras 项目支持切换配置环境".to_string());
+    }
+
+    let key = project.path.to_string_lossy().to_string();
+    match config_env {
+        Some(env) => workspace.settings.config_envs.insert(key, env),
+        None => workspace.settings.config_envs.remove(&key),
+    };
+    rescan_workspace_project(&mut workspace, &project_id)?;
+
+    // 保存工作区
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
+
+    Ok(workspace)
+}
+
+/// 固定项目按 v2 或 v3 解析，None 表示恢复自动识别；固定结果保存在工作区设置中，重新扫描后仍然生效
+#[tauri::command]
+pub async fn update_project_version_pin(
+    mut workspace: Workspace,
+    project_id: String,
+    version: Option<ZebrasVersion>,
+) -> Result<Workspace, String> {
+    let project = workspace
+        .projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .ok_or("未找到指定的项目".to_string())?;
+    if project.source_type != ProjectSourceType::Zebras {
+        return Err("只有 Zebras 项目支持固定版本".to_string());
+    }
+
+    let key = project.path.to_string_lossy().to_string();
+    match version {
+        Some(ZebrasVersion::Managed) => return Err("只能固定为 v2 或 v3".to_string()),
+        Some(version) => {
+            if !ConfigLayers::has_config(&project.path, &version) {
+                let main_file = ConfigLayers::main_file(&project.path, &version);
+                return Err(format!("项目中没有 {}", main_file.display()));
+            }
+            workspace.settings.version_pins.insert(key, version);
+        }
+        None => {
+            workspace.settings.version_pins.remove(&key);
+        }
+    }
+    rescan_workspace_project(&mut workspace, &project_id)?;
+
+    // 保存工作区
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
+
+    Ok(workspace)
+}
+
+/// 应用用户确认过的 v2 → v3 迁移，并按 v3 重新解析该项目
+#[tauri::command]
+pub async fn apply_v2_migration(
+    mut workspace: Workspace,
+    project_id: String,
+    plan: MigrationPlan,
+) -> Result<Workspace, String> {
+    let project = workspace
+        .projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .ok_or("未找到指定的项目".to_string())?;
+    if project.path != plan.project_path {
+        return Err("迁移预览与项目不匹配".to_string());
+    }
+
+    ConfigMigration::apply(&plan)?;
+    // 迁移后只剩 v3 配置，之前固定的版本不再需要
+    let key = project.path.to_string_lossy().to_string();
+    workspace.settings.version_pins.remove(&key);
+    rescan_workspace_project(&mut workspace, &project_id)?;
+
+    // 保存工作区
+    WorkspaceService::save_workspace(&mut workspace)?;
+    sync_port_leases(&workspace);
 
     Ok(workspace)
 }
+
+/// 按当前工作区设置重新解析单个项目，保留用户在启动器中设置的字段
+pub(crate) fn rescan_workspace_project(
+    workspace: &mut Workspace,
+    project_id: &str,
+) -> Result<(), String> {
+    let project = workspace
+        .projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or("未找到指定的项目".to_string())?;
+
+    let mut rescanned = ProjectScanner::rescan_project(&project.path, &workspace.settings)?;
+    rescanned.inherit_user_state(project);
+    *project = rescanned;
+    Ok(())
+}
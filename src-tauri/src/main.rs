@@ -9,6 +9,7 @@ mod services;
 mod state;
 mod utils;
 
+use services::PortRegistry;
 use state::AppState;
 use tauri::Manager;
 
@@ -18,6 +19,14 @@ fn main() {
             let window = app.get_window("main").unwrap();
             app.manage(AppState::new(window.clone()));
 
+            // 上次会话的进程已在退出时停止，清理遗留的运行租约
+            if let Err(e) = PortRegistry::update(|registry| {
+                registry.release_all_running();
+                Ok(())
+            }) {
+                eprintln!("[PortRegistry] 清理运行租约失败: {}", e);
+            }
+
             // 监听窗口关闭事件，自动清理进程
             let app_handle = app.handle();
             window.on_window_event(move |event| {
@@ -26,6 +35,11 @@ fn main() {
                     // 阻塞式停止所有进程
                     tauri::async_runtime::block_on(async {
                         let _ = state.process_manager.stop_all().await;
+                        state.health_monitor.stop_all().await;
+                        state.gateway.stop().await;
+                        state.capture.stop_all().await;
+                        state.mock_server.stop().await;
+                        state.app_registry.stop().await;
                         let _ = state.terminal_manager.stop_all().await;
                     });
                 }
@@ -45,17 +59,39 @@ fn main() {
             commands::scan_workspace_projects,
             commands::save_workspace,
             commands::delete_workspace,
+            commands::rescan_workspace,
+            commands::update_scan_settings,
             commands::add_workspace_folder,
             commands::remove_workspace_folder,
+            commands::watch_workspace,
+            commands::unwatch_workspace,
             commands::get_workspace_list,
             commands::update_project_enabled,
+            commands::update_project_health_check,
+            commands::update_project_config_env,
+            commands::update_project_version_pin,
+            commands::apply_v2_migration,
             // Port commands
             commands::check_port_available,
-            commands::resolve_port_conflicts,
+            commands::check_port_availability,
+            commands::plan_port_changes,
+            commands::apply_port_changes,
+            commands::rollback_port_changes,
+            commands::inspect_port,
+            commands::free_port,
+            commands::get_port_registry,
+            commands::reserve_port_range,
+            commands::release_port_range,
             // Project commands
             commands::get_project_details,
             commands::rescan_project,
+            commands::get_project_config,
+            commands::list_project_config_envs,
+            commands::preview_v2_migration,
             commands::is_zebras_project,
+            // Template commands
+            commands::list_project_templates,
+            commands::create_project_from_template,
             // Process commands
             commands::start_project,
             commands::stop_project,
@@ -63,6 +99,28 @@ fn main() {
             commands::stop_all_projects,
             commands::start_all_projects,
             commands::run_project_task,
+            commands::check_project_health,
+            // Gateway commands
+            commands::start_gateway,
+            commands::stop_gateway,
+            commands::get_gateway_status,
+            commands::export_gateway_ca,
+            // Capture commands
+            commands::update_project_capture,
+            commands::get_capture_sessions,
+            commands::query_captures,
+            commands::clear_captures,
+            commands::export_captures_har,
+            // Mock commands
+            commands::get_mock_rules,
+            commands::update_mock_rules,
+            commands::start_mock_server,
+            commands::stop_mock_server,
+            commands::get_mock_server_status,
+            // Registry commands
+            commands::start_app_registry,
+            commands::stop_app_registry,
+            commands::get_app_registry_status,
             // Terminal commands
             commands::create_terminal_session,
             commands::get_terminal_sessions,
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 抓包代理配置，项目配置了该项时启动后自动在项目端口前挂一层代理
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CaptureConfig {
+    /// 代理监听端口，不填时自动分配
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub listen_port: Option<u16>,
+    /// 每个请求/响应体最多记录的字节数，超出部分截断
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+}
+
+fn default_max_body_bytes() -> usize {
+    64 * 1024
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            listen_port: None,
+            max_body_bytes: default_max_body_bytes(),
+        }
+    }
+}
+
+/// 运行中的抓包代理
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureSession {
+    pub project_id: String,
+    pub project_name: String,
+    pub listen_port: u16,
+    pub target_port: u16,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CapturedHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// 记录的请求/响应体；非 UTF-8 内容只记录大小
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CapturedBody {
+    /// 实际大小（字节）
+    pub size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// 超过大小上限被截断
+    pub truncated: bool,
+}
+
+/// 一次请求及其响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedExchange {
+    pub id: u64,
+    pub project_id: String,
+    pub project_name: String,
+    pub started_at: DateTime<Utc>,
+    /// 从收到请求到响应体结束的耗时
+    pub duration_ms: u64,
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub request_headers: Vec<CapturedHeader>,
+    pub request_body: CapturedBody,
+    /// 上游无响应时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub response_headers: Vec<CapturedHeader>,
+    pub response_body: CapturedBody,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 查询条件，均为可选
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CaptureFilter {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub method: Option<String>,
+    /// URL 包含的文本
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub url_contains: Option<String>,
+    /// 只返回状态码 >= 该值的记录（例如 400 只看错误）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub min_status: Option<u16>,
+    /// 最多返回最近的多少条
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub limit: Option<usize>,
+}
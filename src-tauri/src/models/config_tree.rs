@@ -0,0 +1,76 @@
+use super::ZebrasVersion;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// 配置值的来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    /// zebras.config.ts / zebra.json
+    Main,
+    /// 当前环境的覆盖层，例如 zebras.config.dev.ts / zebra.dev.json
+    Env,
+    /// zebras.config.local.ts / zebra.local.json
+    Local,
+    /// 配置中没有写，使用启动器的默认值
+    Default,
+}
+
+/// 合并后的配置树，每个节点记录来源文件和行号
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConfigTreeNode {
+    Object {
+        source: ConfigSource,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        line: Option<usize>,
+        entries: Vec<ConfigTreeEntry>,
+    },
+    /// 叶子节点；数组作为整体处理，local 中的数组会整体覆盖 main
+    Value {
+        value: Value,
+        source: ConfigSource,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        line: Option<usize>,
+        /// 无法静态求值的表达式原文（如 process.env.PORT），此时 value 为 null
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        expression: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigTreeEntry {
+    pub key: String,
+    pub node: ConfigTreeNode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigFile {
+    pub source: ConfigSource,
+    pub path: String,
+    pub exists: bool,
+}
+
+/// get_project_config 的返回值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    pub version: ZebrasVersion,
+    pub files: Vec<ConfigFile>,
+    pub tree: ConfigTreeNode,
+    /// 去掉来源信息后的合并结果
+    pub merged: Value,
+}
+
+impl ConfigTreeNode {
+    pub fn to_json(&self) -> Value {
+        match self {
+            ConfigTreeNode::Object { entries, .. } => Value::Object(
+                entries
+                    .iter()
+                    .map(|entry| (entry.key.clone(), entry.node.to_json()))
+                    .collect::<Map<String, Value>>(),
+            ),
+            ConfigTreeNode::Value { value, .. } => value.clone(),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    /// 配置无法使用，或启动器只能退回默认值
+    Error,
+    /// 配置可以使用，但结果可能与预期不符
+    Warning,
+}
+
+/// 配置校验发现的问题，行号和列号从 1 开始
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigDiagnostic {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub column: Option<usize>,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// 建议的修改方式
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub suggestion: Option<String>,
+}
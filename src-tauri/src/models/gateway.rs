@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// 开发网关的一条路由，由运行中的项目自动生成
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GatewayRoute {
+    pub process_id: String,
+    pub project_id: String,
+    pub project_name: String,
+    /// 按 Host 匹配，例如 order.localhost
+    pub host: String,
+    /// 按路径前缀匹配，例如 /order
+    pub path_prefix: String,
+    pub target_port: u16,
+    /// 基座项目作为未匹配请求的默认路由
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// 开启 HTTPS 时的监听端口
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub https_port: Option<u16>,
+    pub routes: Vec<GatewayRoute>,
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 项目的 HTTP 健康检查配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthCheckConfig {
+    #[serde(default = "default_health_path")]
+    pub path: String,
+    /// 期望的状态码，未设置时接受任意 2xx
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub expected_status: Option<u16>,
+    /// 响应体需要匹配的正则，例如入口 chunk 的文件名
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body_pattern: Option<String>,
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+    /// 覆盖默认的 http://127.0.0.1:<port>
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub base_url: Option<String>,
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// 响应耗时超过该值时视为降级
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub slow_threshold_ms: Option<u64>,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            path: default_health_path(),
+            expected_status: None,
+            body_pattern: None,
+            interval_secs: default_interval_secs(),
+            base_url: None,
+            timeout_ms: default_timeout_ms(),
+            slow_threshold_ms: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthState {
+    Healthy,
+    /// 有响应但内容不符合预期或响应过慢
+    Degraded,
+    /// 无法连接、超时或状态码不符合预期
+    Unhealthy,
+}
+
+/// 最近一次健康检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthStatus {
+    pub state: HealthState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// 健康状态变化事件
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthTransition {
+    pub process_id: String,
+    pub project_id: String,
+    pub project_name: String,
+    pub previous: Option<HealthState>,
+    pub current: HealthStatus,
+}
+
+fn default_health_path() -> String {
+    "/".to_string()
+}
+
+fn default_interval_secs() -> u64 {
+    10
+}
+
+fn default_timeout_ms() -> u64 {
+    3000
+}
@@ -13,6 +13,8 @@ pub enum WorkspaceSourceType {
 pub enum ProjectSourceType {
     Zebras,
     ManagedProject,
+    /// 通过项目识别器发现的非 Zebras 项目，例如 Vite、Spring Boot
+    Detected,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
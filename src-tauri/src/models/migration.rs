@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationChangeKind {
+    Create,
+    Modify,
+    Delete,
+}
+
+/// 迁移对单个文件的改动
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MigrationFileChange {
+    pub path: PathBuf,
+    pub kind: MigrationChangeKind,
+    /// 逐行对比，每行以 `+`、`-` 或空格开头
+    pub diff: String,
+    /// 改动后的完整内容，删除时为空
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub content: Option<String>,
+}
+
+/// v2 → v3 迁移预览，确认后原样应用
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MigrationPlan {
+    pub project_path: PathBuf,
+    pub changes: Vec<MigrationFileChange>,
+    /// 无法自动迁移、需要手动处理的内容
+    pub notes: Vec<String>,
+}
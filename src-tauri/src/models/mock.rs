@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 一组 Mock 规则，在独立端口上提供服务，开发服务器把接口代理到该端口
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MockRuleSet {
+    pub id: String,
+    pub name: String,
+    /// 所属项目，为空时是工作区级规则，项目规则未命中时会继续匹配工作区级规则
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub project_id: Option<String>,
+    pub port: u16,
+    /// 未命中规则时透传的上游地址，例如 http://127.0.0.1:8080
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub upstream: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<MockRule>,
+}
+
+/// 方法 + 路径模式 → 响应方式
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MockRule {
+    /// 为空时匹配所有方法
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub method: Option<String>,
+    /// 路径模式：`:id` 或 `*` 匹配一段，`**` 匹配剩余所有段，例如 /api/orders/:id
+    pub path: String,
+    pub action: MockAction,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MockAction {
+    /// 返回静态 JSON：file 为 JSON 文件路径（相对项目目录，托管实例相对 .zebras/mocks），
+    /// 或直接写 body
+    Static {
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        file: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        body: Option<serde_json::Value>,
+        #[serde(default = "default_status")]
+        status: u16,
+        #[serde(default)]
+        delay_ms: u64,
+        #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+        headers: HashMap<String, String>,
+    },
+    /// 透传到上游，为空时使用规则组的 upstream
+    PassThrough {
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        upstream: Option<String>,
+    },
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+/// 运行中的 Mock 服务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockServerStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+    /// 规则来源：工作区配置文件或托管实例的 .zebras/mocks 目录
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub rule_sets: Vec<MockRuleSet>,
+    /// 正在监听的端口
+    pub ports: Vec<u16>,
+    /// 最近一次加载规则失败的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
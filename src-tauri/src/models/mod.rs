@@ -1,13 +1,33 @@
+pub mod capture;
+pub mod config_tree;
+pub mod diagnostic;
+pub mod gateway;
 pub mod git_status;
+pub mod health;
 pub mod managed_project;
+pub mod migration;
+pub mod mock;
+pub mod port;
 pub mod process_info;
 pub mod project;
+pub mod registry;
+pub mod template;
 pub mod terminal;
 pub mod workspace;
 
+pub use capture::*;
+pub use config_tree::*;
+pub use diagnostic::*;
+pub use gateway::*;
 pub use git_status::*;
+pub use health::*;
 pub use managed_project::*;
+pub use migration::*;
+pub use mock::*;
+pub use port::*;
 pub use process_info::*;
 pub use project::*;
+pub use registry::*;
+pub use template::*;
 pub use terminal::*;
 pub use workspace::*;
@@ -0,0 +1,166 @@
+use super::PortChange;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 端口占用情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortInspection {
+    pub port: u16,
+    pub available: bool,
+    pub owners: Vec<PortOwner>,
+}
+
+/// 监听端口的进程
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortOwner {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub command_line: Option<String>,
+    pub cwd: Option<PathBuf>,
+    pub ownership: PortOwnership,
+}
+
+/// 占用者归属
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PortOwnership {
+    /// 由启动器启动的进程（或其子进程）
+    LauncherProcess {
+        process_id: String,
+        project_id: String,
+        project_name: String,
+    },
+    /// 工作目录位于某个已知项目内，但不是当前启动器启动的（例如遗留的 node 进程）
+    KnownProject {
+        workspace_id: String,
+        workspace_name: String,
+        project_id: String,
+        project_name: String,
+    },
+    /// 与启动器无关的进程
+    External,
+}
+
+/// 端口检测方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PortCheckMode {
+    /// 按开发服务器的方式尝试绑定
+    #[default]
+    Bind,
+    /// 尝试连接，判断是否已有服务在监听
+    Connect,
+    /// 同时使用以上两种方式
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PortProbe {
+    Bind,
+    Connect,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressStatus {
+    Available,
+    InUse,
+    /// 当前系统不支持该地址（例如未启用 IPv6），不影响结论
+    Unsupported,
+    /// 其他错误（例如权限不足），按不可用处理
+    Error,
+}
+
+/// 单个地址的检测结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressCheck {
+    pub address: String,
+    pub probe: PortProbe,
+    pub status: AddressStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 端口可用性报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortAvailability {
+    pub port: u16,
+    pub available: bool,
+    pub checks: Vec<AddressCheck>,
+}
+
+/// 端口租约类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaseType {
+    /// 项目配置文件中声明的端口
+    Configured,
+    /// 启动器正在运行的进程占用的端口
+    Running,
+}
+
+/// 端口登记记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortLease {
+    pub port: u16,
+    pub workspace_id: String,
+    pub project_id: String,
+    pub project_name: String,
+    pub lease_type: LeaseType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    pub updated_at: DateTime<Utc>,
+    /// 过期时间，None 表示直到释放前一直有效（运行中租约）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// 工作区独占的端口段
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PortRangeReservation {
+    pub workspace_id: String,
+    pub start: u16,
+    pub end: u16,
+}
+
+impl PortRangeReservation {
+    pub fn contains(&self, port: u16) -> bool {
+        self.start <= port && port <= self.end
+    }
+
+    pub fn overlaps(&self, start: u16, end: u16) -> bool {
+        self.start <= end && start <= self.end
+    }
+}
+
+/// 应用端口变更前备份的本地配置文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigFileBackup {
+    pub path: PathBuf,
+    /// 原始内容，None 表示应用前文件不存在
+    pub original: Option<String>,
+}
+
+/// 应用端口变更前工作区设置中的端口覆盖，对应没有本地配置文件的项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortOverrideBackup {
+    pub project_path: String,
+    /// 写入的新端口
+    pub port: u16,
+    /// 原来的覆盖端口，None 表示应用前没有覆盖
+    pub original: Option<u16>,
+}
+
+/// 一次端口变更的备份，用于回滚
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortChangeBackup {
+    pub id: String,
+    pub workspace_id: String,
+    pub created_at: DateTime<Utc>,
+    pub changes: Vec<PortChange>,
+    pub files: Vec<ConfigFileBackup>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub overrides: Vec<PortOverrideBackup>,
+}
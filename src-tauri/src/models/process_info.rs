@@ -1,3 +1,4 @@
+use super::HealthStatus;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +10,9 @@ pub struct ProcessInfo {
     pub status: ProcessStatus,
     pub started_at: DateTime<Utc>,
     pub pid: Option<u32>,
+    /// 最近一次 HTTP 健康检查结果，未配置健康检查时为空
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub health: Option<HealthStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -1,8 +1,13 @@
-use super::{ProjectSourceType, ProvisionStatus, RepoRole};
+use super::{
+    CaptureConfig, ConfigDiagnostic, HealthCheckConfig, PortAvailability, ProjectSourceType,
+    ProvisionStatus, RepoRole, Workspace,
+};
+use crate::utils::hash::fnv1a;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectInfo {
@@ -31,6 +36,57 @@ pub struct ProjectInfo {
     pub enabled: Option<bool>, // 是否在"全部启动"时启动，默认 true
     #[serde(default = "default_runnable")]
     pub runnable: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub health_check: Option<HealthCheckConfig>, // HTTP 健康检查配置
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub capture: Option<CaptureConfig>, // 抓包代理配置，为空时不抓包
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub diagnostics: Vec<ConfigDiagnostic>, // 配置校验结果
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub config_env: Option<String>, // 参与合并的环境覆盖层，例如 dev 对应 zebras.config.dev.ts
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub version_candidates: Vec<VersionCandidate>, // 非空表示 v2/v3 配置同时存在且无法判断版本
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub start_command: Option<StartCommand>, // 为空时使用 npm run start
+}
+
+/// 项目的启动命令，参数和环境变量中的 {port} 在启动时替换为项目端口
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StartCommand {
+    /// 以 ./ 开头时相对于项目目录，例如 ./mvnw
+    pub program: String,
+    pub args: Vec<String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub env: HashMap<String, String>,
+}
+
+impl StartCommand {
+    /// Zebras 项目的默认启动方式
+    pub fn npm_start() -> Self {
+        Self {
+            program: "npm".to_string(),
+            args: vec!["run".to_string(), "start".to_string()],
+            env: HashMap::new(),
+        }
+    }
+
+    /// 替换 {port} 占位符
+    pub fn with_port(&self, port: u16) -> Self {
+        let port = port.to_string();
+        Self {
+            program: self.program.clone(),
+            args: self
+                .args
+                .iter()
+                .map(|arg| arg.replace("{port}", &port))
+                .collect(),
+            env: self
+                .env
+                .iter()
+                .map(|(key, value)| (key.clone(), value.replace("{port}", &port)))
+                .collect(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -41,17 +97,56 @@ pub enum ZebrasVersion {
     Managed,
 }
 
+/// 按某个版本解析项目得到的结果，用于展示 v2/v3 冲突
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionCandidate {
+    pub version: ZebrasVersion,
+    pub config_file: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub name: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none", default)]
+    pub type_: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub domain: Option<String>,
+    /// 按该版本解析失败的原因
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortChange {
+    /// 旧版本生成的备份中没有该字段
+    #[serde(default)]
+    pub project_id: String,
     pub project_name: String,
     pub old_port: u16,
     pub new_port: u16,
+    pub reason: PortChangeReason,
+    /// 原端口被系统占用时的检测详情
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub availability: Option<PortAvailability>,
+}
+
+/// 端口需要调整的原因
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PortChangeReason {
+    /// 原端口被系统中的其他进程占用
+    OsOccupied,
+    /// 原端口已登记给其他工作区，或位于其他工作区预留的端口段内
+    ClaimedByOtherWorkspace,
+    /// 与同一工作区内的其他项目重复
+    DuplicateInWorkspace,
+    /// 哈希策略下调整到项目的首选端口
+    HashedPreference,
 }
 
 impl ProjectInfo {
     pub fn new(path: PathBuf, name: String) -> Self {
         Self {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: Self::stable_id(&path),
             path,
             version: ZebrasVersion::V3,
             source_type: ProjectSourceType::Zebras,
@@ -69,8 +164,68 @@ impl ProjectInfo {
             debug: None,
             enabled: Some(true), // 默认启用
             runnable: true,
+            health_check: None,
+            capture: None,
+            diagnostics: Vec::new(),
+            config_env: None,
+            version_candidates: Vec::new(),
+            start_command: None,
         }
     }
+
+    /// 由规范化后的项目路径计算稳定的 ID，同一目录多次扫描得到相同的 ID；
+    /// 路径不存在时按原样计算
+    pub fn stable_id(path: &Path) -> String {
+        let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        format!("{:016x}", fnv1a(canonical.to_string_lossy().bytes()))
+    }
+
+    /// 重新解析后沿用之前的 ID 和用户在启动器中设置的字段
+    pub fn inherit_user_state(&mut self, previous: &ProjectInfo) {
+        self.id = previous.id.clone();
+        self.enabled = previous.enabled;
+        self.health_check = previous.health_check.clone();
+        self.capture = previous.capture.clone();
+    }
+}
+
+/// 重新扫描时的项目变化
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RescanReport {
+    pub added: Vec<RescanEntry>,
+    pub removed: Vec<RescanEntry>,
+    pub changed: Vec<RescanEntry>,
+    #[serde(default)]
+    pub stats: ScanStats,
+}
+
+/// 一次扫描的统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanStats {
+    /// 遍历的目录数
+    pub dirs_visited: usize,
+    /// 识别到的项目数
+    pub projects: usize,
+    /// 配置文件未变化、直接使用缓存结果的项目数
+    pub cache_hits: usize,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RescanEntry {
+    pub id: String,
+    pub name: String,
+    pub path: PathBuf,
+    /// 发生变化的配置字段，仅 changed 中使用
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fields: Vec<String>,
+}
+
+/// 重新扫描后的工作区和变化明细
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RescanResult {
+    pub workspace: Workspace,
+    pub report: RescanReport,
 }
 
 fn default_project_source_type() -> ProjectSourceType {
@@ -0,0 +1,36 @@
+use super::ZebrasVersion;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 注册表中的一个运行中子应用，基座应用据此加载入口
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RegistryEntry {
+    pub project_id: String,
+    pub process_id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub version: ZebrasVersion,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    pub port: u16,
+    /// 入口地址：开发网关有该项目的路由时为网关地址，例如 https://order.localhost:8443/，
+    /// 否则为 http://localhost:8003/
+    pub entry_url: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// 注册表接口返回的内容，也是 SSE 推送的数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrySnapshot {
+    pub updated_at: DateTime<Utc>,
+    pub apps: Vec<RegistryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppRegistryStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    pub apps: Vec<RegistryEntry>,
+}
@@ -0,0 +1,46 @@
+use super::{Workspace, ZebrasVersion};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateSource {
+    /// 随启动器发布的内置模板
+    Bundled,
+    /// ~/.zebras-launcher/templates 下的用户模板
+    User,
+}
+
+/// 项目模板，文件中的 {{name}}、{{domain}}、{{port}} 在创建项目时替换
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTemplate {
+    pub id: String,
+    pub name: String,
+    pub version: ZebrasVersion,
+    pub source: TemplateSource,
+    /// 用户模板所在目录
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub path: Option<PathBuf>,
+}
+
+/// 从模板创建项目的参数，项目创建在 folder/name 下
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaffoldRequest {
+    pub template_id: String,
+    pub source: TemplateSource,
+    /// 工作区中的文件夹
+    pub folder: String,
+    pub name: String,
+    #[serde(default)]
+    pub domain: Option<String>,
+    #[serde(default)]
+    pub git_init: bool,
+}
+
+/// 创建项目后的工作区；项目已创建但 git init 失败时附带警告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaffoldResult {
+    pub workspace: Workspace,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub warning: Option<String>,
+}
@@ -1,6 +1,7 @@
-use super::{ProvisionStatus, WorkspaceSourceType};
+use super::{MockRuleSet, ProvisionStatus, WorkspaceSourceType, ZebrasVersion};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,8 +16,13 @@ pub struct Workspace {
     pub folders: Vec<String>, // 包含的多个代码文件夹路径
     pub created_at: DateTime<Utc>,
     pub last_modified: DateTime<Utc>,
+    /// 每次保存加一，保存时据此判断磁盘上的工作区是否已被其他写入方更新
+    #[serde(default)]
+    pub revision: u64,
     pub projects: Vec<super::ProjectInfo>,
     pub settings: WorkspaceSettings,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub mock_rule_sets: Vec<MockRuleSet>, // Mock 规则（托管实例保存在 .zebras/mocks）
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,13 +31,69 @@ pub struct WorkspaceSettings {
     pub port_strategy: PortStrategy,
     pub port_range_start: u16,
     pub port_range_end: u16,
+    /// 项目路径 → 所选的配置环境（zebras.config.<env>.ts / zebra.<env>.json）
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub config_envs: HashMap<String, String>,
+    /// 项目路径 → 固定使用的 Zebras 版本，v2/v3 配置同时存在时按此解析
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub version_pins: HashMap<String, ZebrasVersion>,
+    /// 写回 zebra*.json 时输出严格 JSON，去掉注释和尾随逗号
+    #[serde(default)]
+    pub strict_json: bool,
+    /// 项目路径 → 端口，用于没有 Zebras 配置文件可写的自动识别项目，重新扫描后仍然生效
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub port_overrides: HashMap<String, u16>,
+    /// 扫描工作区文件夹时的深度和排除规则
+    #[serde(default)]
+    pub scan: ScanSettings,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 项目扫描设置，glob 相对于所在的工作区文件夹匹配目录路径
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScanSettings {
+    pub max_depth: usize,
+    /// 非空时只在匹配的目录中识别项目，其余目录仍会继续向下查找
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// 匹配的目录连同子目录一起跳过
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// 进入符号链接指向的目录，同一目标只进入一次，避免链接成环
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    /// 跳过 .gitignore 和 .ignore 中忽略的目录
+    #[serde(default = "default_respect_ignore_files")]
+    pub respect_ignore_files: bool,
+}
+
+impl Default for ScanSettings {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            include: Vec::new(),
+            exclude: ["**/node_modules", "**/.git", "**/dist", "**/target"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            follow_symlinks: false,
+            respect_ignore_files: true,
+        }
+    }
+}
+
+fn default_respect_ignore_files() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum PortStrategy {
+    /// 冲突时从原端口开始顺序查找
     Sequential,
+    /// 从不修改端口，冲突作为错误报告
     Fixed,
+    /// 由项目名和项目目录名计算稳定的首选端口，冲突时向后探测
+    Hashed,
 }
 
 impl Default for WorkspaceSettings {
@@ -41,6 +103,11 @@ impl Default for WorkspaceSettings {
             port_strategy: PortStrategy::Sequential,
             port_range_start: 8000,
             port_range_end: 9000,
+            config_envs: HashMap::new(),
+            version_pins: HashMap::new(),
+            strict_json: false,
+            port_overrides: HashMap::new(),
+            scan: ScanSettings::default(),
         }
     }
 }
@@ -56,8 +123,10 @@ impl Workspace {
             folders: Vec::new(), // 初始为空，后续添加文件夹
             created_at: Utc::now(),
             last_modified: Utc::now(),
+            revision: 0,
             projects: Vec::new(),
             settings: WorkspaceSettings::default(),
+            mock_rule_sets: Vec::new(),
         }
     }
 
@@ -0,0 +1,442 @@
+use crate::models::{
+    AppRegistryStatus, ProcessInfo, ProjectInfo, ProjectSourceType, RegistryEntry, RegistrySnapshot,
+};
+use chrono::Utc;
+use hyper::header::{HeaderValue, CACHE_CONTROL, CONTENT_TYPE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::{broadcast, oneshot, Mutex};
+use tokio::task::JoinHandle;
+
+/// SSE 连接的心跳间隔，避免被中间代理断开
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Copy)]
+enum RegistryEvent {
+    Changed,
+    /// 服务停止，结束所有 SSE 连接
+    Closed,
+}
+
+struct RegistryState {
+    apps: RwLock<Vec<RegistryEntry>>,
+    changes: broadcast::Sender<RegistryEvent>,
+}
+
+impl RegistryState {
+    fn snapshot(&self) -> RegistrySnapshot {
+        RegistrySnapshot {
+            updated_at: Utc::now(),
+            apps: self.apps.read().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+
+    fn snapshot_json(&self) -> String {
+        serde_json::to_string(&self.snapshot()).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+struct RunningRegistry {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+/// 子应用注册表：在本地端口上提供运行中 Zebras 项目的 JSON 列表（GET /registry.json），
+/// 并通过 SSE（GET /events）推送变化，基座应用无需手动修改 debug 配置
+pub struct AppRegistry {
+    state: Arc<RegistryState>,
+    server: Mutex<Option<RunningRegistry>>,
+}
+
+impl Default for AppRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AppRegistry {
+    pub fn new() -> Self {
+        let (changes, _) = broadcast::channel(16);
+
+        Self {
+            state: Arc::new(RegistryState {
+                apps: RwLock::new(Vec::new()),
+                changes,
+            }),
+            server: Mutex::new(None),
+        }
+    }
+
+    /// 启动注册表服务，已在其他端口运行时先停止
+    pub async fn start(&self, port: u16) -> Result<AppRegistryStatus, String> {
+        let mut server = self.server.lock().await;
+
+        if let Some(running) = server.as_ref() {
+            if running.port == port {
+                drop(server);
+                return Ok(self.status().await);
+            }
+        }
+        if let Some(running) = server.take() {
+            self.shutdown(running).await;
+        }
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], port));
+        let builder = Server::try_bind(&addr)
+            .map_err(|e| format!("注册表服务无法监听端口 {}: {}", port, e))?;
+
+        let state = self.state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| handle(req, state.clone()))) }
+        });
+
+        let bound = builder.serve(make_service);
+        let bound_port = bound.local_addr().port();
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            let graceful = bound.with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            });
+            if let Err(e) = graceful.await {
+                eprintln!("[AppRegistry] 注册表服务异常退出: {}", e);
+            }
+        });
+
+        *server = Some(RunningRegistry {
+            port: bound_port,
+            shutdown,
+            handle,
+        });
+        drop(server);
+
+        Ok(self.status().await)
+    }
+
+    pub async fn stop(&self) {
+        if let Some(running) = self.server.lock().await.take() {
+            self.shutdown(running).await;
+        }
+    }
+
+    async fn shutdown(&self, running: RunningRegistry) {
+        let _ = running.shutdown.send(());
+        let _ = self.state.changes.send(RegistryEvent::Closed);
+        let _ = running.handle.await;
+    }
+
+    pub async fn status(&self) -> AppRegistryStatus {
+        let port = self.server.lock().await.as_ref().map(|s| s.port);
+
+        AppRegistryStatus {
+            running: port.is_some(),
+            port,
+            apps: self.apps(),
+        }
+    }
+
+    pub fn apps(&self) -> Vec<RegistryEntry> {
+        self.state
+            .apps
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// 项目启动后登记，非 Zebras 项目或没有端口的项目忽略；
+    /// gateway_url 为项目经开发网关访问的地址，网关未运行时为空
+    pub fn register(
+        &self,
+        process: &ProcessInfo,
+        project: &ProjectInfo,
+        gateway_url: Option<String>,
+    ) {
+        let Some(entry) = entry_for_project(process, project, gateway_url) else {
+            return;
+        };
+
+        {
+            let mut apps = self.state.apps.write().unwrap_or_else(|e| e.into_inner());
+            apps.retain(|app| app.project_id != entry.project_id);
+            apps.push(entry);
+            apps.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        self.notify();
+    }
+
+    pub fn unregister(&self, process_id: &str) {
+        let removed = {
+            let mut apps = self.state.apps.write().unwrap_or_else(|e| e.into_inner());
+            let before = apps.len();
+            apps.retain(|app| app.process_id != process_id);
+            apps.len() != before
+        };
+        if removed {
+            self.notify();
+        }
+    }
+
+    /// 开发网关启动或停止后更新入口地址，没有网关路由的项目使用 localhost
+    pub fn set_gateway_urls(&self, urls: &HashMap<String, String>) {
+        let changed = {
+            let mut apps = self.state.apps.write().unwrap_or_else(|e| e.into_inner());
+            let mut changed = false;
+            for app in apps.iter_mut() {
+                let url = entry_url(app.port, urls.get(&app.project_id).cloned());
+                if app.entry_url != url {
+                    app.entry_url = url;
+                    changed = true;
+                }
+            }
+            changed
+        };
+        if changed {
+            self.notify();
+        }
+    }
+
+    pub fn clear(&self) {
+        self.state
+            .apps
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        self.notify();
+    }
+
+    fn notify(&self) {
+        // 没有订阅者时发送失败，忽略即可
+        let _ = self.state.changes.send(RegistryEvent::Changed);
+    }
+}
+
+pub fn entry_for_project(
+    process: &ProcessInfo,
+    project: &ProjectInfo,
+    gateway_url: Option<String>,
+) -> Option<RegistryEntry> {
+    if project.source_type != ProjectSourceType::Zebras || project.port == 0 {
+        return None;
+    }
+
+    Some(RegistryEntry {
+        project_id: project.id.clone(),
+        process_id: process.process_id.clone(),
+        name: project.name.clone(),
+        type_: project.type_.clone(),
+        version: project.version.clone(),
+        domain: project.domain.clone(),
+        port: project.port,
+        entry_url: entry_url(project.port, gateway_url),
+        started_at: process.started_at,
+    })
+}
+
+/// 有网关路由时使用网关地址（域名、https），否则直接访问开发服务器
+fn entry_url(port: u16, gateway_url: Option<String>) -> String {
+    gateway_url.unwrap_or_else(|| format!("http://localhost:{}/", port))
+}
+
+/// 基座应用通常运行在其他端口，允许跨域访问
+fn with_cors(mut response: Response<Body>) -> Response<Body> {
+    let headers = response.headers_mut();
+    headers.insert("access-control-allow-origin", HeaderValue::from_static("*"));
+    headers.insert(
+        "access-control-allow-methods",
+        HeaderValue::from_static("GET, OPTIONS"),
+    );
+    response
+}
+
+async fn handle(
+    req: Request<Body>,
+    state: Arc<RegistryState>,
+) -> Result<Response<Body>, Infallible> {
+    let response = match (req.method(), req.uri().path()) {
+        (&Method::OPTIONS, _) => Response::new(Body::empty()),
+        (&Method::GET, "/" | "/registry.json") => Response::builder()
+            .header(CONTENT_TYPE, "application/json; charset=utf-8")
+            .header(CACHE_CONTROL, "no-cache")
+            .body(Body::from(state.snapshot_json()))
+            .unwrap_or_else(|_| Response::new(Body::empty())),
+        (&Method::GET, "/events") => events(state),
+        _ => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Not Found"))
+            .unwrap_or_else(|_| Response::new(Body::empty())),
+    };
+
+    Ok(with_cors(response))
+}
+
+/// SSE：连接后先推送一次完整列表，之后每次变化推送最新列表
+fn events(state: Arc<RegistryState>) -> Response<Body> {
+    let (mut sender, body) = Body::channel();
+    let mut changes = state.changes.subscribe();
+
+    tokio::spawn(async move {
+        let mut keep_alive = tokio::time::interval(KEEP_ALIVE_INTERVAL);
+        keep_alive.tick().await;
+
+        let initial = format!("event: registry\ndata: {}\n\n", state.snapshot_json());
+        if sender.send_data(initial.into()).await.is_err() {
+            return;
+        }
+
+        loop {
+            let message = tokio::select! {
+                changed = changes.recv() => match changed {
+                    // 消息积压时直接推送最新列表
+                    Ok(RegistryEvent::Changed) | Err(broadcast::error::RecvError::Lagged(_)) => {
+                        format!("event: registry\ndata: {}\n\n", state.snapshot_json())
+                    }
+                    Ok(RegistryEvent::Closed) | Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = keep_alive.tick() => ": keep-alive\n\n".to_string(),
+            };
+
+            if sender.send_data(message.into()).await.is_err() {
+                break; // 客户端已断开
+            }
+        }
+    });
+
+    Response::builder()
+        .header(CONTENT_TYPE, "text/event-stream")
+        .header(CACHE_CONTROL, "no-cache")
+        .body(body)
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProcessStatus, ZebrasVersion};
+    use hyper::body::HttpBody;
+    use hyper::Client;
+    use std::path::PathBuf;
+
+    fn project(name: &str, port: u16) -> ProjectInfo {
+        let mut project =
+            ProjectInfo::new(PathBuf::from(format!("/code/{}", name)), name.to_string());
+        project.id = name.to_string();
+        project.type_ = "app".to_string();
+        project.version = ZebrasVersion::V3;
+        project.domain = Some(name.to_string());
+        project.port = port;
+        project
+    }
+
+    fn running(project: &ProjectInfo) -> ProcessInfo {
+        ProcessInfo {
+            process_id: format!("proc-{}", project.id),
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            status: ProcessStatus::Running,
+            started_at: Utc::now(),
+            pid: None,
+            health: None,
+        }
+    }
+
+    /// 读取 SSE 流直到收到一条完整事件
+    async fn next_event(body: &mut Body) -> String {
+        let mut buffer = String::new();
+        loop {
+            let chunk = body.data().await.unwrap().unwrap();
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            if buffer.starts_with("event:") && buffer.ends_with("\n\n") {
+                return buffer;
+            }
+            if buffer.starts_with(':') {
+                buffer.clear();
+            }
+        }
+    }
+
+    #[test]
+    fn entry_skips_managed_projects_and_projects_without_port() {
+        let order = project("order", 8003);
+        let entry = entry_for_project(&running(&order), &order, None).unwrap();
+        assert_eq!(entry.entry_url, "http://localhost:8003/");
+        let via_gateway = Some("https://order.localhost:8443/".to_string());
+        let entry = entry_for_project(&running(&order), &order, via_gateway).unwrap();
+        assert_eq!(entry.entry_url, "https://order.localhost:8443/");
+        assert_eq!(
+            serde_json::to_value(&entry).unwrap()["type"],
+            serde_json::json!("app")
+        );
+
+        let mut managed = project("api", 8080);
+        managed.source_type = ProjectSourceType::ManagedProject;
+        assert!(entry_for_project(&running(&managed), &managed, None).is_none());
+
+        let no_port = project("lib", 0);
+        assert!(entry_for_project(&running(&no_port), &no_port, None).is_none());
+    }
+
+    #[tokio::test]
+    async fn serves_registry_and_pushes_changes_over_sse() {
+        let registry = AppRegistry::new();
+        let port = registry.start(0).await.unwrap().port.unwrap();
+
+        let order = project("order", 8003);
+        registry.register(&running(&order), &order, None);
+
+        let response = Client::new()
+            .get(
+                format!("http://127.0.0.1:{}/registry.json", port)
+                    .parse()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()["access-control-allow-origin"],
+            HeaderValue::from_static("*")
+        );
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let snapshot: RegistrySnapshot = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(snapshot.apps.len(), 1);
+        assert_eq!(snapshot.apps[0].name, "order");
+
+        let events = Client::new()
+            .get(format!("http://127.0.0.1:{}/events", port).parse().unwrap())
+            .await
+            .unwrap();
+        let mut body = events.into_body();
+        assert!(next_event(&mut body).await.contains("\"order\""));
+
+        let user = project("user", 8004);
+        registry.register(&running(&user), &user, None);
+        let event = next_event(&mut body).await;
+        assert!(event.contains("\"user\"") && event.contains("\"order\""));
+
+        // 网关启动后入口改为网关地址，停止后恢复为 localhost
+        let urls = HashMap::from([(
+            "user".to_string(),
+            "https://user.localhost:8443/".to_string(),
+        )]);
+        registry.set_gateway_urls(&urls);
+        let event = next_event(&mut body).await;
+        assert!(event.contains("https://user.localhost:8443/"));
+        assert!(event.contains("http://localhost:8003/"));
+        registry.set_gateway_urls(&HashMap::new());
+        assert!(next_event(&mut body)
+            .await
+            .contains("http://localhost:8004/"));
+
+        registry.unregister(&running(&order).process_id);
+        let event = next_event(&mut body).await;
+        assert!(!event.contains("\"order\""));
+
+        registry.stop().await;
+        assert!(!registry.status().await.running);
+    }
+}
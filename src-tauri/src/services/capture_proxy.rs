@@ -0,0 +1,846 @@
+use crate::models::{
+    CaptureConfig, CaptureFilter, CaptureSession, CapturedBody, CapturedExchange, CapturedHeader,
+    ProjectInfo,
+};
+use crate::services::dev_gateway::{forward, is_upgrade_request};
+use chrono::Utc;
+use hyper::body::HttpBody;
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderMap, ACCEPT_ENCODING, CONTENT_TYPE, HOST};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Client, Request, Response, Server, StatusCode};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{oneshot, watch, Mutex};
+use tokio::task::JoinHandle;
+
+/// 所有项目共用的抓包记录上限，超出后丢弃最早的记录
+const MAX_CAPTURES: usize = 1000;
+
+/// 停止代理时等待连接结束的上限，超时后直接中止
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 有界的抓包记录
+pub struct CaptureStore {
+    entries: VecDeque<CapturedExchange>,
+    capacity: usize,
+    next_id: u64,
+}
+
+impl CaptureStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: capacity.max(1),
+            next_id: 1,
+        }
+    }
+
+    pub fn push(&mut self, mut exchange: CapturedExchange) {
+        exchange.id = self.next_id;
+        self.next_id += 1;
+
+        while self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(exchange);
+    }
+
+    /// 按条件查询，按时间先后返回；有 limit 时只保留最近的记录
+    pub fn query(&self, filter: &CaptureFilter) -> Vec<CapturedExchange> {
+        let url_contains = filter.url_contains.as_deref().map(str::to_lowercase);
+
+        let mut matched: Vec<CapturedExchange> = self
+            .entries
+            .iter()
+            .filter(|e| {
+                filter
+                    .project_id
+                    .as_deref()
+                    .is_none_or(|id| e.project_id == id)
+            })
+            .filter(|e| {
+                filter
+                    .method
+                    .as_deref()
+                    .is_none_or(|method| e.method.eq_ignore_ascii_case(method))
+            })
+            .filter(|e| {
+                url_contains
+                    .as_deref()
+                    .is_none_or(|text| e.url.to_lowercase().contains(text))
+            })
+            .filter(|e| {
+                filter
+                    .min_status
+                    .is_none_or(|min| e.status.is_some_and(|status| status >= min))
+            })
+            .cloned()
+            .collect();
+
+        if let Some(limit) = filter.limit {
+            let skip = matched.len().saturating_sub(limit);
+            matched.drain(..skip);
+        }
+
+        matched
+    }
+
+    pub fn clear(&mut self, project_id: Option<&str>) {
+        match project_id {
+            Some(id) => self.entries.retain(|e| e.project_id != id),
+            None => self.entries.clear(),
+        }
+    }
+}
+
+type SharedStore = Arc<std::sync::Mutex<CaptureStore>>;
+
+struct RunningCapture {
+    session: CaptureSession,
+    /// 置为 true 时停止接受连接，并结束仍在转发的流式 body
+    closed: watch::Sender<bool>,
+    handle: JoinHandle<()>,
+}
+
+/// 单个抓包代理转发所需的信息
+struct CaptureTarget {
+    project_id: String,
+    project_name: String,
+    listen_port: u16,
+    target_port: u16,
+    max_body_bytes: usize,
+    store: SharedStore,
+    client: Client<HttpConnector>,
+    closed: watch::Receiver<bool>,
+}
+
+/// 抓包代理：在项目端口前挂一层 HTTP 代理，记录经过的请求和响应。
+/// 只有开启抓包的项目才会启动代理，未开启时请求不经过这里
+pub struct CaptureProxy {
+    store: SharedStore,
+    sessions: Mutex<HashMap<String, RunningCapture>>,
+}
+
+impl Default for CaptureProxy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CaptureProxy {
+    pub fn new() -> Self {
+        Self {
+            store: Arc::new(std::sync::Mutex::new(CaptureStore::new(MAX_CAPTURES))),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 为项目启动抓包代理，已在运行时先停止
+    pub async fn start(
+        &self,
+        project: &ProjectInfo,
+        config: &CaptureConfig,
+    ) -> Result<CaptureSession, String> {
+        if project.port == 0 {
+            return Err(format!("项目 {} 未配置端口，无法抓包", project.name));
+        }
+
+        // 先从表中取出再停止，等待期间不占用锁
+        let previous = self.sessions.lock().await.remove(&project.id);
+        if let Some(running) = previous {
+            Self::shutdown(running).await;
+        }
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], config.listen_port.unwrap_or(0)));
+        let builder = Server::try_bind(&addr).map_err(|e| {
+            format!(
+                "抓包代理无法监听端口 {}: {}",
+                config.listen_port.unwrap_or(0),
+                e
+            )
+        })?;
+        let listen_port = builder.local_addr().port();
+
+        let (closed, closed_rx) = watch::channel(false);
+        let target = Arc::new(CaptureTarget {
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            listen_port,
+            target_port: project.port,
+            max_body_bytes: config.max_body_bytes,
+            store: self.store.clone(),
+            client: Client::builder().build_http::<Body>(),
+            closed: closed_rx.clone(),
+        });
+        let make_service = make_service_fn(move |_| {
+            let target = target.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| capture(req, target.clone()))) }
+        });
+
+        let mut shutdown_rx = closed_rx;
+        let server = builder
+            .serve(make_service)
+            .with_graceful_shutdown(async move {
+                let _ = shutdown_rx.wait_for(|closed| *closed).await;
+            });
+        let handle = tokio::spawn(async move {
+            if let Err(e) = server.await {
+                eprintln!("[CaptureProxy] 抓包代理异常退出: {}", e);
+            }
+        });
+
+        let session = CaptureSession {
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            listen_port,
+            target_port: project.port,
+            started_at: Utc::now(),
+        };
+        let replaced = self.sessions.lock().await.insert(
+            project.id.clone(),
+            RunningCapture {
+                session: session.clone(),
+                closed,
+                handle,
+            },
+        );
+        // 并发启动同一项目时停止被替换的代理
+        if let Some(running) = replaced {
+            Self::shutdown(running).await;
+        }
+
+        Ok(session)
+    }
+
+    /// 停止项目的抓包代理，已记录的数据保留
+    pub async fn stop(&self, project_id: &str) -> Option<CaptureSession> {
+        let running = self.sessions.lock().await.remove(project_id)?;
+        let session = running.session.clone();
+        Self::shutdown(running).await;
+        Some(session)
+    }
+
+    pub async fn stop_all(&self) -> Vec<CaptureSession> {
+        let drained: Vec<RunningCapture> = self
+            .sessions
+            .lock()
+            .await
+            .drain()
+            .map(|(_, running)| running)
+            .collect();
+
+        let mut stopped = Vec::new();
+        for running in drained {
+            stopped.push(running.session.clone());
+            Self::shutdown(running).await;
+        }
+        stopped
+    }
+
+    /// 通知代理停止并结束正在转发的流式响应；仍有连接未结束时最多等待 SHUTDOWN_TIMEOUT
+    async fn shutdown(mut running: RunningCapture) {
+        let _ = running.closed.send(true);
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, &mut running.handle)
+            .await
+            .is_err()
+        {
+            running.handle.abort();
+        }
+    }
+
+    pub async fn sessions(&self) -> Vec<CaptureSession> {
+        self.sessions
+            .lock()
+            .await
+            .values()
+            .map(|running| running.session.clone())
+            .collect()
+    }
+
+    pub fn query(&self, filter: &CaptureFilter) -> Vec<CapturedExchange> {
+        self.store
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .query(filter)
+    }
+
+    pub fn clear(&self, project_id: Option<&str>) {
+        self.store
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear(project_id);
+    }
+
+    /// 按条件导出为 HAR 1.2
+    pub fn export_har(&self, filter: &CaptureFilter) -> Value {
+        to_har(&self.query(filter))
+    }
+}
+
+async fn capture(
+    req: Request<Body>,
+    target: Arc<CaptureTarget>,
+) -> Result<Response<Body>, Infallible> {
+    let started = Instant::now();
+    let host = req
+        .headers()
+        .get(HOST)
+        .and_then(|h| h.to_str().ok())
+        .map(|h| h.to_string())
+        .unwrap_or_else(|| format!("127.0.0.1:{}", target.listen_port));
+    let path_and_query = req
+        .uri()
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+
+    let mut exchange = CapturedExchange {
+        id: 0,
+        project_id: target.project_id.clone(),
+        project_name: target.project_name.clone(),
+        started_at: Utc::now(),
+        duration_ms: 0,
+        method: req.method().to_string(),
+        url: format!("http://{}{}", host, path_and_query),
+        http_version: format!("{:?}", req.version()),
+        request_headers: captured_headers(req.headers()),
+        request_body: CapturedBody::default(),
+        status: None,
+        response_headers: Vec::new(),
+        response_body: CapturedBody::default(),
+        error: None,
+    };
+
+    // WebSocket 只记录握手，数据帧直接转发
+    if is_upgrade_request(&req) {
+        let response = forward(
+            req,
+            target.target_port,
+            &target.project_name,
+            &host,
+            "http",
+            &target.client,
+            target.closed.clone(),
+        )
+        .await;
+        exchange.status = Some(response.status().as_u16());
+        exchange.response_headers = captured_headers(response.headers());
+        exchange.duration_ms = started.elapsed().as_millis() as u64;
+        push(&target.store, exchange);
+        return Ok(response);
+    }
+
+    // 请求体和响应体都边转发边记录前 max_body_bytes 字节，不把整个 body 读进内存
+    let (mut parts, body) = req.into_parts();
+    let (body, request_recorded) = if body.is_end_stream() {
+        (Body::empty(), None)
+    } else {
+        let (tee, recorded) = tee_body(
+            body,
+            target.max_body_bytes,
+            "读取请求体失败",
+            "上游提前断开",
+            target.closed.clone(),
+        );
+        (tee, Some(recorded))
+    };
+
+    // 不让上游压缩，记录到的响应体才是可读的文本
+    parts.headers.remove(ACCEPT_ENCODING);
+    let response = forward(
+        Request::from_parts(parts, body),
+        target.target_port,
+        &target.project_name,
+        &host,
+        "http",
+        &target.client,
+        target.closed.clone(),
+    )
+    .await;
+    exchange.status = Some(response.status().as_u16());
+    exchange.response_headers = captured_headers(response.headers());
+
+    let (parts, body) = response.into_parts();
+    let (tee, response_recorded) = tee_body(
+        body,
+        target.max_body_bytes,
+        "读取响应体失败",
+        "客户端提前断开",
+        target.closed.clone(),
+    );
+    let max_body_bytes = target.max_body_bytes;
+    let store = target.store.clone();
+    tokio::spawn(async move {
+        let mut errors = Vec::new();
+        if let Some(request_recorded) = request_recorded {
+            let recorded = request_recorded.await.unwrap_or_default();
+            errors.extend(recorded.error);
+            exchange.request_body = captured_body(&recorded.bytes, recorded.size, max_body_bytes);
+        }
+        let recorded = response_recorded.await.unwrap_or_default();
+        errors.extend(recorded.error);
+        exchange.response_body = captured_body(&recorded.bytes, recorded.size, max_body_bytes);
+        if !errors.is_empty() {
+            exchange.error = Some(errors.join("；"));
+        }
+        exchange.duration_ms = started.elapsed().as_millis() as u64;
+        push(&store, exchange);
+    });
+
+    Ok(Response::from_parts(parts, tee))
+}
+
+/// 转发过程中记录到的 body：前 max_bytes 字节、实际大小和中断原因
+#[derive(Default)]
+struct Recorded {
+    bytes: Vec<u8>,
+    size: usize,
+    error: Option<String>,
+}
+
+/// 把 body 转发到返回的新 body，同时记录前 max_bytes 字节，转发结束后通过 Receiver 返回记录；
+/// 代理停止时立即结束转发，SSE 等长连接不会拖住停止
+fn tee_body(
+    mut body: Body,
+    max_bytes: usize,
+    read_error: &'static str,
+    closed_error: &'static str,
+    mut closed: watch::Receiver<bool>,
+) -> (Body, oneshot::Receiver<Recorded>) {
+    let (mut sender, tee) = Body::channel();
+    let (done, recorded_rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let mut recorded = Recorded::default();
+        let forwarded = async {
+            while let Some(chunk) = body.data().await {
+                match chunk {
+                    Ok(chunk) => {
+                        recorded.size += chunk.len();
+                        let remaining = max_bytes.saturating_sub(recorded.bytes.len());
+                        recorded
+                            .bytes
+                            .extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+                        if sender.send_data(chunk).await.is_err() {
+                            return Err(closed_error.to_string());
+                        }
+                    }
+                    Err(e) => return Err(format!("{}: {}", read_error, e)),
+                }
+            }
+            Ok(())
+        };
+        let result = tokio::select! {
+            result = forwarded => result,
+            _ = closed.wait_for(|closed| *closed) => Err("抓包代理已停止".to_string()),
+        };
+        if let Err(error) = result {
+            recorded.error = Some(error);
+            sender.abort();
+        }
+        let _ = done.send(recorded);
+    });
+    (tee, recorded_rx)
+}
+
+fn push(store: &SharedStore, exchange: CapturedExchange) {
+    store
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(exchange);
+}
+
+fn captured_headers(headers: &HeaderMap) -> Vec<CapturedHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| CapturedHeader {
+            name: name.to_string(),
+            value: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+        })
+        .collect()
+}
+
+/// recorded 最多 max_bytes 字节，size 为实际大小
+fn captured_body(recorded: &[u8], size: usize, max_bytes: usize) -> CapturedBody {
+    let recorded = &recorded[..recorded.len().min(max_bytes)];
+    let truncated = size > recorded.len();
+
+    let text = match std::str::from_utf8(recorded) {
+        Ok(text) => Some(text.to_string()),
+        // 截断处切开了多字节字符时保留前面的部分
+        Err(e) if truncated && e.error_len().is_none() => {
+            Some(String::from_utf8_lossy(&recorded[..e.valid_up_to()]).into_owned())
+        }
+        Err(_) => None,
+    };
+
+    CapturedBody {
+        size,
+        text: text.filter(|t| !t.is_empty()),
+        truncated,
+    }
+}
+
+fn header_value<'a>(headers: &'a [CapturedHeader], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+fn har_headers(headers: &[CapturedHeader]) -> Value {
+    Value::Array(
+        headers
+            .iter()
+            .map(|h| json!({ "name": h.name, "value": h.value }))
+            .collect(),
+    )
+}
+
+fn har_query_string(url: &str) -> Value {
+    let query = url.split_once('?').map(|(_, q)| q).unwrap_or_default();
+
+    Value::Array(
+        query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                json!({ "name": name, "value": value })
+            })
+            .collect(),
+    )
+}
+
+fn har_truncated_comment(body: &CapturedBody) -> &'static str {
+    if body.truncated {
+        "内容超过大小上限，已截断"
+    } else {
+        ""
+    }
+}
+
+/// 转换为 HAR 1.2（http://www.softwareishard.com/blog/har-12-spec/）
+pub fn to_har(exchanges: &[CapturedExchange]) -> Value {
+    let entries: Vec<Value> = exchanges
+        .iter()
+        .map(|e| {
+            let request_mime =
+                header_value(&e.request_headers, CONTENT_TYPE.as_str()).unwrap_or("");
+            let response_mime =
+                header_value(&e.response_headers, CONTENT_TYPE.as_str()).unwrap_or("");
+            let status = e.status.unwrap_or(0);
+            let status_text = StatusCode::from_u16(status)
+                .ok()
+                .and_then(|s| s.canonical_reason())
+                .unwrap_or("");
+
+            let mut request = json!({
+                "method": e.method,
+                "url": e.url,
+                "httpVersion": e.http_version,
+                "cookies": [],
+                "headers": har_headers(&e.request_headers),
+                "queryString": har_query_string(&e.url),
+                "headersSize": -1,
+                "bodySize": e.request_body.size,
+            });
+            if e.request_body.size > 0 {
+                request["postData"] = json!({
+                    "mimeType": request_mime,
+                    "text": e.request_body.text.clone().unwrap_or_default(),
+                    "comment": har_truncated_comment(&e.request_body),
+                });
+            }
+
+            let mut content = json!({
+                "size": e.response_body.size,
+                "mimeType": response_mime,
+                "comment": har_truncated_comment(&e.response_body),
+            });
+            if let Some(text) = e.response_body.text.as_ref() {
+                content["text"] = json!(text);
+            }
+
+            json!({
+                "startedDateTime": e.started_at.to_rfc3339(),
+                "time": e.duration_ms,
+                "request": request,
+                "response": {
+                    "status": status,
+                    "statusText": status_text,
+                    "httpVersion": e.http_version,
+                    "cookies": [],
+                    "headers": har_headers(&e.response_headers),
+                    "content": content,
+                    "redirectURL": header_value(&e.response_headers, "location").unwrap_or(""),
+                    "headersSize": -1,
+                    "bodySize": e.response_body.size,
+                },
+                "cache": {},
+                "timings": { "send": 0, "wait": e.duration_ms, "receive": 0 },
+                "comment": e.error.clone().unwrap_or_else(|| e.project_name.clone()),
+            })
+        })
+        .collect();
+
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "Zebras Launcher", "version": env!("CARGO_PKG_VERSION") },
+            "pages": [],
+            "entries": entries,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    async fn spawn_upstream() -> u16 {
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|req: Request<Body>| async move {
+                let path = req.uri().path().to_string();
+                let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                let response = Response::builder()
+                    .status(if path == "/missing" { 404 } else { 200 })
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(format!(
+                        r#"{{"path":"{}","received":{}}}"#,
+                        path,
+                        body.len()
+                    )))
+                    .unwrap();
+                Ok::<_, Infallible>(response)
+            }))
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let port = server.local_addr().port();
+        tokio::spawn(server);
+        port
+    }
+
+    fn exchange(project_id: &str, method: &str, url: &str, status: u16) -> CapturedExchange {
+        CapturedExchange {
+            id: 0,
+            project_id: project_id.to_string(),
+            project_name: project_id.to_string(),
+            started_at: Utc::now(),
+            duration_ms: 3,
+            method: method.to_string(),
+            url: url.to_string(),
+            http_version: "HTTP/1.1".to_string(),
+            request_headers: Vec::new(),
+            request_body: CapturedBody::default(),
+            status: Some(status),
+            response_headers: Vec::new(),
+            response_body: CapturedBody::default(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn store_is_bounded_and_filters_by_project() {
+        let mut store = CaptureStore::new(3);
+        store.push(exchange("order", "GET", "http://order.localhost/a", 200));
+        store.push(exchange("user", "POST", "http://user.localhost/login", 401));
+        store.push(exchange(
+            "order",
+            "GET",
+            "http://order.localhost/b?id=1",
+            500,
+        ));
+        store.push(exchange("order", "GET", "http://order.localhost/c", 200));
+
+        // 最早的记录被丢弃
+        let all = store.query(&CaptureFilter::default());
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].id, 2);
+
+        let order = store.query(&CaptureFilter {
+            project_id: Some("order".to_string()),
+            ..CaptureFilter::default()
+        });
+        assert_eq!(order.len(), 2);
+
+        let errors = store.query(&CaptureFilter {
+            min_status: Some(400),
+            limit: Some(1),
+            ..CaptureFilter::default()
+        });
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].status, Some(500));
+
+        store.clear(Some("order"));
+        assert_eq!(store.query(&CaptureFilter::default()).len(), 1);
+    }
+
+    #[test]
+    fn captured_body_truncates_on_char_boundary() {
+        let text = "订单列表".as_bytes();
+        let body = captured_body(&text[..7], text.len(), 7);
+        assert!(body.truncated);
+        assert_eq!(body.size, 12);
+        assert_eq!(body.text.as_deref(), Some("订单"));
+
+        let binary = captured_body(&[0xff, 0xfe, 0x00], 3, 64);
+        assert!(!binary.truncated);
+        assert!(binary.text.is_none());
+    }
+
+    #[tokio::test]
+    async fn proxy_records_exchanges_and_exports_har() {
+        let upstream_port = spawn_upstream().await;
+        let mut project = ProjectInfo::new(PathBuf::from("/code/order"), "order".to_string());
+        project.id = "order".to_string();
+        project.port = upstream_port;
+
+        let proxy = CaptureProxy::new();
+        let config = CaptureConfig {
+            max_body_bytes: 8,
+            ..CaptureConfig::default()
+        };
+        let session = proxy.start(&project, &config).await.unwrap();
+
+        let req = Request::post(format!(
+            "http://127.0.0.1:{}/api/orders?page=2",
+            session.listen_port
+        ))
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{"status":"paid"}"#))
+        .unwrap();
+        let response = Client::new().request(req).await.unwrap();
+        assert_eq!(response.status(), 200);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], br#"{"path":"/api/orders","received":17}"#);
+
+        // 响应体转发完成后才写入记录
+        let mut captures = Vec::new();
+        for _ in 0..50 {
+            captures = proxy.query(&CaptureFilter::default());
+            if !captures.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(captures.len(), 1);
+        let captured = &captures[0];
+        assert_eq!(captured.method, "POST");
+        assert_eq!(captured.status, Some(200));
+        assert_eq!(captured.request_body.size, 17);
+        assert_eq!(captured.request_body.text.as_deref(), Some(r#"{"status"#));
+        assert!(captured.response_body.truncated);
+
+        // 大请求体原样转发给上游，只记录前 max_body_bytes 字节
+        let large = vec![b'a'; 1 << 20];
+        let req = Request::put(format!("http://127.0.0.1:{}/upload", session.listen_port))
+            .body(Body::from(large))
+            .unwrap();
+        let response = Client::new().request(req).await.unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], br#"{"path":"/upload","received":1048576}"#);
+        let mut upload = None;
+        for _ in 0..50 {
+            upload = proxy
+                .query(&CaptureFilter::default())
+                .into_iter()
+                .find(|e| e.method == "PUT");
+            if upload.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let upload = upload.unwrap();
+        assert_eq!(upload.request_body.size, 1 << 20);
+        assert!(upload.request_body.truncated);
+        assert_eq!(upload.request_body.text.as_deref(), Some("aaaaaaaa"));
+        assert!(upload.error.is_none());
+
+        let har = proxy.export_har(&CaptureFilter {
+            project_id: Some("order".to_string()),
+            ..CaptureFilter::default()
+        });
+        assert_eq!(har["log"]["version"], "1.2");
+        let entry = &har["log"]["entries"][0];
+        assert_eq!(
+            entry["request"]["postData"]["comment"],
+            "内容超过大小上限，已截断"
+        );
+        assert_eq!(entry["request"]["queryString"][0]["name"], "page");
+        assert_eq!(entry["request"]["postData"]["mimeType"], "application/json");
+        assert_eq!(entry["response"]["status"], 200);
+        assert_eq!(entry["response"]["statusText"], "OK");
+
+        assert!(proxy.stop("order").await.is_some());
+        assert!(proxy.sessions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stopping_does_not_wait_for_open_streams() {
+        // 上游返回一个永不结束的流式响应，类似 SSE
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|_req: Request<Body>| async move {
+                let (mut sender, body) = Body::channel();
+                tokio::spawn(async move {
+                    let _ = sender.send_data("data: ping\n\n".into()).await;
+                    tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+                    drop(sender);
+                });
+                Ok::<_, Infallible>(Response::new(body))
+            }))
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let upstream_port = server.local_addr().port();
+        tokio::spawn(server);
+
+        let mut project = ProjectInfo::new(PathBuf::from("/code/order"), "order".to_string());
+        project.id = "order".to_string();
+        project.port = upstream_port;
+        let proxy = CaptureProxy::new();
+        let session = proxy
+            .start(&project, &CaptureConfig::default())
+            .await
+            .unwrap();
+
+        let response = Client::new()
+            .get(
+                format!("http://127.0.0.1:{}/events", session.listen_port)
+                    .parse()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let mut body = response.into_body();
+        assert_eq!(&body.data().await.unwrap().unwrap()[..], b"data: ping\n\n");
+
+        // 流仍在转发时停止代理，立即返回且流被中止
+        let stopped = tokio::time::timeout(std::time::Duration::from_secs(1), proxy.stop("order"))
+            .await
+            .expect("停止抓包不应等待流式响应结束");
+        assert!(stopped.is_some());
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_secs(1), body.data())
+                .await
+                .unwrap()
+                .is_none_or(|chunk| chunk.is_err())
+        );
+
+        let mut captures = Vec::new();
+        for _ in 0..50 {
+            captures = proxy.query(&CaptureFilter::default());
+            if !captures.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0].error.as_deref(), Some("抓包代理已停止"));
+    }
+}
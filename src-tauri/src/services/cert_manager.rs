@@ -0,0 +1,341 @@
+use chrono::{DateTime, Datelike, Duration, Utc};
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType,
+    ExtendedKeyUsagePurpose, IsCa, KeyPair, KeyUsagePurpose, SanType,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+const CA_COMMON_NAME: &str = "Zebras Launcher Local CA";
+const CA_VALID_DAYS: i64 = 3650;
+const LEAF_VALID_DAYS: i64 = 365;
+/// 剩余有效期少于该天数时自动续期
+const RENEW_BEFORE_DAYS: i64 = 30;
+
+/// 通配证书覆盖的名称
+pub const WILDCARD_LOCALHOST_NAMES: [&str; 4] = ["*.localhost", "localhost", "127.0.0.1", "::1"];
+
+/// 叶子证书的 PEM 内容
+#[derive(Debug, Clone)]
+pub struct LeafCert {
+    pub cert_pem: String,
+    pub key_pem: String,
+}
+
+/// 记录叶子证书覆盖的名称和到期时间，用于判断是否需要重新签发
+#[derive(Debug, Serialize, Deserialize)]
+struct LeafMeta {
+    names: Vec<String>,
+    not_after: DateTime<Utc>,
+}
+
+/// 本地 CA 和各域名的叶子证书，保存在 ~/.zebras-launcher/certs
+#[derive(Clone)]
+pub struct CertManager {
+    dir: PathBuf,
+}
+
+impl CertManager {
+    pub fn new() -> Result<Self, String> {
+        let home = dirs_next::home_dir().ok_or("无法获取用户主目录".to_string())?;
+
+        Self::with_dir(home.join(".zebras-launcher").join("certs"))
+    }
+
+    pub fn with_dir(dir: PathBuf) -> Result<Self, String> {
+        // 确保目录存在
+        fs::create_dir_all(&dir).map_err(|e| format!("创建证书目录失败: {}", e))?;
+        // CA 私钥可以签发任意域名的证书，只允许当前用户访问
+        restrict_permissions(&dir, 0o700).map_err(|e| format!("设置证书目录权限失败: {}", e))?;
+
+        Ok(Self { dir })
+    }
+
+    pub fn ca_cert_path(&self) -> PathBuf {
+        self.dir.join("ca.pem")
+    }
+
+    fn ca_key_path(&self) -> PathBuf {
+        self.dir.join("ca.key.pem")
+    }
+
+    /// 导出 CA 证书，供手动加入系统/浏览器信任
+    pub fn export_ca(&self, dest: &Path) -> Result<(), String> {
+        self.load_or_create_ca()?;
+
+        fs::copy(self.ca_cert_path(), dest).map_err(|e| format!("导出 CA 证书失败: {}", e))?;
+
+        Ok(())
+    }
+
+    /// 为每个域名确保有有效的叶子证书，另外签发一张覆盖 *.localhost 的通配证书
+    /// 返回 (域名, 证书)，通配证书的键为 "*.localhost"
+    pub fn ensure_for_hosts(&self, hosts: &[String]) -> Result<Vec<(String, LeafCert)>, String> {
+        let mut certs = Vec::new();
+
+        let wildcard: Vec<String> = WILDCARD_LOCALHOST_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        certs.push((wildcard[0].clone(), self.ensure_leaf(&wildcard)?));
+
+        for host in hosts {
+            if certs.iter().any(|(name, _)| name == host) {
+                continue;
+            }
+            certs.push((host.clone(), self.ensure_leaf(std::slice::from_ref(host))?));
+        }
+
+        Ok(certs)
+    }
+
+    /// 覆盖名称未变且未临近过期时复用已有证书，否则重新签发
+    pub fn ensure_leaf(&self, names: &[String]) -> Result<LeafCert, String> {
+        let first = names.first().ok_or("证书至少需要一个域名".to_string())?;
+        let stem = first.replace('*', "_wildcard");
+        let cert_path = self.dir.join(format!("{}.pem", stem));
+        let key_path = self.dir.join(format!("{}.key.pem", stem));
+        let meta_path = self.dir.join(format!("{}.json", stem));
+
+        let ca = self.load_or_create_ca()?;
+
+        if let Some(leaf) = Self::load_valid_leaf(&cert_path, &key_path, &meta_path, names) {
+            return Ok(leaf);
+        }
+
+        let now = Utc::now();
+        let not_after = now + Duration::days(LEAF_VALID_DAYS);
+
+        let mut params = CertificateParams::default();
+        params.distinguished_name = DistinguishedName::new();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, first.as_str());
+        params.subject_alt_names = names
+            .iter()
+            .map(|name| match name.parse::<IpAddr>() {
+                Ok(ip) => SanType::IpAddress(ip),
+                Err(_) => SanType::DnsName(name.clone()),
+            })
+            .collect();
+        set_validity(&mut params, now - Duration::days(1), not_after);
+        params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        params.use_authority_key_identifier_extension = true;
+
+        let leaf = Certificate::from_params(params).map_err(|e| format!("生成证书失败: {}", e))?;
+        let cert_pem = leaf
+            .serialize_pem_with_signer(&ca)
+            .map_err(|e| format!("签发证书失败: {}", e))?;
+        let key_pem = leaf.serialize_private_key_pem();
+
+        let meta = LeafMeta {
+            names: names.to_vec(),
+            not_after,
+        };
+        let meta_json = serde_json::to_string_pretty(&meta)
+            .map_err(|e| format!("序列化证书信息失败: {}", e))?;
+
+        fs::write(&cert_path, &cert_pem).map_err(|e| format!("写入证书失败: {}", e))?;
+        write_private_key(&key_path, &key_pem).map_err(|e| format!("写入证书私钥失败: {}", e))?;
+        fs::write(&meta_path, meta_json).map_err(|e| format!("写入证书信息失败: {}", e))?;
+
+        Ok(LeafCert { cert_pem, key_pem })
+    }
+
+    fn load_valid_leaf(
+        cert_path: &Path,
+        key_path: &Path,
+        meta_path: &Path,
+        names: &[String],
+    ) -> Option<LeafCert> {
+        let meta: LeafMeta = serde_json::from_str(&fs::read_to_string(meta_path).ok()?).ok()?;
+        if meta.names != names || meta.not_after - Utc::now() < Duration::days(RENEW_BEFORE_DAYS) {
+            return None;
+        }
+
+        Some(LeafCert {
+            cert_pem: fs::read_to_string(cert_path).ok()?,
+            key_pem: fs::read_to_string(key_path).ok()?,
+        })
+    }
+
+    /// 加载 CA；不存在时生成新的 CA，并清理旧 CA 签发的叶子证书
+    fn load_or_create_ca(&self) -> Result<Certificate, String> {
+        let cert_path = self.ca_cert_path();
+        let key_path = self.ca_key_path();
+
+        if cert_path.exists() && key_path.exists() {
+            // 旧版本按默认权限写入的私钥
+            restrict_permissions(&key_path, 0o600)
+                .map_err(|e| format!("设置 CA 私钥权限失败: {}", e))?;
+            let key_pem =
+                fs::read_to_string(&key_path).map_err(|e| format!("读取 CA 私钥失败: {}", e))?;
+            let key_pair =
+                KeyPair::from_pem(&key_pem).map_err(|e| format!("解析 CA 私钥失败: {}", e))?;
+
+            // 签发只需要 CA 的主题和私钥，与 ca.pem 中的证书一致
+            return Certificate::from_params(Self::ca_params(key_pair))
+                .map_err(|e| format!("加载 CA 失败: {}", e));
+        }
+
+        self.remove_leaf_files()?;
+
+        let key_pair = KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256)
+            .map_err(|e| format!("生成 CA 私钥失败: {}", e))?;
+        let mut params = Self::ca_params(key_pair);
+        let now = Utc::now();
+        set_validity(
+            &mut params,
+            now - Duration::days(1),
+            now + Duration::days(CA_VALID_DAYS),
+        );
+
+        let ca = Certificate::from_params(params).map_err(|e| format!("生成 CA 失败: {}", e))?;
+        let cert_pem = ca
+            .serialize_pem()
+            .map_err(|e| format!("生成 CA 证书失败: {}", e))?;
+
+        fs::write(&cert_path, cert_pem).map_err(|e| format!("写入 CA 证书失败: {}", e))?;
+        write_private_key(&key_path, &ca.serialize_private_key_pem())
+            .map_err(|e| format!("写入 CA 私钥失败: {}", e))?;
+
+        Ok(ca)
+    }
+
+    fn ca_params(key_pair: KeyPair) -> CertificateParams {
+        let mut params = CertificateParams::default();
+        params.distinguished_name = DistinguishedName::new();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, CA_COMMON_NAME);
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.key_usages = vec![
+            KeyUsagePurpose::KeyCertSign,
+            KeyUsagePurpose::CrlSign,
+            KeyUsagePurpose::DigitalSignature,
+        ];
+        params.key_pair = Some(key_pair);
+        params
+    }
+
+    fn remove_leaf_files(&self) -> Result<(), String> {
+        let entries = fs::read_dir(&self.dir).map_err(|e| format!("读取证书目录失败: {}", e))?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_ca = path == self.ca_cert_path() || path == self.ca_key_path();
+            let is_cert_file = path
+                .extension()
+                .is_some_and(|ext| ext == "pem" || ext == "json");
+            if is_cert_file && !is_ca {
+                let _ = fs::remove_file(&path);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 私钥文件创建时即为 0600，避免写入后再改权限之间被其他用户读取
+fn write_private_key(path: &Path, pem: &str) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // 已存在的文件不受 mode 影响
+    restrict_permissions(path, 0o600)?;
+    file.write_all(pem.as_bytes())
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path, mode: u32) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+}
+
+/// Windows 上用户目录默认只有本人可访问
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path, _mode: u32) -> std::io::Result<()> {
+    Ok(())
+}
+
+fn set_validity(params: &mut CertificateParams, from: DateTime<Utc>, to: DateTime<Utc>) {
+    params.not_before = rcgen::date_time_ymd(from.year(), from.month() as u8, from.day() as u8);
+    params.not_after = rcgen::date_time_ymd(to.year(), to.month() as u8, to.day() as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TempDir;
+
+    /// 返回的 TempDir 需要在测试结束前保持存活
+    fn temp_manager() -> (TempDir, CertManager) {
+        let dir = TempDir::new("certs");
+        let manager = CertManager::with_dir(dir.to_path_buf()).unwrap();
+        (dir, manager)
+    }
+
+    #[test]
+    fn ensure_leaf_reuses_until_names_change() {
+        let (_dir, manager) = temp_manager();
+
+        let first = manager
+            .ensure_leaf(&["order.localhost".to_string()])
+            .unwrap();
+        let ca_pem = fs::read_to_string(manager.ca_cert_path()).unwrap();
+        assert!(ca_pem.contains("BEGIN CERTIFICATE"));
+        assert!(first.key_pem.contains("PRIVATE KEY"));
+
+        let again = manager
+            .ensure_leaf(&["order.localhost".to_string()])
+            .unwrap();
+        assert_eq!(first.cert_pem, again.cert_pem);
+        // CA 不会被重新生成
+        assert_eq!(fs::read_to_string(manager.ca_cert_path()).unwrap(), ca_pem);
+
+        let changed = manager
+            .ensure_leaf(&["order.localhost".to_string(), "order.test".to_string()])
+            .unwrap();
+        assert_ne!(first.cert_pem, changed.cert_pem);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: PathBuf| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(manager.ca_key_path()), 0o600);
+            assert_eq!(mode(manager.dir.join("order.localhost.key.pem")), 0o600);
+            assert_eq!(mode(manager.dir.clone()), 0o700);
+        }
+    }
+
+    #[test]
+    fn new_ca_discards_old_leaf_certificates() {
+        let (_dir, manager) = temp_manager();
+
+        let hosts = vec!["user.localhost".to_string()];
+        let certs = manager.ensure_for_hosts(&hosts).unwrap();
+        assert_eq!(certs.len(), 2);
+        assert_eq!(certs[0].0, "*.localhost");
+        assert!(manager.dir.join("_wildcard.localhost.pem").exists());
+
+        fs::remove_file(manager.ca_cert_path()).unwrap();
+        let renewed = manager.ensure_for_hosts(&hosts).unwrap();
+        assert_ne!(certs[1].1.cert_pem, renewed[1].1.cert_pem);
+
+        let exported = manager.dir.join("exported-ca.pem");
+        manager.export_ca(&exported).unwrap();
+        assert_eq!(
+            fs::read_to_string(exported).unwrap(),
+            fs::read_to_string(manager.ca_cert_path()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::models::{ConfigSource, ZebrasVersion};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// v3 配置文件支持的扩展名，同时存在多个时按此顺序取第一个
+const V3_EXTENSIONS: [&str; 4] = ["ts", "mts", "js", "mjs"];
+
+/// 参与合并的一层配置文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigLayer {
+    pub source: ConfigSource,
+    pub path: PathBuf,
+    pub exists: bool,
+}
+
+impl ConfigLayer {
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// 按版本列出配置文件：main → 环境覆盖层 → local，后面的覆盖前面的
+/// v3：zebras.config.{ts,mts,js,mjs}、zebras.config.<env>.*、zebras.config.local.*
+/// v2：zebra.json、zebra.<env>.json、zebra.local.json
+pub struct ConfigLayers;
+
+impl ConfigLayers {
+    /// 返回所有层，包括尚不存在的层（exists 为 false）；未选择环境时不包含环境层
+    pub fn resolve(
+        project_path: &Path,
+        version: &ZebrasVersion,
+        env: Option<&str>,
+    ) -> Vec<ConfigLayer> {
+        if *version == ZebrasVersion::Managed {
+            return Vec::new();
+        }
+
+        let main = Self::find(project_path, version, None, None);
+        let ext = Self::extension(&main);
+        let mut layers = vec![Self::layer(ConfigSource::Main, main)];
+        if let Some(env) = env {
+            let path = Self::find(project_path, version, Some(env), ext.as_deref());
+            layers.push(Self::layer(ConfigSource::Env, path));
+        }
+        let local = Self::find(project_path, version, Some("local"), ext.as_deref());
+        layers.push(Self::layer(ConfigSource::Local, local));
+        layers
+    }
+
+    /// 主配置文件路径，不存在时返回默认文件名
+    pub fn main_file(project_path: &Path, version: &ZebrasVersion) -> PathBuf {
+        Self::find(project_path, version, None, None)
+    }
+
+    /// 写入端口、debug 等本地修改的文件；不存在时与主配置使用相同扩展名
+    pub fn local_file(project_path: &Path, version: &ZebrasVersion) -> PathBuf {
+        let main = Self::main_file(project_path, version);
+        Self::find(
+            project_path,
+            version,
+            Some("local"),
+            Self::extension(&main).as_deref(),
+        )
+    }
+
+    pub fn has_config(project_path: &Path, version: &ZebrasVersion) -> bool {
+        *version != ZebrasVersion::Managed && Self::main_file(project_path, version).exists()
+    }
+
+    /// 目录中已有的环境覆盖层名称，例如 zebras.config.dev.ts 对应 dev
+    pub fn available_envs(project_path: &Path, version: &ZebrasVersion) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(project_path) else {
+            return Vec::new();
+        };
+
+        let mut envs: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let env = match version {
+                    ZebrasVersion::V3 => V3_EXTENSIONS.iter().find_map(|ext| {
+                        name.strip_prefix("zebras.config.")?
+                            .strip_suffix(&format!(".{}", ext))
+                    }),
+                    ZebrasVersion::V2 => name.strip_prefix("zebra.")?.strip_suffix(".json"),
+                    ZebrasVersion::Managed => None,
+                }?;
+                Self::is_valid_env(env).then(|| env.to_string())
+            })
+            .collect();
+        envs.sort();
+        envs.dedup();
+        envs
+    }
+
+    /// 影响项目识别的文件：zebras.config*、zebra*.json、package.json
+    pub fn is_config_file_name(name: &str) -> bool {
+        name.starts_with("zebras.config")
+            || (name.starts_with("zebra") && name.ends_with(".json"))
+            || name == "package.json"
+    }
+
+    /// 环境名只允许字母、数字、`-` 和 `_`，且不能是 local
+    pub fn is_valid_env(env: &str) -> bool {
+        !env.is_empty()
+            && env != "local"
+            && env
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    fn layer(source: ConfigSource, path: PathBuf) -> ConfigLayer {
+        ConfigLayer {
+            source,
+            exists: path.exists(),
+            path,
+        }
+    }
+
+    /// 按扩展名优先级查找已存在的文件，都不存在时使用 default_ext（默认 ts）
+    fn find(
+        project_path: &Path,
+        version: &ZebrasVersion,
+        suffix: Option<&str>,
+        default_ext: Option<&str>,
+    ) -> PathBuf {
+        let infix = suffix.map(|s| format!(".{}", s)).unwrap_or_default();
+        match version {
+            ZebrasVersion::V2 => project_path.join(format!("zebra{}.json", infix)),
+            _ => {
+                let candidate =
+                    |ext: &str| project_path.join(format!("zebras.config{}.{}", infix, ext));
+                V3_EXTENSIONS
+                    .iter()
+                    .map(|ext| candidate(ext))
+                    .find(|path| path.exists())
+                    .unwrap_or_else(|| candidate(default_ext.unwrap_or(V3_EXTENSIONS[0])))
+            }
+        }
+    }
+
+    fn extension(path: &Path) -> Option<String> {
+        path.extension()
+            .map(|ext| ext.to_string_lossy().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TempDir;
+
+    #[test]
+    fn resolves_layers_with_matching_extensions_and_envs() {
+        let dir = TempDir::new("layers");
+        for name in [
+            "zebras.config.mjs",
+            "zebras.config.dev.ts",
+            "zebras.config.test.mjs",
+        ] {
+            fs::write(dir.join(name), "export default {};\n").unwrap();
+        }
+
+        let layers = ConfigLayers::resolve(&dir, &ZebrasVersion::V3, Some("dev"));
+        let names: Vec<(ConfigSource, String, bool)> = layers
+            .iter()
+            .map(|layer| (layer.source, layer.file_name(), layer.exists))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (ConfigSource::Main, "zebras.config.mjs".to_string(), true),
+                (ConfigSource::Env, "zebras.config.dev.ts".to_string(), true),
+                (
+                    ConfigSource::Local,
+                    "zebras.config.local.mjs".to_string(),
+                    false
+                ),
+            ]
+        );
+        assert_eq!(
+            ConfigLayers::available_envs(&dir, &ZebrasVersion::V3),
+            vec!["dev".to_string(), "test".to_string()]
+        );
+        assert!(!ConfigLayers::has_config(&dir, &ZebrasVersion::V2));
+    }
+}
@@ -0,0 +1,326 @@
+use super::config_layers::ConfigLayers;
+use super::config_parser::ConfigParser;
+use super::config_validator::{V2_TYPES, V3_TYPES};
+use crate::models::{MigrationChangeKind, MigrationFileChange, MigrationPlan, ZebrasVersion};
+use crate::utils::config_edit::{set_key_path, ConfigSyntax};
+use crate::utils::js_object::parse_value_document;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 写在配置最前面的字段，其余字段按名称排序
+const LEADING_KEYS: [&str; 5] = ["name", "platform", "type", "domain", "port"];
+
+const EMPTY_MODULE: &str = "export default {\n};\n";
+
+/// Zebras v2 → v3 配置迁移：
+/// zebra.json → zebras.config.ts，zebra.<env>.json → zebras.config.<env>.ts，
+/// zebra.local.json → zebras.config.local.ts，并把 scripts.start 中的 zebra 命令换成 zebras
+pub struct ConfigMigration;
+
+impl ConfigMigration {
+    /// 生成迁移预览，不修改任何文件
+    pub fn plan(project_path: &Path) -> Result<MigrationPlan, String> {
+        if ConfigLayers::has_config(project_path, &ZebrasVersion::V3) {
+            let existing = ConfigLayers::main_file(project_path, &ZebrasVersion::V3);
+            return Err(format!("已存在 {}，无法迁移", existing.display()));
+        }
+        // 先按 v2 完整解析一遍，配置有问题时不生成迁移
+        let project =
+            ConfigParser::parse_v2_config(project_path, None).map_err(|e| e.to_string())?;
+
+        let mut creates = Vec::new();
+        let mut deletes = Vec::new();
+        let mut notes = Vec::new();
+
+        let main_file = ConfigLayers::main_file(project_path, &ZebrasVersion::V2);
+        let mut main = Self::read_layer(&main_file, &mut notes)?;
+        if main.remove("debug").is_some() && project.debug.is_none() {
+            notes.push("zebra.json 中的 debug 没有有效条目，未迁移".to_string());
+        }
+        creates.push(Self::create(
+            ConfigLayers::main_file(project_path, &ZebrasVersion::V3),
+            Self::render_module(&main)?,
+        ));
+        deletes.push(Self::delete(main_file)?);
+
+        for env in ConfigLayers::available_envs(project_path, &ZebrasVersion::V2) {
+            let source = ConfigLayers::resolve(project_path, &ZebrasVersion::V2, Some(&env))[1]
+                .path
+                .clone();
+            let mut layer = Self::read_layer(&source, &mut notes)?;
+            if layer.remove("debug").is_some() {
+                notes.push(format!(
+                    "zebra.{}.json 中的 debug 未迁移，v3 只从 zebras.config.local.ts 读取 debug",
+                    env
+                ));
+            }
+            let target = ConfigLayers::resolve(project_path, &ZebrasVersion::V3, Some(&env))[1]
+                .path
+                .clone();
+            creates.push(Self::create(target, Self::render_module(&layer)?));
+            deletes.push(Self::delete(source)?);
+        }
+
+        // v3 只从 local 读取 debug，main 和 local 中合并后的 debug 都写到 local
+        let local_file = ConfigLayers::local_file(project_path, &ZebrasVersion::V2);
+        let mut local = if local_file.exists() {
+            let layer = Self::read_layer(&local_file, &mut notes)?;
+            deletes.push(Self::delete(local_file)?);
+            layer
+        } else {
+            Map::new()
+        };
+        local.remove("debug");
+        if let Some(debug) = &project.debug {
+            let entries = debug
+                .iter()
+                .map(|(name, url)| (name.clone(), Value::String(url.clone())))
+                .collect();
+            local.insert("debug".to_string(), Value::Object(entries));
+        }
+        if !local.is_empty() {
+            creates.push(Self::create(
+                ConfigLayers::local_file(project_path, &ZebrasVersion::V3),
+                Self::render_module(&local)?,
+            ));
+        }
+
+        let mut changes = creates;
+        changes.extend(Self::migrate_package_json(project_path, &mut notes)?);
+        changes.extend(deletes);
+
+        Ok(MigrationPlan {
+            project_path: project_path.to_path_buf(),
+            changes,
+            notes,
+        })
+    }
+
+    /// 重新生成预览并与确认的预览比较，期间文件被修改过时拒绝应用
+    pub fn apply(plan: &MigrationPlan) -> Result<(), String> {
+        let current = Self::plan(&plan.project_path)?;
+        if current != *plan {
+            return Err("预览生成后项目文件已被修改，请重新预览".to_string());
+        }
+
+        // 新文件先于删除写入，中途失败时旧配置仍然保留
+        for change in &current.changes {
+            let result = match change.kind {
+                MigrationChangeKind::Create | MigrationChangeKind::Modify => {
+                    fs::write(&change.path, change.content.as_deref().unwrap_or_default())
+                }
+                MigrationChangeKind::Delete => fs::remove_file(&change.path),
+            };
+            result.map_err(|e| format!("写入 {} 失败: {}", change.path.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// 读取一层 v2 配置（JSONC），并把项目类型换成 v3 的名称
+    fn read_layer(path: &Path, notes: &mut Vec<String>) -> Result<Map<String, Value>, String> {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+        let Value::Object(mut layer) = parse_value_document(&content)
+            .map_err(|e| format!("{} {}", file_name, e))?
+            .to_json()
+        else {
+            return Err(format!("{} 的根节点必须是对象", file_name));
+        };
+
+        if let Some(Value::String(type_)) = layer.get("type") {
+            match V2_TYPES.iter().position(|t| t == type_) {
+                Some(index) => {
+                    layer.insert("type".to_string(), Value::from(V3_TYPES[index]));
+                }
+                None => notes.push(format!(
+                    "{} 中的 type '{}' 不是 v2 类型，已原样保留",
+                    file_name, type_
+                )),
+            }
+        }
+        Ok(layer)
+    }
+
+    /// 把 scripts.start 中的 zebra 命令换成 zebras，其余内容保持不变
+    fn migrate_package_json(
+        project_path: &Path,
+        notes: &mut Vec<String>,
+    ) -> Result<Option<MigrationFileChange>, String> {
+        let path = project_path.join("package.json");
+        let Ok(content) = fs::read_to_string(&path) else {
+            notes.push("没有 package.json，请手动添加 zebras 启动脚本".to_string());
+            return Ok(None);
+        };
+        let package = parse_value_document(&content)
+            .map_err(|e| format!("package.json {}", e))?
+            .to_json();
+
+        if package
+            .get("devDependencies")
+            .and_then(|deps| deps.get("zebra-cli"))
+            .is_some()
+        {
+            notes.push("devDependencies 中的 zebra-cli 需要手动换成 zebras-cli".to_string());
+        }
+
+        let Some(start) = package.pointer("/scripts/start").and_then(Value::as_str) else {
+            notes.push("package.json 中没有 scripts.start，请手动添加 zebras 启动脚本".to_string());
+            return Ok(None);
+        };
+        let command = Regex::new(r"\bzebra\b").map_err(|e| e.to_string())?;
+        if !command.is_match(start) {
+            notes.push(format!(
+                "scripts.start `{}` 没有使用 zebra 命令，未修改",
+                start
+            ));
+            return Ok(None);
+        }
+
+        let updated_start = command.replace_all(start, "zebras").to_string();
+        let updated = set_key_path(
+            &content,
+            ConfigSyntax::Json,
+            &["scripts", "start"],
+            &Value::String(updated_start),
+        )?;
+        Ok(Some(MigrationFileChange {
+            diff: Self::line_diff(&content, &updated),
+            path,
+            kind: MigrationChangeKind::Modify,
+            content: Some(updated),
+        }))
+    }
+
+    /// 生成默认导出对象，常用字段在前
+    fn render_module(config: &Map<String, Value>) -> Result<String, String> {
+        let mut keys: Vec<&String> = config.keys().collect();
+        keys.sort_by_key(|key| {
+            let rank = LEADING_KEYS
+                .iter()
+                .position(|leading| leading == key)
+                .unwrap_or(LEADING_KEYS.len());
+            (rank, key.as_str())
+        });
+
+        let mut module = EMPTY_MODULE.to_string();
+        for key in keys {
+            module = set_key_path(&module, ConfigSyntax::TypeScript, &[key], &config[key])?;
+        }
+        Ok(module)
+    }
+
+    fn create(path: PathBuf, content: String) -> MigrationFileChange {
+        MigrationFileChange {
+            diff: Self::line_diff("", &content),
+            path,
+            kind: MigrationChangeKind::Create,
+            content: Some(content),
+        }
+    }
+
+    fn delete(path: PathBuf) -> Result<MigrationFileChange, String> {
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+        Ok(MigrationFileChange {
+            diff: Self::line_diff(&content, ""),
+            path,
+            kind: MigrationChangeKind::Delete,
+            content: None,
+        })
+    }
+
+    /// 基于最长公共子序列的逐行对比
+    fn line_diff(old: &str, new: &str) -> String {
+        let old: Vec<&str> = old.lines().collect();
+        let new: Vec<&str> = new.lines().collect();
+
+        let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                common[i][j] = if old[i] == new[j] {
+                    common[i + 1][j + 1] + 1
+                } else {
+                    common[i + 1][j].max(common[i][j + 1])
+                };
+            }
+        }
+
+        let mut lines = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() || j < new.len() {
+            if i < old.len() && j < new.len() && old[i] == new[j] {
+                lines.push(format!(" {}", old[i]));
+                i += 1;
+                j += 1;
+            } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+                lines.push(format!("-{}", old[i]));
+                i += 1;
+            } else {
+                lines.push(format!("+{}", new[j]));
+                j += 1;
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TempDir;
+
+    #[test]
+    fn migrates_v2_project_after_confirmation() {
+        let dir = TempDir::new("migration");
+        fs::write(
+            dir.join("zebra.json"),
+            r#"{
+  // 用户中心
+  "name": "user",
+  "type": "sub",
+  "domain": "yilu",
+  "port": 8101,
+  "debug": { "base": "http://localhost:8000" },
+}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("zebra.local.json"), r#"{ "port": 8201 }"#).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            "{\n  \"scripts\": {\n    \"start\": \"zebra dev --open\"\n  }\n}\n",
+        )
+        .unwrap();
+
+        let plan = ConfigMigration::plan(&dir).unwrap();
+        let main = &plan.changes[0];
+        assert_eq!(main.path, dir.join("zebras.config.ts"));
+        assert_eq!(
+            main.content.as_deref(),
+            Some(
+                "export default {\n    name: 'user',\n    type: 'app',\n    domain: 'yilu',\n    port: 8101,\n};\n"
+            )
+        );
+        assert!(plan.changes.iter().any(|change| {
+            change.kind == MigrationChangeKind::Modify
+                && change.diff.contains("-    \"start\": \"zebra dev --open\"")
+                && change
+                    .diff
+                    .contains("+    \"start\": \"zebras dev --open\"")
+        }));
+        // 预览不修改文件
+        assert!(!dir.join("zebras.config.ts").exists());
+
+        ConfigMigration::apply(&plan).unwrap();
+        assert!(!dir.join("zebra.json").exists());
+        let project = ConfigParser::parse_v3_config(&dir, None).unwrap();
+        assert_eq!(project.type_, "app");
+        assert_eq!(project.port, 8201);
+        assert_eq!(
+            project.debug.unwrap().get("base").map(String::as_str),
+            Some("http://localhost:8000")
+        );
+    }
+}
@@ -1,9 +1,18 @@
-use crate::models::{ProjectInfo, ZebrasVersion};
+use super::config_layers::ConfigLayers;
+use crate::models::{ConfigSource, ProjectInfo, ZebrasVersion};
+use crate::utils::config_edit::{self, ConfigSyntax};
+use crate::utils::js_object::parse_value_document;
 use crate::utils::ts_parser;
 use regex::Regex;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// 配置中未填写时使用的默认值
+pub const DEFAULT_PLATFORM: &str = "web";
+pub const DEFAULT_TYPE: &str = "app";
+pub const DEFAULT_PORT: u16 = 8000;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -11,6 +20,8 @@ pub enum ParseError {
     IoError(std::io::Error),
     #[error("JSON 错误: {0}")]
     JsonError(serde_json::Error),
+    #[error("配置语法错误: {0}")]
+    SyntaxError(String),
     #[error("缺少字段: {0}")]
     MissingField(String),
     #[error("不是 Zebras 项目")]
@@ -32,40 +43,38 @@ impl From<serde_json::Error> for ParseError {
 pub struct ConfigParser;
 
 impl ConfigParser {
-    /// 解析 Zebras v2 项目配置 (JSON)
-    pub fn parse_v2_config(project_path: &Path) -> Result<ProjectInfo, ParseError> {
-        // 读取 zebra.json
-        let main_config_path = project_path.join("zebra.json");
-        if !main_config_path.exists() {
+    /// 解析 Zebras v2 项目配置 (JSONC)，env 为选择的环境覆盖层
+    pub fn parse_v2_config(
+        project_path: &Path,
+        env: Option<&str>,
+    ) -> Result<ProjectInfo, ParseError> {
+        let layers = ConfigLayers::resolve(project_path, &ZebrasVersion::V2, env);
+        if !layers.first().is_some_and(|main| main.exists) {
             return Err(ParseError::NotAZebrasProject);
         }
 
-        let main_json = fs::read_to_string(&main_config_path)?;
-        let main: Value = serde_json::from_str(&main_json)?;
-
-        // 读取 zebra.local.json (可选)
-        let local_config_path = project_path.join("zebra.local.json");
-        let local: Value = if local_config_path.exists() {
-            let local_json = fs::read_to_string(&local_config_path)?;
-            serde_json::from_str(&local_json)?
-        } else {
-            Value::Object(serde_json::Map::new())
-        };
-
-        // 将本地配置整体覆盖主配置
-        let merged = Self::merge_json(&main, &local);
+        // 按 zebra.json → zebra.<env>.json → zebra.local.json 的顺序逐层覆盖；
+        // 按 JSONC 解析，允许注释和尾随逗号
+        let mut merged = Value::Object(serde_json::Map::new());
+        for layer in layers.iter().filter(|layer| layer.exists) {
+            let json = fs::read_to_string(&layer.path)?;
+            let value = parse_value_document(&json)
+                .map_err(|e| ParseError::SyntaxError(format!("{} {}", layer.file_name(), e)))?
+                .to_json();
+            merged = Self::merge_json(&merged, &value);
+        }
 
         // 提取字段（local > main）
         let platform = merged
             .get("platform")
             .and_then(|v| v.as_str())
-            .unwrap_or("web")
+            .unwrap_or(DEFAULT_PLATFORM)
             .to_string();
 
         let type_ = merged
             .get("type")
             .and_then(|v| v.as_str())
-            .unwrap_or("app")
+            .unwrap_or(DEFAULT_TYPE)
             .to_string();
 
         let name = merged
@@ -83,7 +92,7 @@ impl ConfigParser {
         let port = merged
             .get("port")
             .and_then(Self::value_to_u16)
-            .unwrap_or(8000) as u16;
+            .unwrap_or(DEFAULT_PORT);
 
         // 解析 debug 配置
         let debug = merged.get("debug").and_then(|v| {
@@ -112,42 +121,38 @@ impl ConfigParser {
         project.port = port;
         project.framework = None; // V2 不指定框架
         project.debug = debug;
+        project.config_env = env.map(str::to_string);
 
         Ok(project)
     }
 
-    /// 解析 Zebras v3 项目配置 (TypeScript)
-    pub fn parse_v3_config(project_path: &Path) -> Result<ProjectInfo, ParseError> {
-        // 读取 zebras.config.ts
-        let main_config_path = project_path.join("zebras.config.ts");
-        if !main_config_path.exists() {
+    /// 解析 Zebras v3 项目配置 (TypeScript / JavaScript)，env 为选择的环境覆盖层
+    pub fn parse_v3_config(
+        project_path: &Path,
+        env: Option<&str>,
+    ) -> Result<ProjectInfo, ParseError> {
+        let layers = ConfigLayers::resolve(project_path, &ZebrasVersion::V3, env);
+        if !layers.first().is_some_and(|main| main.exists) {
             return Err(ParseError::NotAZebrasProject);
         }
 
-        let main_content = fs::read_to_string(&main_config_path)?;
-        let main_config = ts_parser::parse_ts_config_simple(&main_content)
-            .map_err(|e| ParseError::MissingField(e))?;
-
-        // 读取 zebras.config.local.ts (可选)
-        let local_config_path = project_path.join("zebras.config.local.ts");
-        let (config, debug) = if local_config_path.exists() {
-            let local_content = fs::read_to_string(&local_config_path)?;
-            let local_config = ts_parser::parse_ts_config_simple(&local_content)
-                .map_err(|e| ParseError::MissingField(e))?;
-            let merged = ts_parser::merge_configs(&main_config, &local_config);
-
-            // 解析 debug 配置（从 local 文件）
-            let debug_map = ts_parser::parse_debug_config(&local_content);
-            let debug = if debug_map.is_empty() {
-                None
-            } else {
-                Some(debug_map)
-            };
-
-            (merged, debug)
-        } else {
-            (main_config, None)
-        };
+        // 按 main → 环境覆盖层 → local 的顺序逐层覆盖
+        let mut config = HashMap::new();
+        let mut debug = None;
+        for layer in layers.iter().filter(|layer| layer.exists) {
+            let content = fs::read_to_string(&layer.path)?;
+            let layer_config = ts_parser::parse_ts_config(&content)
+                .map_err(|e| ParseError::SyntaxError(format!("{} {}", layer.file_name(), e)))?;
+            config = ts_parser::merge_configs(&config, &layer_config);
+
+            // debug 配置只从 local 文件读取
+            if layer.source == ConfigSource::Local {
+                let debug_map = ts_parser::parse_debug_config(&content);
+                if !debug_map.is_empty() {
+                    debug = Some(debug_map);
+                }
+            }
+        }
 
         // 提取字段
         let name = config
@@ -158,18 +163,18 @@ impl ConfigParser {
         let platform = config
             .get("platform")
             .map(|s| s.clone())
-            .unwrap_or_else(|| "web".to_string());
+            .unwrap_or_else(|| DEFAULT_PLATFORM.to_string());
         let type_ = config
             .get("type")
             .map(|s| s.clone())
-            .unwrap_or_else(|| "app".to_string());
+            .unwrap_or_else(|| DEFAULT_TYPE.to_string());
         let domain = config.get("domain").map(|s| s.clone());
         let framework = config.get("framework").map(|s| s.clone());
 
         let port = config
             .get("port")
             .and_then(|s| s.parse::<u16>().ok())
-            .unwrap_or(8000);
+            .unwrap_or(DEFAULT_PORT);
 
         let mut project = ProjectInfo::new(project_path.to_path_buf(), name);
         project.version = ZebrasVersion::V3;
@@ -179,106 +184,129 @@ impl ConfigParser {
         project.port = port;
         project.framework = framework;
         project.debug = debug;
+        project.config_env = env.map(str::to_string);
 
         Ok(project)
     }
 
     /// 自动检测并解析项目配置
     pub fn parse_project(project_path: &Path) -> Result<ProjectInfo, ParseError> {
+        let version = Self::detect_version(project_path).ok_or(ParseError::NotAZebrasProject)?;
+        Self::parse_version(project_path, &version, None)
+    }
+
+    /// 按指定版本和环境解析项目配置
+    pub fn parse_version(
+        project_path: &Path,
+        version: &ZebrasVersion,
+        env: Option<&str>,
+    ) -> Result<ProjectInfo, ParseError> {
+        match version {
+            ZebrasVersion::V3 => Self::parse_v3_config(project_path, env),
+            ZebrasVersion::V2 => Self::parse_v2_config(project_path, env),
+            ZebrasVersion::Managed => Err(ParseError::NotAZebrasProject),
+        }
+    }
+
+    /// 检测项目使用的 Zebras 版本：优先看 package.json 的启动脚本，其次看配置文件
+    pub fn detect_version(project_path: &Path) -> Option<ZebrasVersion> {
         if let Some(version) = Self::detect_version_from_package_json(project_path) {
             println!(
                 "[ConfigParser] Detected version {:?} via package.json for {}",
                 version,
                 project_path.display()
             );
-            return match version {
-                ZebrasVersion::V3 => Self::parse_v3_config(project_path),
-                ZebrasVersion::V2 => Self::parse_v2_config(project_path),
-                ZebrasVersion::Managed => Err(ParseError::NotAZebrasProject),
-            };
+            return Some(version);
         }
         println!(
             "[ConfigParser] package.json unavailable or inconclusive for {}, fallback to file detection",
             project_path.display()
         );
 
-        let has_v3 = project_path.join("zebras.config.ts").exists();
-        let has_v2 = project_path.join("zebra.json").exists();
+        let v3_path = ConfigLayers::main_file(project_path, &ZebrasVersion::V3);
+        let v2_path = ConfigLayers::main_file(project_path, &ZebrasVersion::V2);
+        let has_v3 = v3_path.exists();
+        let has_v2 = v2_path.exists();
 
         match (has_v3, has_v2) {
-            (false, false) => Err(ParseError::NotAZebrasProject),
-            (true, false) => Self::parse_v3_config(project_path),
-            (false, true) => Self::parse_v2_config(project_path),
+            (false, false) => None,
+            (true, false) => Some(ZebrasVersion::V3),
+            (false, true) => Some(ZebrasVersion::V2),
             (true, true) => {
-                // 两个配置文件都存在，尝试比较配置文件的修改时间
-                let v3_path = project_path.join("zebras.config.ts");
-                let v2_path = project_path.join("zebra.json");
-
-                let v3_modified = fs::metadata(&v3_path).and_then(|m| m.modified()).ok();
-                let v2_modified = fs::metadata(&v2_path).and_then(|m| m.modified()).ok();
-
-                match (v3_modified, v2_modified) {
-                    (Some(v3_time), Some(v2_time)) => {
-                        if v3_time > v2_time {
-                            println!(
-                                "[ConfigParser] Both configs exist, zebras.config.ts is newer for {}",
-                                project_path.display()
-                            );
-                            Self::parse_v3_config(project_path)
-                        } else {
-                            println!(
-                                "[ConfigParser] Both configs exist, zebra.json is newer for {}",
-                                project_path.display()
-                            );
-                            Self::parse_v2_config(project_path)
-                        }
-                    }
-                    _ => {
-                        // 无法比较时间，默认使用 V2（更保守的选择）
-                        println!(
-                            "[ConfigParser] Both configs exist, defaulting to V2 for {}",
-                            project_path.display()
-                        );
-                        Self::parse_v2_config(project_path)
-                    }
-                }
+                // 两个配置文件都存在且 package.json 无法判断：不再按修改时间选择，
+                // 固定使用 V2（更保守的选择），由扫描结果报告冲突，用户可在工作区中固定版本
+                println!(
+                    "[ConfigParser] Both {} and {} exist, defaulting to V2 for {}",
+                    v3_path.display(),
+                    v2_path.display(),
+                    project_path.display()
+                );
+                Some(ZebrasVersion::V2)
             }
         }
     }
 
+    /// v2 与 v3 配置同时存在，且 package.json 无法判断版本
+    pub fn is_version_ambiguous(project_path: &Path) -> bool {
+        ConfigLayers::has_config(project_path, &ZebrasVersion::V3)
+            && ConfigLayers::has_config(project_path, &ZebrasVersion::V2)
+            && Self::detect_version_from_package_json(project_path).is_none()
+    }
+
+    /// 端口写入的本地配置文件路径
+    pub fn local_config_path(project: &ProjectInfo) -> Option<PathBuf> {
+        match project.version {
+            ZebrasVersion::Managed => None,
+            _ => Some(ConfigLayers::local_file(&project.path, &project.version)),
+        }
+    }
+
     /// 更新项目的本地配置文件中的端口
-    pub fn update_port(project: &ProjectInfo, new_port: u16) -> Result<(), ParseError> {
+    /// strict_json 为 true 时 v2 本地配置写成严格 JSON（去掉注释和尾随逗号）
+    pub fn update_port(
+        project: &ProjectInfo,
+        new_port: u16,
+        strict_json: bool,
+    ) -> Result<(), ParseError> {
         match project.version {
-            ZebrasVersion::V2 => Self::update_v2_port(&project.path, new_port),
+            ZebrasVersion::V2 => Self::update_v2_port(&project.path, new_port, strict_json),
             ZebrasVersion::V3 => Self::update_v3_port(&project.path, new_port),
             ZebrasVersion::Managed => Err(ParseError::NotAZebrasProject),
         }
     }
 
-    /// 更新 Zebras v2 的端口配置
-    fn update_v2_port(project_path: &Path, new_port: u16) -> Result<(), ParseError> {
-        let local_path = project_path.join("zebra.local.json");
+    /// 更新 Zebras v2 的端口配置，只改动 port 字段，保留文件原有格式
+    fn update_v2_port(
+        project_path: &Path,
+        new_port: u16,
+        strict_json: bool,
+    ) -> Result<(), ParseError> {
+        let local_path = ConfigLayers::local_file(project_path, &ZebrasVersion::V2);
 
-        let mut config: Value = if local_path.exists() {
-            let content = fs::read_to_string(&local_path)?;
-            serde_json::from_str(&content)?
+        let content = if local_path.exists() {
+            fs::read_to_string(&local_path)?
         } else {
-            Value::Object(serde_json::Map::new())
+            "{\n}\n".to_string()
         };
 
-        if let Some(obj) = config.as_object_mut() {
-            obj.insert("port".to_string(), Value::Number(new_port.into()));
+        let mut updated = config_edit::set_key_path(
+            &content,
+            ConfigSyntax::Json,
+            &["port"],
+            &Value::from(new_port),
+        )
+        .map_err(ParseError::SyntaxError)?;
+        if strict_json {
+            updated = config_edit::to_strict_json(&updated).map_err(ParseError::SyntaxError)?;
         }
-
-        let json_string = serde_json::to_string_pretty(&config)?;
-        fs::write(&local_path, json_string)?;
+        fs::write(&local_path, updated)?;
 
         Ok(())
     }
 
     /// 更新 Zebras v3 的端口配置
     fn update_v3_port(project_path: &Path, new_port: u16) -> Result<(), ParseError> {
-        let local_path = project_path.join("zebras.config.local.ts");
+        let local_path = ConfigLayers::local_file(project_path, &ZebrasVersion::V3);
 
         let content = if local_path.exists() {
             fs::read_to_string(&local_path)?
@@ -286,7 +314,8 @@ impl ConfigParser {
             "export default {\n};\n".to_string()
         };
 
-        let updated = ts_parser::update_port_in_ts(&content, new_port);
+        let updated =
+            ts_parser::update_port_in_ts(&content, new_port).map_err(ParseError::SyntaxError)?;
         fs::write(&local_path, updated)?;
 
         Ok(())
@@ -320,10 +349,51 @@ impl ConfigParser {
             );
         }
 
+        if let Some(version) = Self::version_from_dev_dependencies(&content) {
+            println!(
+                "[ConfigParser] devDependencies resolved to {:?} for {}",
+                version,
+                project_path.display()
+            );
+            return Some(version);
+        }
+
         // 不做全文搜索 fallback，让 parse_project 回退到文件检测
         None
     }
 
+    /// 根据 devDependencies 中的 zebras-cli / zebra-cli 判断版本，
+    /// zebras-cli 2.x 及以下仍是 v2；两者同时存在且结论不同时视为无法判断
+    fn version_from_dev_dependencies(content: &str) -> Option<ZebrasVersion> {
+        let package = parse_value_document(content).ok()?.to_json();
+        let dev_dependencies = package.get("devDependencies")?.as_object()?;
+
+        let v3_cli = dev_dependencies.get("zebras-cli").map(|range| {
+            match range.as_str().and_then(Self::major_version) {
+                Some(major) if major <= 2 => ZebrasVersion::V2,
+                _ => ZebrasVersion::V3,
+            }
+        });
+        let v2_cli = dev_dependencies
+            .contains_key("zebra-cli")
+            .then_some(ZebrasVersion::V2);
+
+        match (v3_cli, v2_cli) {
+            (Some(a), Some(b)) if a != b => None,
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// 版本范围中的主版本号，例如 "^3.1.0" → 3；latest、workspace:* 等返回 None
+    fn major_version(range: &str) -> Option<u64> {
+        range
+            .trim_start_matches(|c: char| !c.is_ascii_digit())
+            .split(|c: char| !c.is_ascii_digit())
+            .next()?
+            .parse()
+            .ok()
+    }
+
     fn extract_start_script(content: &str) -> Option<String> {
         if let Ok(package) = serde_json::from_str::<Value>(content) {
             if let Some(start) = package
@@ -433,7 +503,7 @@ impl ConfigParser {
 
     fn value_to_u16(value: &Value) -> Option<u16> {
         if let Some(number) = value.as_u64() {
-            return Some(number as u16);
+            return u16::try_from(number).ok();
         }
         if let Some(text) = value.as_str() {
             return text.parse::<u16>().ok();
@@ -445,6 +515,7 @@ impl ConfigParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::test_dir::TempDir;
     use std::path::PathBuf;
 
     #[test]
@@ -454,6 +525,82 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_v3_merges_main_env_and_local_layers() {
+        let dir = TempDir::new("parser");
+        fs::write(
+            dir.join("zebras.config.mjs"),
+            "export default { name: 'order', port: 8003, domain: 'order' };\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("zebras.config.dev.mjs"),
+            "export default { domain: 'order-dev', port: 8103 };\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("zebras.config.local.mjs"),
+            "export default { port: 9003 };\n",
+        )
+        .unwrap();
+
+        let project = ConfigParser::parse_v3_config(&dir, Some("dev")).unwrap();
+        assert_eq!(project.domain.as_deref(), Some("order-dev"));
+        assert_eq!(project.port, 9003);
+        assert_eq!(project.config_env.as_deref(), Some("dev"));
+
+        let project = ConfigParser::parse_v3_config(&dir, None).unwrap();
+        assert_eq!(project.domain.as_deref(), Some("order"));
+        assert_eq!(
+            ConfigParser::local_config_path(&project).unwrap(),
+            dir.join("zebras.config.local.mjs")
+        );
+    }
+
+    #[test]
+    fn parse_v2_accepts_comments_and_trailing_commas() {
+        let dir = TempDir::new("parser");
+        fs::write(
+            dir.join("zebra.json"),
+            "{\n  // 用户中心\n  \"name\": \"user\",\n  \"type\": \"sub\", /* 子应用 */\n  \"port\": 8004,\n}\n",
+        )
+        .unwrap();
+        fs::write(dir.join("zebra.local.json"), "{ \"port\": 9004, }").unwrap();
+
+        let project = ConfigParser::parse_v2_config(&dir, None).unwrap();
+        assert_eq!(project.name, "user");
+        assert_eq!(project.type_, "sub");
+        assert_eq!(project.port, 9004);
+    }
+
+    #[test]
+    fn dev_dependencies_decide_version() {
+        let package = |deps: &str| format!("{{ \"devDependencies\": {{ {} }}, }}", deps);
+
+        assert_eq!(
+            ConfigParser::version_from_dev_dependencies(&package("\"zebras-cli\": \"^3.2.0\"")),
+            Some(ZebrasVersion::V3)
+        );
+        assert_eq!(
+            ConfigParser::version_from_dev_dependencies(&package("\"zebras-cli\": \"~2.8.1\"")),
+            Some(ZebrasVersion::V2)
+        );
+        assert_eq!(
+            ConfigParser::version_from_dev_dependencies(&package("\"zebra-cli\": \"1.4.0\"")),
+            Some(ZebrasVersion::V2)
+        );
+        assert_eq!(
+            ConfigParser::version_from_dev_dependencies(&package(
+                "\"zebras-cli\": \"latest\", \"zebra-cli\": \"1.4.0\""
+            )),
+            None
+        );
+        assert_eq!(
+            ConfigParser::version_from_dev_dependencies(&package("\"vite\": \"^5.0.0\"")),
+            None
+        );
+    }
+
     #[test]
     fn extract_start_script_ignores_line_comment() {
         let content = r#"
@@ -0,0 +1,274 @@
+use super::config_layers::ConfigLayers;
+use super::config_parser::{DEFAULT_PLATFORM, DEFAULT_PORT, DEFAULT_TYPE};
+use crate::models::{
+    ConfigFile, ConfigSource, ConfigTreeEntry, ConfigTreeNode, ProjectConfig, ZebrasVersion,
+};
+use crate::utils::js_object::{
+    line_col, parse_config_module, parse_value_document, JsNode, JsValue,
+};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// 读取项目的完整配置：main、环境覆盖层与 local 依次深度合并，每个值标注来源文件和行号
+pub struct ConfigTreeBuilder;
+
+impl ConfigTreeBuilder {
+    pub fn load(
+        project_path: &Path,
+        version: &ZebrasVersion,
+        env: Option<&str>,
+    ) -> Result<ProjectConfig, String> {
+        if *version == ZebrasVersion::Managed {
+            return Err("项目实例和自动识别的项目没有 Zebras 配置文件".to_string());
+        }
+        let is_ts = *version == ZebrasVersion::V3;
+
+        let mut files = Vec::new();
+        let mut tree: Option<ConfigTreeNode> = None;
+
+        for layer in ConfigLayers::resolve(project_path, version, env) {
+            let name = layer.file_name();
+            files.push(ConfigFile {
+                source: layer.source,
+                path: layer.path.to_string_lossy().to_string(),
+                exists: layer.exists,
+            });
+
+            if !layer.exists {
+                if layer.source == ConfigSource::Main {
+                    return Err(format!("未找到 {}", name));
+                }
+                continue;
+            }
+
+            let content = fs::read_to_string(&layer.path)
+                .map_err(|e| format!("读取 {} 失败: {}", name, e))?;
+            let parsed = if is_ts {
+                parse_config_module(&content)
+            } else {
+                parse_value_document(&content)
+            }
+            .map_err(|e| format!("{} {}", name, e))?;
+
+            let layer = Self::from_node(&parsed, layer.source, &content);
+            tree = Some(match tree {
+                Some(base) => Self::merge(base, layer),
+                None => layer,
+            });
+        }
+
+        let mut tree = tree.ok_or_else(|| "未找到配置文件".to_string())?;
+        Self::apply_defaults(&mut tree);
+        let merged = tree.to_json();
+
+        Ok(ProjectConfig {
+            version: version.clone(),
+            files,
+            tree,
+            merged,
+        })
+    }
+
+    fn from_node(node: &JsNode, source: ConfigSource, content: &str) -> ConfigTreeNode {
+        let line = Some(line_col(content, node.span.start).0);
+
+        match &node.value {
+            JsValue::Object(props) => {
+                let mut entries: Vec<ConfigTreeEntry> = Vec::new();
+                for prop in props {
+                    let mut child = Self::from_node(&prop.value, source, content);
+                    // 叶子标注键所在的行
+                    if let ConfigTreeNode::Value { line, .. } = &mut child {
+                        *line = Some(line_col(content, prop.key_span.start).0);
+                    }
+                    Self::upsert(&mut entries, prop.key.clone(), child);
+                }
+                ConfigTreeNode::Object {
+                    source,
+                    line,
+                    entries,
+                }
+            }
+            JsValue::Expression(raw) => ConfigTreeNode::Value {
+                value: Value::Null,
+                source,
+                line,
+                expression: Some(raw.clone()),
+            },
+            _ => ConfigTreeNode::Value {
+                value: node.to_json(),
+                source,
+                line,
+                expression: None,
+            },
+        }
+    }
+
+    /// 对象逐键合并，其余值由 overlay 覆盖；overlay 中的 null 不覆盖
+    fn merge(base: ConfigTreeNode, overlay: ConfigTreeNode) -> ConfigTreeNode {
+        match (base, overlay) {
+            (
+                ConfigTreeNode::Object {
+                    source,
+                    line,
+                    mut entries,
+                },
+                ConfigTreeNode::Object {
+                    entries: overlay_entries,
+                    ..
+                },
+            ) => {
+                for entry in overlay_entries {
+                    match entries.iter_mut().find(|e| e.key == entry.key) {
+                        Some(existing) => {
+                            existing.node = Self::merge(existing.node.clone(), entry.node);
+                        }
+                        None => entries.push(entry),
+                    }
+                }
+                ConfigTreeNode::Object {
+                    source,
+                    line,
+                    entries,
+                }
+            }
+            (
+                base,
+                ConfigTreeNode::Value {
+                    value: Value::Null,
+                    expression: None,
+                    ..
+                },
+            ) => base,
+            (_, overlay) => overlay,
+        }
+    }
+
+    /// 同名键覆盖原有位置，新键追加到末尾
+    fn upsert(entries: &mut Vec<ConfigTreeEntry>, key: String, node: ConfigTreeNode) {
+        match entries.iter_mut().find(|entry| entry.key == key) {
+            Some(entry) => entry.node = node,
+            None => entries.push(ConfigTreeEntry { key, node }),
+        }
+    }
+
+    fn apply_defaults(tree: &mut ConfigTreeNode) {
+        let ConfigTreeNode::Object { entries, .. } = tree else {
+            return;
+        };
+
+        let defaults = [
+            ("platform", Value::from(DEFAULT_PLATFORM)),
+            ("type", Value::from(DEFAULT_TYPE)),
+            ("port", Value::from(DEFAULT_PORT)),
+        ];
+        for (key, value) in defaults {
+            if !entries.iter().any(|entry| entry.key == key) {
+                entries.push(ConfigTreeEntry {
+                    key: key.to_string(),
+                    node: ConfigTreeNode::Value {
+                        value,
+                        source: ConfigSource::Default,
+                        line: None,
+                        expression: None,
+                    },
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TempDir;
+
+    fn child<'a>(node: &'a ConfigTreeNode, key: &str) -> Option<&'a ConfigTreeNode> {
+        match node {
+            ConfigTreeNode::Object { entries, .. } => entries
+                .iter()
+                .find(|entry| entry.key == key)
+                .map(|entry| &entry.node),
+            ConfigTreeNode::Value { .. } => None,
+        }
+    }
+
+    fn leaf(node: &ConfigTreeNode) -> (&Value, ConfigSource, Option<usize>) {
+        match node {
+            ConfigTreeNode::Value {
+                value,
+                source,
+                line,
+                ..
+            } => (value, *source, *line),
+            other => panic!("expected leaf, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn merges_v3_configs_with_sources_and_lines() {
+        let dir = TempDir::with_files("config-tree", &[
+            (
+                "zebras.config.ts",
+                "export default defineConfig({\n  name: 'order',\n  port: 8003,\n  proxy: {\n    '/api': { target: 'http://a', changeOrigin: true },\n  },\n  publicPath: process.env.PUBLIC_PATH,\n});\n",
+            ),
+            (
+                "zebras.config.local.ts",
+                "export default {\n  // 本地端口\n  port: 9003,\n  proxy: { '/api': { target: 'http://b' } },\n};\n",
+            ),
+        ]);
+
+        let config = ConfigTreeBuilder::load(&dir, &ZebrasVersion::V3, None).unwrap();
+        let tree = &config.tree;
+
+        assert_eq!(
+            leaf(child(tree, "name").unwrap()),
+            (&Value::from("order"), ConfigSource::Main, Some(2))
+        );
+        assert_eq!(
+            leaf(child(tree, "port").unwrap()),
+            (&Value::from(9003), ConfigSource::Local, Some(3))
+        );
+
+        let api = child(tree, "proxy").and_then(|p| child(p, "/api")).unwrap();
+        assert_eq!(leaf(child(api, "target").unwrap()).1, ConfigSource::Local);
+        assert_eq!(
+            leaf(child(api, "changeOrigin").unwrap()),
+            (&Value::Bool(true), ConfigSource::Main, Some(5))
+        );
+
+        assert!(matches!(
+            child(tree, "publicPath").unwrap(),
+            ConfigTreeNode::Value { expression: Some(raw), .. } if raw == "process.env.PUBLIC_PATH"
+        ));
+        assert_eq!(
+            leaf(child(tree, "platform").unwrap()),
+            (&Value::from("web"), ConfigSource::Default, None)
+        );
+        assert_eq!(
+            config.merged["proxy"]["/api"]["target"],
+            Value::from("http://b")
+        );
+    }
+
+    #[test]
+    fn merges_v2_json_and_keeps_main_when_local_is_missing() {
+        let dir = TempDir::with_files("config-tree", &[(
+            "zebra.json",
+            "{\n  \"name\": \"user\",\n  \"type\": \"main\",\n  \"externals\": [\"react\"]\n}\n",
+        )]);
+
+        let config = ConfigTreeBuilder::load(&dir, &ZebrasVersion::V2, None).unwrap();
+        assert!(!config.files[1].exists);
+        assert_eq!(
+            leaf(child(&config.tree, "type").unwrap()),
+            (&Value::from("main"), ConfigSource::Main, Some(3))
+        );
+        assert_eq!(config.merged["externals"], serde_json::json!(["react"]));
+        assert_eq!(
+            leaf(child(&config.tree, "port").unwrap()).1,
+            ConfigSource::Default
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use super::config_layers::ConfigLayers;
+use super::config_parser::DEFAULT_PORT;
+use crate::models::{ConfigDiagnostic, ConfigSource, DiagnosticSeverity, ZebrasVersion};
+use crate::utils::js_object::{
+    line_col, parse_config_module, parse_value_document, JsNode, JsValue,
+};
+use std::fs;
+use std::path::Path;
+
+/// v2 与 v3 的项目类型，按位置一一对应：main→base、sub→app、component→lib
+pub const V2_TYPES: [&str; 3] = ["main", "sub", "component"];
+pub const V3_TYPES: [&str; 3] = ["base", "app", "lib"];
+
+/// 解析成功的单个配置文件
+struct ParsedLayer {
+    file: String,
+    content: String,
+    root: JsNode,
+}
+
+impl ParsedLayer {
+    fn diagnostic(
+        &self,
+        node: &JsNode,
+        severity: DiagnosticSeverity,
+        message: String,
+        suggestion: String,
+    ) -> ConfigDiagnostic {
+        let (line, column) = line_col(&self.content, node.span.start);
+        ConfigDiagnostic {
+            file: self.file.clone(),
+            line: Some(line),
+            column: Some(column),
+            severity,
+            message,
+            suggestion: Some(suggestion),
+        }
+    }
+}
+
+/// 配置校验：找出会让启动器静默退回默认值或直接丢弃项目的问题
+pub struct ConfigValidator;
+
+impl ConfigValidator {
+    pub fn validate(
+        project_path: &Path,
+        version: &ZebrasVersion,
+        env: Option<&str>,
+    ) -> Vec<ConfigDiagnostic> {
+        if *version == ZebrasVersion::Managed {
+            return Vec::new();
+        }
+        let is_ts = *version == ZebrasVersion::V3;
+
+        let mut diagnostics = Vec::new();
+        let resolved = ConfigLayers::resolve(project_path, version, env);
+
+        let mut layers = Vec::new();
+        let mut main_parsed = false;
+        for layer in resolved {
+            let file = layer.file_name();
+            if !layer.exists {
+                if layer.source == ConfigSource::Env {
+                    diagnostics.push(ConfigDiagnostic {
+                        file: file.clone(),
+                        line: None,
+                        column: None,
+                        severity: DiagnosticSeverity::Warning,
+                        message: format!(
+                            "已选择环境 {}，但 {} 不存在",
+                            env.unwrap_or_default(),
+                            file
+                        ),
+                        suggestion: Some("创建该文件，或切换到其他环境".to_string()),
+                    });
+                }
+                continue;
+            }
+
+            let content = match fs::read_to_string(&layer.path) {
+                Ok(content) => content,
+                Err(e) => {
+                    diagnostics.push(Self::file_diagnostic(&file, format!("读取失败: {}", e)));
+                    continue;
+                }
+            };
+
+            let parsed = if is_ts {
+                parse_config_module(&content)
+            } else {
+                parse_value_document(&content)
+            };
+            match parsed {
+                Ok(root) if matches!(root.value, JsValue::Object(_)) => {
+                    main_parsed |= layer.source == ConfigSource::Main;
+                    layers.push(ParsedLayer {
+                        file,
+                        content,
+                        root,
+                    });
+                }
+                Ok(root) => {
+                    let (line, column) = line_col(&content, root.span.start);
+                    diagnostics.push(ConfigDiagnostic {
+                        file,
+                        line: Some(line),
+                        column: Some(column),
+                        severity: DiagnosticSeverity::Error,
+                        message: "配置的顶层必须是对象".to_string(),
+                        suggestion: Some("把配置写成 { ... } 形式的对象".to_string()),
+                    });
+                }
+                Err(e) => diagnostics.push(ConfigDiagnostic {
+                    file,
+                    line: Some(e.line),
+                    column: Some(e.column),
+                    severity: DiagnosticSeverity::Error,
+                    message: format!("无法解析: {}", e.message),
+                    suggestion: Some("检查该位置附近的括号、引号和逗号是否成对".to_string()),
+                }),
+            }
+        }
+
+        if main_parsed {
+            Self::check_name(project_path, &layers, is_ts, &mut diagnostics);
+        }
+        for layer in &layers {
+            if let Some(port) = layer.root.get("port") {
+                diagnostics.extend(Self::check_port(layer, port));
+            }
+            if let Some(type_) = layer.root.get("type") {
+                diagnostics.extend(Self::check_type(layer, type_, version));
+            }
+        }
+
+        diagnostics
+    }
+
+    /// 没有定位信息的文件级错误，例如读取失败
+    pub fn file_diagnostic(file: &str, message: String) -> ConfigDiagnostic {
+        ConfigDiagnostic {
+            file: file.to_string(),
+            line: None,
+            column: None,
+            severity: DiagnosticSeverity::Error,
+            message,
+            suggestion: None,
+        }
+    }
+
+    /// v2 与 v3 配置同时存在且无法判断版本时的提示，used_file 为当前采用的主配置
+    pub fn ambiguity_diagnostic(project_path: &Path, used_file: &Path) -> ConfigDiagnostic {
+        let v3_file = ConfigLayers::main_file(project_path, &ZebrasVersion::V3);
+        let file_name = |path: &Path| {
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        };
+
+        ConfigDiagnostic {
+            file: file_name(used_file),
+            line: None,
+            column: None,
+            severity: DiagnosticSeverity::Warning,
+            message: format!(
+                "同时存在 {} 和 zebra.json，无法判断版本，暂按 {} 解析",
+                file_name(&v3_file),
+                file_name(used_file)
+            ),
+            suggestion: Some(
+                "在项目卡片中固定版本，删除不再使用的配置文件，或在 package.json 中通过 scripts.start / devDependencies 明确使用 zebras-cli（v3）或 zebra-cli（v2）"
+                    .to_string(),
+            ),
+        }
+    }
+
+    fn check_name(
+        project_path: &Path,
+        layers: &[ParsedLayer],
+        is_ts: bool,
+        diagnostics: &mut Vec<ConfigDiagnostic>,
+    ) {
+        // local 覆盖 main，以最后一个出现的 name 为准
+        let found = layers
+            .iter()
+            .rev()
+            .find_map(|layer| layer.root.get("name").map(|node| (layer, node)));
+
+        let Some((layer, node)) = found else {
+            let main = &layers[0];
+            let dir_name = project_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "my-app".to_string());
+            let example = if is_ts {
+                format!("name: '{}'", dir_name)
+            } else {
+                format!("\"name\": \"{}\"", dir_name)
+            };
+            diagnostics.push(main.diagnostic(
+                &main.root,
+                DiagnosticSeverity::Error,
+                "缺少 name 字段，项目无法识别".to_string(),
+                format!("在 {} 中添加 {}", main.file, example),
+            ));
+            return;
+        };
+
+        match node.as_str() {
+            Some(name) if !name.trim().is_empty() => {}
+            Some(_) => diagnostics.push(layer.diagnostic(
+                node,
+                DiagnosticSeverity::Error,
+                "name 不能为空".to_string(),
+                "填写项目名称，通常与 package.json 的 name 一致".to_string(),
+            )),
+            None => diagnostics.push(layer.diagnostic(
+                node,
+                DiagnosticSeverity::Error,
+                format!("name 必须是字符串字面量，当前为 {}", Self::describe(node)),
+                "把 name 改成字符串，例如 'order'".to_string(),
+            )),
+        }
+    }
+
+    fn check_port(layer: &ParsedLayer, node: &JsNode) -> Option<ConfigDiagnostic> {
+        let suggestion = format!("改为 1-65535 之间的整数，例如 {}", DEFAULT_PORT);
+        let (severity, message) = match &node.value {
+            JsValue::Number(n) if n.fract() != 0.0 => {
+                (DiagnosticSeverity::Error, format!("端口 {} 不是整数", n))
+            }
+            JsValue::Number(n) if *n < 1.0 || *n > f64::from(u16::MAX) => (
+                DiagnosticSeverity::Error,
+                format!("端口 {} 超出范围 1-65535", n),
+            ),
+            JsValue::Number(_) => return None,
+            JsValue::String(text) => match text.trim().parse::<u64>() {
+                Ok(port) if (1..=u64::from(u16::MAX)).contains(&port) => return None,
+                Ok(port) => (
+                    DiagnosticSeverity::Error,
+                    format!("端口 {} 超出范围 1-65535", port),
+                ),
+                Err(_) => (
+                    DiagnosticSeverity::Error,
+                    format!("端口 '{}' 不是数字", text),
+                ),
+            },
+            JsValue::Expression(raw) => (
+                DiagnosticSeverity::Warning,
+                format!(
+                    "端口是表达式 `{}`，启动器无法求值，将使用默认端口 {}",
+                    raw, DEFAULT_PORT
+                ),
+            ),
+            _ => (
+                DiagnosticSeverity::Error,
+                format!("端口必须是数字，当前为 {}", Self::describe(node)),
+            ),
+        };
+
+        let fallback = if severity == DiagnosticSeverity::Error {
+            format!("，启动器将使用默认端口 {}", DEFAULT_PORT)
+        } else {
+            String::new()
+        };
+        Some(layer.diagnostic(node, severity, message + &fallback, suggestion))
+    }
+
+    fn check_type(
+        layer: &ParsedLayer,
+        node: &JsNode,
+        version: &ZebrasVersion,
+    ) -> Option<ConfigDiagnostic> {
+        let (allowed, other) = match version {
+            ZebrasVersion::V2 => (V2_TYPES, V3_TYPES),
+            _ => (V3_TYPES, V2_TYPES),
+        };
+        let version_label = if *version == ZebrasVersion::V2 {
+            "v2"
+        } else {
+            "v3"
+        };
+        let options = format!("可选值: {}", allowed.join("、"));
+
+        let Some(value) = node.as_str() else {
+            return Some(layer.diagnostic(
+                node,
+                DiagnosticSeverity::Warning,
+                format!("type 必须是字符串，当前为 {}", Self::describe(node)),
+                options,
+            ));
+        };
+        if allowed.contains(&value) {
+            return None;
+        }
+
+        // 常见情况：混用了另一个版本的类型名
+        let suggestion = match other.iter().position(|t| *t == value) {
+            Some(index) => format!("{} 中对应的类型是 '{}'", version_label, allowed[index]),
+            None => options,
+        };
+        Some(layer.diagnostic(
+            node,
+            DiagnosticSeverity::Warning,
+            format!("未知的 {} 项目类型 '{}'", version_label, value),
+            suggestion,
+        ))
+    }
+
+    fn describe(node: &JsNode) -> String {
+        match &node.value {
+            JsValue::Object(_) => "对象".to_string(),
+            JsValue::Array(_) => "数组".to_string(),
+            JsValue::String(s) => format!("字符串 '{}'", s),
+            JsValue::Number(n) => format!("数字 {}", n),
+            JsValue::Bool(b) => format!("布尔值 {}", b),
+            JsValue::Null => "null".to_string(),
+            JsValue::Expression(raw) => format!("表达式 `{}`", raw),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TempDir;
+
+    #[test]
+    fn reports_syntax_errors_with_position() {
+        let dir = TempDir::with_files(
+            "validator",
+            &[(
+                "zebras.config.ts",
+                "export default {\n  name: 'order'\n  port: 8003,\n};\n",
+            )],
+        );
+
+        let diagnostics = ConfigValidator::validate(&dir, &ZebrasVersion::V3, None);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert_eq!(diagnostics[0].file, "zebras.config.ts");
+        assert_eq!(diagnostics[0].line, Some(3));
+    }
+
+    #[test]
+    fn reports_missing_name_bad_ports_and_unknown_types() {
+        let dir = TempDir::with_files(
+            "validator",
+            &[
+                (
+                    "zebras.config.ts",
+                    "export default {\n  type: 'sub',\n  port: 70000,\n};\n",
+                ),
+                (
+                    "zebras.config.local.ts",
+                    "export default {\n  port: 'abc',\n};\n",
+                ),
+            ],
+        );
+
+        let diagnostics = ConfigValidator::validate(&dir, &ZebrasVersion::V3, None);
+        let find = |needle: &str| {
+            diagnostics
+                .iter()
+                .find(|d| d.message.contains(needle))
+                .unwrap_or_else(|| panic!("missing `{}` in {:?}", needle, diagnostics))
+        };
+
+        let name = find("缺少 name");
+        assert_eq!((name.line, name.column), (Some(1), Some(16)));
+        assert_eq!(find("70000").line, Some(3));
+        let not_number = find("'abc' 不是数字");
+        assert_eq!(not_number.file, "zebras.config.local.ts");
+        assert_eq!((not_number.line, not_number.column), (Some(2), Some(9)));
+        let type_ = find("未知的 v3 项目类型 'sub'");
+        assert_eq!(type_.severity, DiagnosticSeverity::Warning);
+        assert_eq!(type_.suggestion.as_deref(), Some("v3 中对应的类型是 'app'"));
+    }
+}
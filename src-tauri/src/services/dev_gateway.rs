@@ -0,0 +1,1037 @@
+use crate::models::{GatewayRoute, GatewayStatus, ProcessInfo, ProjectInfo};
+use crate::services::{CertManager, LeafCert};
+use hyper::body::HttpBody;
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderName, HeaderValue, CONNECTION, HOST, UPGRADE};
+use hyper::server::conn::Http;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Client, Request, Response, Server, StatusCode};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::{watch, Mutex};
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+
+/// 逐跳首部，不能转发给上游
+pub(crate) const HOP_BY_HOP_HEADERS: [&str; 8] = [
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// 停止网关时等待连接结束的上限，超时后直接中止
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+type RouteTable = Arc<RwLock<Vec<GatewayRoute>>>;
+
+struct RunningGateway {
+    port: u16,
+    https_port: Option<u16>,
+    /// 置为 true 时停止监听，并断开仍在转发的流式响应和 WebSocket
+    closed: watch::Sender<bool>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+/// HTTPS 监听的参数：证书由 certs 签发，hosts 为需要预先签发证书的域名
+pub struct GatewayTlsOptions {
+    pub port: u16,
+    pub certs: CertManager,
+    pub hosts: Vec<String>,
+}
+
+struct GatewayTls {
+    certs: CertManager,
+    resolver: Arc<SniCertResolver>,
+}
+
+/// 按 SNI 选择证书，未知域名使用 *.localhost 通配证书
+#[derive(Default)]
+struct SniCertResolver {
+    certs: RwLock<HashMap<String, Arc<CertifiedKey>>>,
+}
+
+impl SniCertResolver {
+    fn contains(&self, host: &str) -> bool {
+        self.certs
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(host)
+    }
+
+    fn insert(&self, host: String, leaf: &LeafCert) -> Result<(), String> {
+        let key = certified_key(leaf)?;
+        self.certs
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(host, Arc::new(key));
+        Ok(())
+    }
+}
+
+impl ResolvesServerCert for SniCertResolver {
+    fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        let certs = self.certs.read().unwrap_or_else(|e| e.into_inner());
+        client_hello
+            .server_name()
+            .and_then(|name| certs.get(&name.to_lowercase()))
+            .or_else(|| certs.get(WILDCARD_HOST))
+            .cloned()
+    }
+}
+
+const WILDCARD_HOST: &str = "*.localhost";
+
+fn certified_key(leaf: &LeafCert) -> Result<CertifiedKey, String> {
+    let chain = rustls_pemfile::certs(&mut leaf.cert_pem.as_bytes())
+        .map_err(|e| format!("解析证书失败: {}", e))?
+        .into_iter()
+        .map(rustls::Certificate)
+        .collect();
+    let key = rustls_pemfile::pkcs8_private_keys(&mut leaf.key_pem.as_bytes())
+        .map_err(|e| format!("解析证书私钥失败: {}", e))?
+        .into_iter()
+        .next()
+        .ok_or("证书私钥为空".to_string())?;
+    let signing_key = rustls::sign::any_supported_type(&rustls::PrivateKey(key))
+        .map_err(|e| format!("不支持的证书私钥: {}", e))?;
+
+    Ok(CertifiedKey::new(chain, signing_key))
+}
+
+/// 本地开发网关：在一个端口上按 Host 或路径前缀把请求转发到各项目的开发服务器，
+/// 可选同时在另一个端口提供 HTTPS
+pub struct DevGateway {
+    routes: RouteTable,
+    server: Mutex<Option<RunningGateway>>,
+    tls: std::sync::Mutex<Option<GatewayTls>>,
+}
+
+impl Default for DevGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DevGateway {
+    pub fn new() -> Self {
+        Self {
+            routes: Arc::new(RwLock::new(Vec::new())),
+            server: Mutex::new(None),
+            tls: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// 启动网关，已在其他端口运行时先停止
+    pub async fn start(
+        &self,
+        port: u16,
+        tls: Option<GatewayTlsOptions>,
+    ) -> Result<GatewayStatus, String> {
+        // 先从锁中取出旧网关再停止，等待期间不占用锁
+        let previous = {
+            let mut server = self.server.lock().await;
+            match server.as_ref() {
+                Some(running)
+                    if running.port == port
+                        && running.https_port == tls.as_ref().map(|t| t.port) =>
+                {
+                    drop(server);
+                    // 端口不变时只补签新域名的证书
+                    if let Some(tls) = tls {
+                        self.prepare_tls(tls.certs, &tls.hosts).await?;
+                    }
+                    return Ok(self.status().await);
+                }
+                _ => server.take(),
+            }
+        };
+        if let Some(running) = previous {
+            Self::shutdown(running).await;
+        }
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], port));
+        let builder =
+            Server::try_bind(&addr).map_err(|e| format!("网关无法监听端口 {}: {}", port, e))?;
+
+        let (closed, closed_rx) = watch::channel(false);
+        let client = Client::builder().build_http::<Body>();
+        let routes = self.routes.clone();
+        let http_client = client.clone();
+        let http_closed = closed_rx.clone();
+        let make_service = make_service_fn(move |_| {
+            let routes = routes.clone();
+            let client = http_client.clone();
+            let closed = http_closed.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    proxy(req, routes.clone(), client.clone(), "http", closed.clone())
+                }))
+            }
+        });
+
+        let bound = builder.serve(make_service);
+        let bound_port = bound.local_addr().port();
+        let mut shutdown_rx = closed_rx.clone();
+        let handle = tokio::spawn(async move {
+            let graceful = bound.with_graceful_shutdown(async move {
+                let _ = shutdown_rx.wait_for(|closed| *closed).await;
+            });
+            if let Err(e) = graceful.await {
+                eprintln!("[DevGateway] 网关异常退出: {}", e);
+            }
+        });
+
+        let mut running = RunningGateway {
+            port: bound_port,
+            https_port: None,
+            closed,
+            handles: vec![handle],
+        };
+
+        if let Some(tls) = tls {
+            if let Err(e) = self.start_https(&mut running, tls, client, closed_rx).await {
+                Self::shutdown(running).await;
+                return Err(e);
+            }
+        } else {
+            *self.tls.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        }
+
+        // 并发启动时停止被替换的网关
+        let replaced = self.server.lock().await.replace(running);
+        if let Some(running) = replaced {
+            Self::shutdown(running).await;
+        }
+
+        Ok(self.status().await)
+    }
+
+    async fn start_https(
+        &self,
+        running: &mut RunningGateway,
+        tls: GatewayTlsOptions,
+        client: Client<HttpConnector>,
+        closed: watch::Receiver<bool>,
+    ) -> Result<(), String> {
+        let resolver = self.prepare_tls(tls.certs, &tls.hosts).await?;
+
+        let mut config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_cert_resolver(resolver);
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+
+        let listener = TcpListener::bind(("127.0.0.1", tls.port))
+            .await
+            .map_err(|e| format!("网关无法监听 HTTPS 端口 {}: {}", tls.port, e))?;
+        let https_port = listener
+            .local_addr()
+            .map_err(|e| format!("获取 HTTPS 端口失败: {}", e))?
+            .port();
+
+        let routes = self.routes.clone();
+        let mut shutdown_rx = closed.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    _ = shutdown_rx.wait_for(|closed| *closed) => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(e) => {
+                            eprintln!("[DevGateway] HTTPS 连接失败: {}", e);
+                            continue;
+                        }
+                    },
+                };
+
+                let acceptor = acceptor.clone();
+                let routes = routes.clone();
+                let client = client.clone();
+                let mut closed = closed.clone();
+                tokio::spawn(async move {
+                    let stream = match acceptor.accept(stream).await {
+                        Ok(stream) => stream,
+                        Err(e) => {
+                            eprintln!("[DevGateway] TLS 握手失败: {}", e);
+                            return;
+                        }
+                    };
+                    let proxy_closed = closed.clone();
+                    let service = service_fn(move |req| {
+                        proxy(
+                            req,
+                            routes.clone(),
+                            client.clone(),
+                            "https",
+                            proxy_closed.clone(),
+                        )
+                    });
+                    let connection = Http::new()
+                        .http1_only(true)
+                        .serve_connection(stream, service)
+                        .with_upgrades();
+                    // 网关停止时直接断开连接
+                    tokio::select! {
+                        _ = connection => {}
+                        _ = closed.wait_for(|closed| *closed) => {}
+                    }
+                });
+            }
+        });
+
+        running.https_port = Some(https_port);
+        running.handles.push(handle);
+        Ok(())
+    }
+
+    /// 签发（或复用）通配证书和各域名的证书，保存供后续新增路由时补签；
+    /// 签发证书较慢，在阻塞线程池中进行
+    async fn prepare_tls(
+        &self,
+        certs: CertManager,
+        hosts: &[String],
+    ) -> Result<Arc<SniCertResolver>, String> {
+        let mut hosts: Vec<String> = hosts.to_vec();
+        hosts.extend(self.routes().into_iter().map(|r| r.host));
+
+        let (certs, issued) = tokio::task::spawn_blocking(move || {
+            let issued = certs.ensure_for_hosts(&hosts)?;
+            Ok::<_, String>((certs, issued))
+        })
+        .await
+        .map_err(|e| format!("签发证书失败: {}", e))??;
+
+        let resolver = Arc::new(SniCertResolver::default());
+        for (host, leaf) in issued {
+            resolver.insert(host, &leaf)?;
+        }
+
+        *self.tls.lock().unwrap_or_else(|e| e.into_inner()) = Some(GatewayTls {
+            certs,
+            resolver: resolver.clone(),
+        });
+        Ok(resolver)
+    }
+
+    /// HTTPS 已开启时在后台为新域名补签证书，域名变化后无需重启网关；
+    /// 签好之前 *.localhost 域名使用通配证书
+    fn ensure_route_cert(&self, host: &str) {
+        let (certs, resolver) = {
+            let tls = self.tls.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(tls) = tls.as_ref() else {
+                return;
+            };
+            if tls.resolver.contains(host) {
+                return;
+            }
+            (tls.certs.clone(), tls.resolver.clone())
+        };
+
+        let host = host.to_string();
+        tokio::task::spawn_blocking(move || {
+            let result = certs
+                .ensure_leaf(std::slice::from_ref(&host))
+                .and_then(|leaf| resolver.insert(host.clone(), &leaf));
+            if let Err(e) = result {
+                eprintln!("[DevGateway] 为 {} 签发证书失败: {}", host, e);
+            }
+        });
+    }
+
+    pub async fn stop(&self) {
+        let running = self.server.lock().await.take();
+        if let Some(running) = running {
+            Self::shutdown(running).await;
+        }
+        *self.tls.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// 通知所有监听和连接停止；仍有连接未结束时最多等待 SHUTDOWN_TIMEOUT
+    async fn shutdown(running: RunningGateway) {
+        let _ = running.closed.send(true);
+        for mut handle in running.handles {
+            if tokio::time::timeout(SHUTDOWN_TIMEOUT, &mut handle)
+                .await
+                .is_err()
+            {
+                handle.abort();
+            }
+        }
+    }
+
+    pub async fn status(&self) -> GatewayStatus {
+        let (port, https_port) = self
+            .server
+            .lock()
+            .await
+            .as_ref()
+            .map(|s| (Some(s.port), s.https_port))
+            .unwrap_or_default();
+
+        GatewayStatus {
+            running: port.is_some(),
+            port,
+            https_port,
+            routes: self.routes(),
+        }
+    }
+
+    /// 各项目经网关访问的入口地址（开启 HTTPS 时使用 https），网关未运行时为空
+    pub async fn entry_urls(&self) -> HashMap<String, String> {
+        let status = self.status().await;
+        let (scheme, port, default_port) = match (status.https_port, status.port) {
+            (Some(https_port), _) => ("https", https_port, 443),
+            (None, Some(port)) => ("http", port, 80),
+            (None, None) => return HashMap::new(),
+        };
+        let authority = |host: &str| {
+            if port == default_port {
+                host.to_string()
+            } else {
+                format!("{}:{}", host, port)
+            }
+        };
+
+        status
+            .routes
+            .into_iter()
+            .map(|route| {
+                let url = format!("{}://{}/", scheme, authority(&route.host));
+                (route.project_id, url)
+            })
+            .collect()
+    }
+
+    pub fn routes(&self) -> Vec<GatewayRoute> {
+        self.routes
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// 项目启动后添加路由，路由表实时生效
+    pub fn add_route(&self, process: &ProcessInfo, project: &ProjectInfo) {
+        let Some(route) = route_for_project(process, project) else {
+            return;
+        };
+
+        self.ensure_route_cert(&route.host);
+
+        let mut routes = self.routes.write().unwrap_or_else(|e| e.into_inner());
+        routes.retain(|r| r.project_id != route.project_id);
+        routes.push(route);
+    }
+
+    /// 把项目的路由改为转发到指定端口（例如抓包代理），路由不存在时忽略
+    pub fn set_route_target(&self, project_id: &str, port: u16) {
+        for route in self
+            .routes
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter_mut()
+            .filter(|r| r.project_id == project_id)
+        {
+            route.target_port = port;
+        }
+    }
+
+    pub fn remove_route(&self, process_id: &str) {
+        self.routes
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|r| r.process_id != process_id);
+    }
+
+    pub fn clear_routes(&self) {
+        self.routes
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// 由项目的 domain（没有时用项目名）生成路由：
+/// `order` → Host `order.localhost`、路径前缀 `/order`；带点的 domain 直接作为 Host
+pub fn route_for_project(process: &ProcessInfo, project: &ProjectInfo) -> Option<GatewayRoute> {
+    if project.port == 0 {
+        return None;
+    }
+
+    let label = gateway_label(project);
+    let first_label = label.split('.').next().unwrap_or(&label);
+
+    Some(GatewayRoute {
+        process_id: process.process_id.clone(),
+        project_id: project.id.clone(),
+        project_name: project.name.clone(),
+        host: gateway_host(project),
+        path_prefix: format!("/{}", first_label),
+        target_port: project.port,
+        is_default: matches!(project.type_.as_str(), "base" | "main"),
+    })
+}
+
+/// 项目在网关上的 Host，也用于签发 HTTPS 证书
+pub fn gateway_host(project: &ProjectInfo) -> String {
+    let label = gateway_label(project);
+    if label.contains('.') {
+        label
+    } else {
+        format!("{}.localhost", label)
+    }
+}
+
+fn gateway_label(project: &ProjectInfo) -> String {
+    let domain = project
+        .domain
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+        .unwrap_or(&project.name);
+
+    domain
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// 先按 Host 匹配，再按最长路径前缀匹配，最后使用基座项目
+fn resolve_route<'a>(
+    routes: &'a [GatewayRoute],
+    host: &str,
+    path: &str,
+) -> Option<&'a GatewayRoute> {
+    let host = host.split(':').next().unwrap_or(host).to_lowercase();
+
+    routes
+        .iter()
+        .find(|r| r.host == host)
+        .or_else(|| {
+            routes
+                .iter()
+                .filter(|r| {
+                    path == r.path_prefix
+                        || path
+                            .strip_prefix(r.path_prefix.as_str())
+                            .is_some_and(|rest| rest.starts_with('/'))
+                })
+                .max_by_key(|r| r.path_prefix.len())
+        })
+        .or_else(|| routes.iter().find(|r| r.is_default))
+}
+
+pub(crate) fn is_upgrade_request<B>(req: &Request<B>) -> bool {
+    req.headers().contains_key(UPGRADE)
+        && req
+            .headers()
+            .get_all(CONNECTION)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .any(|v| {
+                v.split(',')
+                    .any(|t| t.trim().eq_ignore_ascii_case("upgrade"))
+            })
+}
+
+fn error_response(status: StatusCode, message: String) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("content-type", "text/plain; charset=utf-8")
+        .body(Body::from(message))
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
+async fn proxy(
+    req: Request<Body>,
+    routes: RouteTable,
+    client: Client<HttpConnector>,
+    scheme: &'static str,
+    closed: watch::Receiver<bool>,
+) -> Result<Response<Body>, Infallible> {
+    let original_host = req
+        .headers()
+        .get(HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    let route = {
+        let routes = routes.read().unwrap_or_else(|e| e.into_inner());
+        resolve_route(&routes, &original_host, req.uri().path()).cloned()
+    };
+    let Some(route) = route else {
+        return Ok(error_response(
+            StatusCode::BAD_GATEWAY,
+            format!(
+                "没有匹配 {}{} 的运行中项目",
+                original_host,
+                req.uri().path()
+            ),
+        ));
+    };
+
+    let response = forward(
+        req,
+        route.target_port,
+        &route.project_name,
+        &original_host,
+        scheme,
+        &client,
+        closed.clone(),
+    )
+    .await;
+    Ok(close_on_shutdown(response, closed))
+}
+
+/// 响应体改为逐块转发，网关停止时中止，SSE 等长连接不会拖住停止
+fn close_on_shutdown(
+    response: Response<Body>,
+    mut closed: watch::Receiver<bool>,
+) -> Response<Body> {
+    let (parts, mut body) = response.into_parts();
+    if body.is_end_stream() {
+        return Response::from_parts(parts, body);
+    }
+
+    let (mut sender, streamed) = Body::channel();
+    tokio::spawn(async move {
+        let forwarded = async {
+            while let Some(chunk) = body.data().await {
+                let Ok(chunk) = chunk else {
+                    return false;
+                };
+                if sender.send_data(chunk).await.is_err() {
+                    return true;
+                }
+            }
+            true
+        };
+        let finished = tokio::select! {
+            finished = forwarded => finished,
+            _ = closed.wait_for(|closed| *closed) => false,
+        };
+        if !finished {
+            sender.abort();
+        }
+    });
+    Response::from_parts(parts, streamed)
+}
+
+/// 把请求转发到 127.0.0.1:<target_port>，WebSocket 升级后双向转发数据，closed 置为 true 时断开
+pub(crate) async fn forward(
+    mut req: Request<Body>,
+    target_port: u16,
+    target_name: &str,
+    original_host: &str,
+    scheme: &'static str,
+    client: &Client<HttpConnector>,
+    mut closed: watch::Receiver<bool>,
+) -> Response<Body> {
+    let path_and_query = req
+        .uri()
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+    let upstream_uri: hyper::Uri =
+        match format!("http://127.0.0.1:{}{}", target_port, path_and_query).parse() {
+            Ok(uri) => uri,
+            Err(e) => {
+                return error_response(StatusCode::BAD_REQUEST, format!("无效的请求地址: {}", e))
+            }
+        };
+
+    let upgrade = is_upgrade_request(&req);
+    let mut upstream_req = Request::builder()
+        .method(req.method().clone())
+        .uri(upstream_uri)
+        .version(hyper::Version::HTTP_11)
+        .body(if upgrade {
+            Body::empty()
+        } else {
+            std::mem::take(req.body_mut())
+        })
+        .unwrap_or_else(|_| Request::new(Body::empty()));
+
+    let headers = upstream_req.headers_mut();
+    for (name, value) in req.headers() {
+        // WebSocket 握手需要保留 Connection/Upgrade
+        if !upgrade && HOP_BY_HOP_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        headers.append(name.clone(), value.clone());
+    }
+    // 开发服务器通常只接受 localhost 的 Host，原始 Host 放到 X-Forwarded-Host
+    if let Ok(host) = HeaderValue::from_str(&format!("127.0.0.1:{}", target_port)) {
+        headers.insert(HOST, host);
+    }
+    if let Ok(forwarded) = HeaderValue::from_str(original_host) {
+        headers.insert(HeaderName::from_static("x-forwarded-host"), forwarded);
+    }
+    headers.insert(
+        HeaderName::from_static("x-forwarded-proto"),
+        HeaderValue::from_static(scheme),
+    );
+
+    let mut response = match client.request(upstream_req).await {
+        Ok(response) => response,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_GATEWAY,
+                format!(
+                    "无法连接项目 {} (127.0.0.1:{}): {}",
+                    target_name, target_port, e
+                ),
+            )
+        }
+    };
+
+    if upgrade && response.status() == StatusCode::SWITCHING_PROTOCOLS {
+        // 两端都完成协议升级后双向转发数据（HMR 的 WebSocket 连接）
+        let upstream_upgrade = hyper::upgrade::on(&mut response);
+        let client_upgrade = hyper::upgrade::on(req);
+        tokio::spawn(async move {
+            match (client_upgrade.await, upstream_upgrade.await) {
+                (Ok(mut client_io), Ok(mut upstream_io)) => {
+                    tokio::select! {
+                        _ = tokio::io::copy_bidirectional(&mut client_io, &mut upstream_io) => {}
+                        _ = closed.wait_for(|closed| *closed) => {}
+                    }
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    eprintln!("[DevGateway] WebSocket 升级失败: {}", e);
+                }
+            }
+        });
+
+        let mut switching = Response::new(Body::empty());
+        *switching.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+        *switching.headers_mut() = response.headers().clone();
+        return switching;
+    }
+
+    let headers = response.headers_mut();
+    for name in HOP_BY_HOP_HEADERS {
+        headers.remove(name);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProcessStatus;
+    use crate::utils::test_dir::TempDir;
+    use std::path::PathBuf;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    /// 测试用上游：返回 "<name> <path>"，并支持 WebSocket 升级后回显
+    async fn spawn_upstream(name: &'static str) -> u16 {
+        let make_service = make_service_fn(move |_| async move {
+            Ok::<_, Infallible>(service_fn(move |req: Request<Body>| async move {
+                if is_upgrade_request(&req) {
+                    tokio::spawn(async move {
+                        if let Ok(mut io) = hyper::upgrade::on(req).await {
+                            let mut buf = [0u8; 64];
+                            while let Ok(n) = io.read(&mut buf).await {
+                                if n == 0 || io.write_all(&buf[..n]).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    });
+                    return Ok::<_, Infallible>(
+                        Response::builder()
+                            .status(StatusCode::SWITCHING_PROTOCOLS)
+                            .header(CONNECTION, "Upgrade")
+                            .header(UPGRADE, "websocket")
+                            .body(Body::empty())
+                            .unwrap(),
+                    );
+                }
+
+                Ok(Response::new(Body::from(format!(
+                    "{} {}",
+                    name,
+                    req.uri().path()
+                ))))
+            }))
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let port = server.local_addr().port();
+        tokio::spawn(server);
+        port
+    }
+
+    fn running(project: &ProjectInfo) -> ProcessInfo {
+        ProcessInfo {
+            process_id: format!("proc-{}", project.id),
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            status: ProcessStatus::Running,
+            started_at: chrono::Utc::now(),
+            pid: None,
+            health: None,
+        }
+    }
+
+    fn project(name: &str, domain: Option<&str>, port: u16) -> ProjectInfo {
+        let mut project =
+            ProjectInfo::new(PathBuf::from(format!("/code/{}", name)), name.to_string());
+        project.id = name.to_string();
+        project.domain = domain.map(|d| d.to_string());
+        project.port = port;
+        project
+    }
+
+    async fn get(port: u16, host: &str, path: &str) -> String {
+        let req = Request::builder()
+            .uri(format!("http://127.0.0.1:{}{}", port, path))
+            .header(HOST, host)
+            .body(Body::empty())
+            .unwrap();
+        let response = Client::new().request(req).await.unwrap();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    #[test]
+    fn resolve_route_prefers_host_then_longest_prefix_then_default() {
+        let mut base = project("base", None, 8000);
+        base.type_ = "base".to_string();
+        let routes: Vec<GatewayRoute> = [base, project("order", Some("order"), 8003)]
+            .iter()
+            .filter_map(|p| route_for_project(&running(p), p))
+            .collect();
+
+        assert_eq!(
+            resolve_route(&routes, "order.localhost:7000", "/")
+                .unwrap()
+                .target_port,
+            8003
+        );
+        assert_eq!(
+            resolve_route(&routes, "localhost:7000", "/order/list")
+                .unwrap()
+                .target_port,
+            8003
+        );
+        assert_eq!(
+            resolve_route(&routes, "localhost:7000", "/orders")
+                .unwrap()
+                .target_port,
+            8000
+        );
+    }
+
+    #[tokio::test]
+    async fn gateway_routes_live_and_proxies_websocket_upgrades() {
+        let order_port = spawn_upstream("order").await;
+        let user_port = spawn_upstream("user").await;
+
+        let gateway = DevGateway::new();
+        let status = gateway.start(0, None).await.unwrap();
+        let gateway_port = status.port.unwrap();
+
+        let order = project("order", Some("order"), order_port);
+        let user = project("user", Some("user"), user_port);
+        gateway.add_route(&running(&order), &order);
+        gateway.add_route(&running(&user), &user);
+
+        assert_eq!(
+            get(gateway_port, "order.localhost", "/index.html").await,
+            "order /index.html"
+        );
+        assert_eq!(
+            get(gateway_port, "localhost", "/user/profile").await,
+            "user /user/profile"
+        );
+
+        // WebSocket 升级后数据双向转发
+        let mut stream = TcpStream::connect(("127.0.0.1", gateway_port))
+            .await
+            .unwrap();
+        stream
+            .write_all(b"GET /ws HTTP/1.1\r\nHost: order.localhost\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\r\n")
+            .await
+            .unwrap();
+        let mut head = Vec::new();
+        let mut byte = [0u8; 1];
+        while !head.ends_with(b"\r\n\r\n") {
+            stream.read_exact(&mut byte).await.unwrap();
+            head.push(byte[0]);
+        }
+        assert!(String::from_utf8_lossy(&head).starts_with("HTTP/1.1 101"));
+        stream.write_all(b"ping").await.unwrap();
+        let mut echo = [0u8; 4];
+        stream.read_exact(&mut echo).await.unwrap();
+        assert_eq!(&echo, b"ping");
+
+        let urls = gateway.entry_urls().await;
+        assert_eq!(
+            urls.get("order").map(String::as_str),
+            Some(format!("http://order.localhost:{}/", gateway_port).as_str())
+        );
+
+        // 停止项目后路由立即移除
+        gateway.remove_route(&running(&order).process_id);
+        assert!(get(gateway_port, "order.localhost", "/")
+            .await
+            .contains("没有匹配"));
+
+        gateway.stop().await;
+        assert!(!gateway.status().await.running);
+        assert!(gateway.entry_urls().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stopping_does_not_wait_for_open_streams() {
+        // 上游返回一个永不结束的流式响应，类似 SSE
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|_req: Request<Body>| async move {
+                let (mut sender, body) = Body::channel();
+                tokio::spawn(async move {
+                    let _ = sender.send_data("data: ping\n\n".into()).await;
+                    tokio::time::sleep(Duration::from_secs(3600)).await;
+                    drop(sender);
+                });
+                Ok::<_, Infallible>(Response::new(body))
+            }))
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let events_port = server.local_addr().port();
+        tokio::spawn(server);
+        let order_port = spawn_upstream("order").await;
+
+        let gateway = DevGateway::new();
+        let gateway_port = gateway.start(0, None).await.unwrap().port.unwrap();
+        let events = project("events", Some("events"), events_port);
+        let order = project("order", Some("order"), order_port);
+        gateway.add_route(&running(&events), &events);
+        gateway.add_route(&running(&order), &order);
+
+        let req = Request::builder()
+            .uri(format!("http://127.0.0.1:{}/stream", gateway_port))
+            .header(HOST, "events.localhost")
+            .body(Body::empty())
+            .unwrap();
+        let mut body = Client::new().request(req).await.unwrap().into_body();
+        assert_eq!(&body.data().await.unwrap().unwrap()[..], b"data: ping\n\n");
+
+        let mut ws = TcpStream::connect(("127.0.0.1", gateway_port))
+            .await
+            .unwrap();
+        ws.write_all(b"GET /ws HTTP/1.1\r\nHost: order.localhost\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\r\n")
+            .await
+            .unwrap();
+        let mut head = Vec::new();
+        let mut byte = [0u8; 1];
+        while !head.ends_with(b"\r\n\r\n") {
+            ws.read_exact(&mut byte).await.unwrap();
+            head.push(byte[0]);
+        }
+        assert!(String::from_utf8_lossy(&head).starts_with("HTTP/1.1 101"));
+
+        // 流式响应和 WebSocket 都未结束时停止网关，立即返回并断开连接
+        tokio::time::timeout(Duration::from_secs(1), gateway.stop())
+            .await
+            .expect("停止网关不应等待流式响应和 WebSocket 结束");
+        assert!(!gateway.status().await.running);
+        assert!(tokio::time::timeout(Duration::from_secs(1), body.data())
+            .await
+            .unwrap()
+            .is_none_or(|chunk| chunk.is_err()));
+        let mut rest = Vec::new();
+        let _ = tokio::time::timeout(Duration::from_secs(1), ws.read_to_end(&mut rest))
+            .await
+            .expect("WebSocket 连接应被断开");
+    }
+
+    #[tokio::test]
+    async fn gateway_serves_https_with_certificates_from_local_ca() {
+        let order_port = spawn_upstream("order").await;
+        let cert_dir = TempDir::new("gateway-certs");
+
+        let gateway = DevGateway::new();
+        let status = gateway
+            .start(
+                0,
+                Some(GatewayTlsOptions {
+                    port: 0,
+                    certs: CertManager::with_dir(cert_dir.to_path_buf()).unwrap(),
+                    hosts: Vec::new(),
+                }),
+            )
+            .await
+            .unwrap();
+        let https_port = status.https_port.unwrap();
+
+        // 启动后新增的域名在后台自动补签证书
+        let order = project("order", Some("order"), order_port);
+        gateway.add_route(&running(&order), &order);
+        let issued = || {
+            let tls = gateway.tls.lock().unwrap();
+            tls.as_ref().unwrap().resolver.contains("order.localhost")
+        };
+        for _ in 0..100 {
+            if issued() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(issued());
+        assert!(cert_dir.join("order.localhost.pem").exists());
+        assert_eq!(
+            gateway.entry_urls().await.get("order").map(String::as_str),
+            Some(format!("https://order.localhost:{}/", https_port).as_str())
+        );
+
+        let mut roots = rustls::RootCertStore::empty();
+        let ca_pem = std::fs::read_to_string(cert_dir.join("ca.pem")).unwrap();
+        for der in rustls_pemfile::certs(&mut ca_pem.as_bytes()).unwrap() {
+            roots.add(&rustls::Certificate(der)).unwrap();
+        }
+        let config = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+
+        let tcp = TcpStream::connect(("127.0.0.1", https_port)).await.unwrap();
+        let server_name = rustls::ServerName::try_from("order.localhost").unwrap();
+        let mut tls = connector.connect(server_name, tcp).await.unwrap();
+        tls.write_all(
+            b"GET /app.js HTTP/1.1\r\nHost: order.localhost\r\nConnection: close\r\n\r\n",
+        )
+        .await
+        .unwrap();
+        let mut response = Vec::new();
+        tls.read_to_end(&mut response).await.unwrap();
+        let response = String::from_utf8_lossy(&response);
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("order /app.js"));
+
+        gateway.stop().await;
+    }
+}
@@ -20,6 +20,11 @@ impl GitManager {
         Path::new(path).join(".git").exists()
     }
 
+    /// 在新建的项目目录中初始化仓库
+    pub fn init_repo(path: &str) -> Result<(), String> {
+        Self::run_git_checked(&["init"], path).map(|_| ())
+    }
+
     fn run_git(args: &[&str], cwd: &str) -> Result<(i32, String, String), String> {
         let mut cmd = Command::new("git");
         cmd.args(args)
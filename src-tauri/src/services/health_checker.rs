@@ -0,0 +1,346 @@
+use crate::models::{HealthCheckConfig, HealthState, HealthStatus, HealthTransition, ProcessInfo};
+use chrono::Utc;
+use hyper::{Body, Client, Uri};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// 健康检查最多读取的响应体大小
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+pub struct HealthChecker;
+
+impl HealthChecker {
+    /// 拼接健康检查地址，默认 http://127.0.0.1:<port>
+    pub fn probe_url(config: &HealthCheckConfig, port: u16) -> String {
+        let base = config
+            .base_url
+            .as_deref()
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| format!("http://127.0.0.1:{}", port));
+
+        if config.path.starts_with('/') {
+            format!("{}{}", base, config.path)
+        } else {
+            format!("{}/{}", base, config.path)
+        }
+    }
+
+    /// 执行一次健康检查
+    pub async fn probe(config: &HealthCheckConfig, port: u16) -> HealthStatus {
+        let url = Self::probe_url(config, port);
+
+        let body_regex = match config.body_pattern.as_deref().map(Regex::new).transpose() {
+            Ok(regex) => regex,
+            Err(e) => {
+                return Self::status(
+                    HealthState::Unhealthy,
+                    None,
+                    None,
+                    format!("响应体正则无效: {}", e),
+                )
+            }
+        };
+
+        let uri: Uri = match url.parse() {
+            Ok(uri) => uri,
+            Err(e) => {
+                return Self::status(
+                    HealthState::Unhealthy,
+                    None,
+                    None,
+                    format!("健康检查地址无效: {}", e),
+                )
+            }
+        };
+        if uri.scheme_str() != Some("http") {
+            return Self::status(
+                HealthState::Unhealthy,
+                None,
+                None,
+                "健康检查仅支持 http 地址".to_string(),
+            );
+        }
+
+        let started = Instant::now();
+        let timeout = Duration::from_millis(config.timeout_ms.max(1));
+        let response = tokio::time::timeout(timeout, async {
+            let response = Client::new().get(uri).await?;
+            let status = response.status().as_u16();
+            let body = read_body(response.into_body()).await?;
+            Ok::<_, hyper::Error>((status, body))
+        })
+        .await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        match response {
+            Err(_) => Self::status(
+                HealthState::Unhealthy,
+                None,
+                None,
+                format!("请求超时（{}ms）", config.timeout_ms),
+            ),
+            Ok(Err(e)) => Self::status(
+                HealthState::Unhealthy,
+                None,
+                None,
+                format!("请求失败: {}", e),
+            ),
+            Ok(Ok((status_code, body))) => {
+                let (state, message) =
+                    Self::evaluate(config, body_regex.as_ref(), status_code, &body, latency_ms);
+                HealthStatus {
+                    state,
+                    latency_ms: Some(latency_ms),
+                    status_code: Some(status_code),
+                    message,
+                    checked_at: Utc::now(),
+                }
+            }
+        }
+    }
+
+    /// 状态码不符合预期为不健康；响应体不匹配或响应过慢为降级
+    fn evaluate(
+        config: &HealthCheckConfig,
+        body_regex: Option<&Regex>,
+        status_code: u16,
+        body: &str,
+        latency_ms: u64,
+    ) -> (HealthState, Option<String>) {
+        let status_ok = match config.expected_status {
+            Some(expected) => status_code == expected,
+            None => (200..300).contains(&status_code),
+        };
+        if !status_ok {
+            return (
+                HealthState::Unhealthy,
+                Some(format!("状态码 {} 不符合预期", status_code)),
+            );
+        }
+
+        if let Some(regex) = body_regex {
+            if !regex.is_match(body) {
+                return (
+                    HealthState::Degraded,
+                    Some(format!("响应体不匹配 {}", regex.as_str())),
+                );
+            }
+        }
+
+        if let Some(threshold) = config.slow_threshold_ms {
+            if latency_ms > threshold {
+                return (
+                    HealthState::Degraded,
+                    Some(format!("响应耗时 {}ms 超过 {}ms", latency_ms, threshold)),
+                );
+            }
+        }
+
+        (HealthState::Healthy, None)
+    }
+
+    fn status(
+        state: HealthState,
+        latency_ms: Option<u64>,
+        status_code: Option<u16>,
+        message: String,
+    ) -> HealthStatus {
+        HealthStatus {
+            state,
+            latency_ms,
+            status_code,
+            message: Some(message),
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+/// 读取响应体，超过上限的部分丢弃
+async fn read_body(mut body: Body) -> Result<String, hyper::Error> {
+    use hyper::body::HttpBody;
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        let remaining = MAX_BODY_BYTES.saturating_sub(bytes.len());
+        bytes.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+        if bytes.len() >= MAX_BODY_BYTES {
+            break;
+        }
+    }
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// 为运行中的进程定时执行健康检查
+pub struct HealthMonitor {
+    tasks: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+    window: tauri::Window,
+}
+
+impl HealthMonitor {
+    pub fn new(window: tauri::Window) -> Self {
+        Self {
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+            window,
+        }
+    }
+
+    /// 开始检查，结果写入 running_processes 中对应的 ProcessInfo，状态变化时发送事件
+    pub async fn start(
+        &self,
+        process: &ProcessInfo,
+        config: HealthCheckConfig,
+        port: u16,
+        running_processes: Arc<Mutex<HashMap<String, ProcessInfo>>>,
+    ) {
+        let process_id = process.process_id.clone();
+        let project_id = process.project_id.clone();
+        let project_name = process.project_name.clone();
+        let window = self.window.clone();
+        let interval = Duration::from_secs(config.interval_secs.max(1));
+
+        let task_process_id = process_id.clone();
+        let handle = tokio::spawn(async move {
+            let mut previous: Option<HealthState> = None;
+
+            loop {
+                // 先等待一个周期，给开发服务器留出启动时间
+                tokio::time::sleep(interval).await;
+
+                let status = HealthChecker::probe(&config, port).await;
+                let current = status.state;
+
+                {
+                    let mut processes = running_processes.lock().await;
+                    match processes.get_mut(&task_process_id) {
+                        Some(info) => info.health = Some(status.clone()),
+                        None => break, // 进程已停止
+                    }
+                }
+
+                if previous != Some(current) {
+                    let transition = HealthTransition {
+                        process_id: task_process_id.clone(),
+                        project_id: project_id.clone(),
+                        project_name: project_name.clone(),
+                        previous,
+                        current: status,
+                    };
+                    let _ = window.emit("project_health_changed", &transition);
+                    previous = Some(current);
+                }
+            }
+        });
+
+        if let Some(old) = self.tasks.lock().await.insert(process_id, handle) {
+            old.abort();
+        }
+    }
+
+    pub async fn stop(&self, process_id: &str) {
+        if let Some(handle) = self.tasks.lock().await.remove(process_id) {
+            handle.abort();
+        }
+    }
+
+    pub async fn stop_all(&self) {
+        for (_, handle) in self.tasks.lock().await.drain() {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Request, Response, Server};
+    use std::convert::Infallible;
+
+    /// 启动本地测试服务器，返回端口
+    async fn spawn_test_server() -> u16 {
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|req: Request<Body>| async move {
+                let response = match req.uri().path() {
+                    "/ok" => Response::new(Body::from(
+                        r#"<div id="root"></div><script src="/main.3f2a.js"></script>"#,
+                    )),
+                    "/blank" => Response::new(Body::from("<div id=\"root\"></div>")),
+                    _ => Response::builder()
+                        .status(500)
+                        .body(Body::from("Internal Server Error"))
+                        .unwrap(),
+                };
+                Ok::<_, Infallible>(response)
+            }))
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let port = server.local_addr().port();
+        tokio::spawn(server);
+        port
+    }
+
+    fn config(path: &str) -> HealthCheckConfig {
+        HealthCheckConfig {
+            path: path.to_string(),
+            body_pattern: Some(r"main\.[0-9a-f]+\.js".to_string()),
+            ..HealthCheckConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn probe_reports_healthy_degraded_and_unhealthy() {
+        let port = spawn_test_server().await;
+
+        let healthy = HealthChecker::probe(&config("/ok"), port).await;
+        assert_eq!(healthy.state, HealthState::Healthy);
+        assert_eq!(healthy.status_code, Some(200));
+        assert!(healthy.latency_ms.is_some());
+
+        let blank = HealthChecker::probe(&config("/blank"), port).await;
+        assert_eq!(blank.state, HealthState::Degraded);
+
+        let error = HealthChecker::probe(&config("/error"), port).await;
+        assert_eq!(error.state, HealthState::Unhealthy);
+        assert_eq!(error.status_code, Some(500));
+
+        let mut expect_500 = config("/error");
+        expect_500.expected_status = Some(500);
+        expect_500.body_pattern = None;
+        let expected = HealthChecker::probe(&expect_500, port).await;
+        assert_eq!(expected.state, HealthState::Healthy);
+    }
+
+    #[tokio::test]
+    async fn probe_reports_unhealthy_when_nothing_listens() {
+        let port = {
+            let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+            listener.local_addr().unwrap().port()
+        };
+
+        let status = HealthChecker::probe(&config("/"), port).await;
+        assert_eq!(status.state, HealthState::Unhealthy);
+        assert!(status.status_code.is_none());
+    }
+
+    #[test]
+    fn probe_url_uses_base_url_override() {
+        let mut config = config("health");
+        assert_eq!(
+            HealthChecker::probe_url(&config, 8003),
+            "http://127.0.0.1:8003/health"
+        );
+
+        config.base_url = Some("http://order.localhost:7000/".to_string());
+        assert_eq!(
+            HealthChecker::probe_url(&config, 8003),
+            "http://order.localhost:7000/health"
+        );
+    }
+}
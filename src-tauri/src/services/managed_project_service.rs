@@ -442,6 +442,7 @@ impl ManagedProjectService {
             root_path.join(".zebras").join("cache"),
             root_path.join(".zebras").join("logs"),
             root_path.join(".zebras").join("links"),
+            root_path.join(".zebras").join("mocks"),
         ] {
             fs::create_dir_all(&dir)
                 .map_err(|e| format!("创建运行目录失败 ({}): {}", dir.display(), e))?;
@@ -710,20 +711,9 @@ fn ensure_dependency_declared(repo_path: &Path, package_name: &str) -> Result<()
 mod tests {
     use super::*;
     use crate::models::{CreateProjectInstanceInput, ManagedFrontendLinkInput, ManagedRepoInput};
+    use crate::utils::test_dir::TempDir;
     use std::fs;
 
-    fn temp_path(name: &str) -> PathBuf {
-        let dir = std::env::temp_dir().join(format!(
-            "zebras-launcher-managed-project-{}-{}",
-            name,
-            uuid::Uuid::new_v4()
-        ));
-        if dir.exists() {
-            let _ = fs::remove_dir_all(&dir);
-        }
-        dir
-    }
-
     fn command_exists(program: &str) -> bool {
         let resolved = resolve_program(program);
         Command::new(resolved)
@@ -767,7 +757,8 @@ mod tests {
 
     #[test]
     fn validate_rejects_non_empty_root_and_invalid_links() {
-        let root = temp_path("validation-root");
+        let temp = TempDir::new("managed-project");
+        let root = temp.join("validation-root");
         fs::create_dir_all(&root).unwrap();
         fs::write(root.join("README.md"), "occupied").unwrap();
 
@@ -809,7 +800,8 @@ mod tests {
             return;
         }
 
-        let source_root = temp_path("source");
+        let temp = TempDir::new("managed-project");
+        let source_root = temp.join("source");
         let frontend_repo = source_root.join("admin-web");
         let backend_repo = source_root.join("user-service");
         init_git_repo(
@@ -833,7 +825,7 @@ mod tests {
         );
         init_git_repo(&backend_repo, &[("pom.xml", "<project></project>")]);
 
-        let target_root = temp_path("target");
+        let target_root = temp.join("target");
         let input = CreateProjectInstanceInput {
             project_name: "demo".to_string(),
             root_path: target_root.to_string_lossy().to_string(),
@@ -883,7 +875,8 @@ mod tests {
             return;
         }
 
-        let target_root = temp_path("degraded-target");
+        let temp = TempDir::new("managed-project");
+        let target_root = temp.join("degraded-target");
         let input = CreateProjectInstanceInput {
             project_name: "demo".to_string(),
             root_path: target_root.to_string_lossy().to_string(),
@@ -915,7 +908,8 @@ mod tests {
             return;
         }
 
-        let source_root = temp_path("source-link");
+        let temp = TempDir::new("managed-project");
+        let source_root = temp.join("source-link");
         let provider_repo = source_root.join("ui-kit");
         let consumer_repo = source_root.join("admin-web");
         init_git_repo(
@@ -936,7 +930,7 @@ mod tests {
             )],
         );
 
-        let target_root = temp_path("target-link");
+        let target_root = temp.join("target-link");
         let input = CreateProjectInstanceInput {
             project_name: "demo-link".to_string(),
             root_path: target_root.to_string_lossy().to_string(),
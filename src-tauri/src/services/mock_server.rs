@@ -0,0 +1,957 @@
+use crate::models::{
+    MockAction, MockRule, MockRuleSet, MockServerStatus, Workspace, WorkspaceSourceType,
+};
+use crate::services::dev_gateway::HOP_BY_HOP_HEADERS;
+use crate::services::WorkspaceService;
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderName, HeaderValue, CONTENT_TYPE, HOST};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Client, Request, Response, Server, StatusCode};
+use hyper_rustls::HttpsConnector;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+
+/// 检查规则文件是否变化的间隔
+const RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Mock 规则的存放位置
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockRuleSource {
+    /// 普通工作区：保存在工作区配置文件中
+    WorkspaceFile(PathBuf),
+    /// 托管实例：<root>/.zebras/mocks/*.json，每个文件一组规则；
+    /// 静态文件相对该目录，放在子目录（如 data/）中
+    Directory(PathBuf),
+}
+
+impl MockRuleSource {
+    pub fn for_workspace(workspace: &Workspace) -> Result<Self, String> {
+        match workspace.source_type {
+            WorkspaceSourceType::ManagedProject => Ok(Self::Directory(
+                workspace.root_path.join(".zebras").join("mocks"),
+            )),
+            WorkspaceSourceType::FolderScan => Ok(Self::WorkspaceFile(
+                WorkspaceService::get_config_path(&workspace.id)?,
+            )),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::WorkspaceFile(path) | Self::Directory(path) => path.to_string_lossy().to_string(),
+        }
+    }
+
+    /// 规则文件的修改时间和大小，变化时重新加载
+    fn signature(&self) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+        let files = match self {
+            Self::WorkspaceFile(path) => vec![path.clone()],
+            Self::Directory(dir) => rule_files(dir),
+        };
+
+        files
+            .into_iter()
+            .map(|path| {
+                let meta = fs::metadata(&path).ok();
+                let modified = meta.as_ref().and_then(|m| m.modified().ok());
+                let len = meta.map(|m| m.len()).unwrap_or(0);
+                (path, modified, len)
+            })
+            .collect()
+    }
+}
+
+fn rule_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// 加载后的规则组，base_dir 用于解析静态文件的相对路径
+#[derive(Debug, Clone)]
+struct LoadedRuleSet {
+    set: MockRuleSet,
+    base_dir: PathBuf,
+}
+
+pub struct MockRuleStore;
+
+impl MockRuleStore {
+    /// 读取工作区的 Mock 规则
+    pub fn load(workspace: &Workspace) -> Result<Vec<MockRuleSet>, String> {
+        let source = MockRuleSource::for_workspace(workspace)?;
+        Ok(Self::load_source(&source)?
+            .into_iter()
+            .map(|loaded| loaded.set)
+            .collect())
+    }
+
+    /// 校验并保存规则：托管实例写入 .zebras/mocks，普通工作区写入工作区配置
+    pub fn save(workspace: &mut Workspace, rule_sets: Vec<MockRuleSet>) -> Result<(), String> {
+        Self::validate(&rule_sets)?;
+
+        match MockRuleSource::for_workspace(workspace)? {
+            MockRuleSource::Directory(dir) => {
+                fs::create_dir_all(&dir).map_err(|e| format!("创建 Mock 目录失败: {}", e))?;
+
+                let mut written = HashSet::new();
+                for set in &rule_sets {
+                    let path = dir.join(format!("{}.json", sanitize_file_stem(&set.id)));
+                    let json = serde_json::to_string_pretty(set)
+                        .map_err(|e| format!("序列化 Mock 规则失败: {}", e))?;
+                    fs::write(&path, json).map_err(|e| format!("写入 Mock 规则失败: {}", e))?;
+                    written.insert(path);
+                }
+                // 删除已移除的规则组
+                for path in rule_files(&dir) {
+                    if !written.contains(&path) {
+                        let _ = fs::remove_file(&path);
+                    }
+                }
+                workspace.mock_rule_sets.clear();
+            }
+            MockRuleSource::WorkspaceFile(_) => {
+                workspace.mock_rule_sets = rule_sets;
+            }
+        }
+
+        WorkspaceService::save_workspace(workspace)
+    }
+
+    fn load_source(source: &MockRuleSource) -> Result<Vec<LoadedRuleSet>, String> {
+        let loaded = match source {
+            MockRuleSource::WorkspaceFile(path) => {
+                let workspace = WorkspaceService::load_workspace(path)?;
+                workspace
+                    .mock_rule_sets
+                    .iter()
+                    .map(|set| {
+                        // 项目级规则的静态文件相对项目目录，工作区级相对工作区根目录
+                        let base_dir = set
+                            .project_id
+                            .as_deref()
+                            .and_then(|id| workspace.projects.iter().find(|p| p.id == id))
+                            .map(|p| p.path.clone())
+                            .unwrap_or_else(|| workspace.root_path.clone());
+                        LoadedRuleSet {
+                            set: set.clone(),
+                            base_dir,
+                        }
+                    })
+                    .collect()
+            }
+            MockRuleSource::Directory(dir) => {
+                let mut loaded = Vec::new();
+                for path in rule_files(dir) {
+                    let content = fs::read_to_string(&path)
+                        .map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+                    let set: MockRuleSet = serde_json::from_str(&content)
+                        .map_err(|e| format!("解析 {} 失败: {}", path.display(), e))?;
+                    loaded.push(LoadedRuleSet {
+                        set,
+                        base_dir: dir.clone(),
+                    });
+                }
+                loaded
+            }
+        };
+
+        let sets: Vec<MockRuleSet> = loaded
+            .iter()
+            .map(|l: &LoadedRuleSet| l.set.clone())
+            .collect();
+        Self::validate(&sets)?;
+        Ok(loaded)
+    }
+
+    pub fn validate(rule_sets: &[MockRuleSet]) -> Result<(), String> {
+        let mut ids = HashSet::new();
+        let mut ports = HashSet::new();
+
+        for set in rule_sets {
+            if set.id.trim().is_empty() {
+                return Err(format!("规则组 {} 缺少 id", set.name));
+            }
+            if !ids.insert(set.id.as_str()) {
+                return Err(format!("规则组 id 重复: {}", set.id));
+            }
+            if set.port != 0 && !ports.insert(set.port) {
+                return Err(format!("多个规则组使用了同一端口 {}", set.port));
+            }
+            if let Some(upstream) = set.upstream.as_deref() {
+                validate_upstream(upstream)?;
+            }
+
+            for rule in &set.rules {
+                if !rule.path.starts_with('/') {
+                    return Err(format!("规则路径必须以 / 开头: {}", rule.path));
+                }
+                match &rule.action {
+                    MockAction::Static { status, file, .. } => {
+                        if StatusCode::from_u16(*status).is_err() {
+                            return Err(format!("规则 {} 的状态码无效: {}", rule.path, status));
+                        }
+                        if let Some(file) = file.as_deref() {
+                            validate_static_file(&rule.path, file)?;
+                        }
+                    }
+                    MockAction::PassThrough { upstream } => match upstream.as_deref() {
+                        Some(upstream) => validate_upstream(upstream)?,
+                        None if set.upstream.is_none() => {
+                            return Err(format!(
+                                "规则 {} 为透传，但规则组 {} 未配置上游地址",
+                                rule.path, set.name
+                            ))
+                        }
+                        None => {}
+                    },
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_upstream(upstream: &str) -> Result<(), String> {
+    let uri: hyper::Uri = upstream
+        .parse()
+        .map_err(|e| format!("上游地址无效 {}: {}", upstream, e))?;
+    match uri.scheme_str() {
+        Some("http") | Some("https") if uri.host().is_some() => Ok(()),
+        _ => Err(format!(
+            "上游地址需以 http:// 或 https:// 开头: {}",
+            upstream
+        )),
+    }
+}
+
+/// 静态文件只能是规则目录内的相对路径，不能是绝对路径或包含 ..
+fn validate_static_file(rule_path: &str, file: &str) -> Result<(), String> {
+    let inside = Path::new(file)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if file.trim().is_empty() || !inside {
+        return Err(format!(
+            "规则 {} 的静态文件必须是相对路径且不能包含 ..: {}",
+            rule_path, file
+        ));
+    }
+    Ok(())
+}
+
+fn sanitize_file_stem(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// 路径模式匹配：`:name` 和 `*` 匹配一段，`**` 匹配剩余所有段
+pub fn match_path(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    for (index, segment) in pattern.iter().enumerate() {
+        if *segment == "**" {
+            return true;
+        }
+        let Some(actual) = path.get(index) else {
+            return false;
+        };
+        if *segment != "*" && !segment.starts_with(':') && segment != actual {
+            return false;
+        }
+    }
+
+    pattern.len() == path.len()
+}
+
+fn rule_matches(rule: &MockRule, method: &str, path: &str) -> bool {
+    rule.enabled
+        && rule
+            .method
+            .as_deref()
+            .is_none_or(|m| m == "*" || m.eq_ignore_ascii_case(method))
+        && match_path(&rule.path, path)
+}
+
+type RuleTable = Arc<RwLock<Vec<LoadedRuleSet>>>;
+type UpstreamClient = Client<HttpsConnector<HttpConnector>>;
+
+struct MockListener {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+/// 各规则组的监听和热加载状态，由热加载任务共享
+struct MockInner {
+    table: RuleTable,
+    listeners: Mutex<HashMap<String, MockListener>>,
+    error: RwLock<Option<String>>,
+    client: UpstreamClient,
+}
+
+struct ActiveMocks {
+    workspace_id: String,
+    source: MockRuleSource,
+    watcher: JoinHandle<()>,
+}
+
+/// Mock 服务：每个规则组在自己的端口上提供服务，规则文件修改后自动重新加载
+pub struct MockServer {
+    inner: Arc<MockInner>,
+    active: Mutex<Option<ActiveMocks>>,
+}
+
+impl Default for MockServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockServer {
+    pub fn new() -> Self {
+        let connector = hyper_rustls::HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .https_or_http()
+            .enable_http1()
+            .build();
+
+        Self {
+            inner: Arc::new(MockInner {
+                table: Arc::new(RwLock::new(Vec::new())),
+                listeners: Mutex::new(HashMap::new()),
+                error: RwLock::new(None),
+                client: Client::builder().build(connector),
+            }),
+            active: Mutex::new(None),
+        }
+    }
+
+    /// 为工作区启动 Mock 服务，已为其他工作区运行时先停止
+    pub async fn start(&self, workspace: &Workspace) -> Result<MockServerStatus, String> {
+        let source = MockRuleSource::for_workspace(workspace)?;
+        self.start_source(workspace.id.clone(), source).await
+    }
+
+    pub async fn start_source(
+        &self,
+        workspace_id: String,
+        source: MockRuleSource,
+    ) -> Result<MockServerStatus, String> {
+        self.stop().await;
+
+        if let Err(e) = self.inner.reload(&source).await {
+            self.inner.stop_listeners().await;
+            return Err(e);
+        }
+
+        let inner = self.inner.clone();
+        let watched = source.clone();
+        let watcher = tokio::spawn(async move {
+            let mut signature = watched.signature();
+            loop {
+                tokio::time::sleep(RELOAD_INTERVAL).await;
+
+                let current = watched.signature();
+                if current == signature {
+                    continue;
+                }
+                signature = current;
+
+                // 加载失败时保留上一次的规则继续服务
+                if let Err(e) = inner.reload(&watched).await {
+                    eprintln!("[MockServer] 重新加载 Mock 规则失败: {}", e);
+                    *inner.error.write().unwrap_or_else(|e| e.into_inner()) = Some(e);
+                }
+            }
+        });
+
+        *self.active.lock().await = Some(ActiveMocks {
+            workspace_id,
+            source,
+            watcher,
+        });
+
+        Ok(self.status().await)
+    }
+
+    pub async fn stop(&self) {
+        if let Some(active) = self.active.lock().await.take() {
+            active.watcher.abort();
+        }
+        self.inner.stop_listeners().await;
+        self.inner
+            .table
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        *self.inner.error.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    pub async fn status(&self) -> MockServerStatus {
+        let active = self.active.lock().await;
+        let mut ports: Vec<u16> = self
+            .inner
+            .listeners
+            .lock()
+            .await
+            .values()
+            .map(|l| l.port)
+            .collect();
+        ports.sort_unstable();
+
+        MockServerStatus {
+            workspace_id: active.as_ref().map(|a| a.workspace_id.clone()),
+            source: active.as_ref().map(|a| a.source.describe()),
+            rule_sets: self
+                .inner
+                .table
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .map(|loaded| loaded.set.clone())
+                .collect(),
+            ports,
+            error: self
+                .inner
+                .error
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+        }
+    }
+}
+
+impl MockInner {
+    /// 重新加载规则，并按规则组增减或迁移监听端口
+    async fn reload(self: &Arc<Self>, source: &MockRuleSource) -> Result<(), String> {
+        let loaded = MockRuleStore::load_source(source)?;
+
+        let mut listeners = self.listeners.lock().await;
+        let wanted: HashMap<String, u16> = loaded
+            .iter()
+            .map(|l| (l.set.id.clone(), l.set.port))
+            .collect();
+
+        let stale: Vec<String> = listeners
+            .iter()
+            .filter(|(id, listener)| {
+                wanted
+                    .get(*id)
+                    .is_none_or(|port| *port != 0 && *port != listener.port)
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in stale {
+            if let Some(listener) = listeners.remove(&id) {
+                let _ = listener.shutdown.send(());
+                let _ = listener.handle.await;
+            }
+        }
+
+        let mut errors = Vec::new();
+        for (id, port) in wanted {
+            if listeners.contains_key(&id) {
+                continue;
+            }
+            match self.listen(id.clone(), port) {
+                Ok(listener) => {
+                    listeners.insert(id, listener);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        *self.table.write().unwrap_or_else(|e| e.into_inner()) = loaded;
+        let error = (!errors.is_empty()).then(|| errors.join("; "));
+        *self.error.write().unwrap_or_else(|e| e.into_inner()) = error.clone();
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn listen(self: &Arc<Self>, set_id: String, port: u16) -> Result<MockListener, String> {
+        let addr = SocketAddr::from(([127, 0, 0, 1], port));
+        let builder = Server::try_bind(&addr)
+            .map_err(|e| format!("Mock 服务无法监听端口 {}: {}", port, e))?;
+
+        let inner = self.clone();
+        let make_service = make_service_fn(move |_| {
+            let inner = inner.clone();
+            let set_id = set_id.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    handle(req, inner.clone(), set_id.clone())
+                }))
+            }
+        });
+
+        let bound = builder.serve(make_service);
+        let bound_port = bound.local_addr().port();
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            let graceful = bound.with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            });
+            if let Err(e) = graceful.await {
+                eprintln!("[MockServer] Mock 服务异常退出: {}", e);
+            }
+        });
+
+        Ok(MockListener {
+            port: bound_port,
+            shutdown,
+            handle,
+        })
+    }
+
+    async fn stop_listeners(&self) {
+        let listeners: Vec<MockListener> = self
+            .listeners
+            .lock()
+            .await
+            .drain()
+            .map(|(_, listener)| listener)
+            .collect();
+        for listener in listeners {
+            let _ = listener.shutdown.send(());
+            let _ = listener.handle.await;
+        }
+    }
+}
+
+/// 命中的规则及其所在规则组
+enum Resolved {
+    Static {
+        action: MockAction,
+        base_dir: PathBuf,
+    },
+    Upstream(String),
+    NotFound,
+}
+
+/// 先匹配本规则组，项目规则组未命中时再匹配工作区级规则组，最后透传到上游
+fn resolve(table: &[LoadedRuleSet], set_id: &str, method: &str, path: &str) -> Resolved {
+    let Some(own) = table.iter().find(|l| l.set.id == set_id) else {
+        return Resolved::NotFound;
+    };
+
+    let candidates = std::iter::once(own).chain(
+        table
+            .iter()
+            .filter(|l| own.set.project_id.is_some() && l.set.project_id.is_none()),
+    );
+    for loaded in candidates {
+        if let Some(rule) = loaded
+            .set
+            .rules
+            .iter()
+            .find(|rule| rule_matches(rule, method, path))
+        {
+            return match &rule.action {
+                MockAction::Static { .. } => Resolved::Static {
+                    action: rule.action.clone(),
+                    base_dir: loaded.base_dir.clone(),
+                },
+                MockAction::PassThrough { upstream } => {
+                    match upstream.clone().or_else(|| loaded.set.upstream.clone()) {
+                        Some(upstream) => Resolved::Upstream(upstream),
+                        None => Resolved::NotFound,
+                    }
+                }
+            };
+        }
+    }
+
+    match own.set.upstream.clone() {
+        Some(upstream) => Resolved::Upstream(upstream),
+        None => Resolved::NotFound,
+    }
+}
+
+fn json_response(status: StatusCode, body: serde_json::Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json; charset=utf-8")
+        .body(Body::from(body.to_string()))
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
+async fn handle(
+    req: Request<Body>,
+    inner: Arc<MockInner>,
+    set_id: String,
+) -> Result<Response<Body>, Infallible> {
+    let resolved = {
+        let table = inner.table.read().unwrap_or_else(|e| e.into_inner());
+        resolve(&table, &set_id, req.method().as_str(), req.uri().path())
+    };
+
+    let response = match resolved {
+        Resolved::Static { action, base_dir } => static_response(action, &base_dir).await,
+        Resolved::Upstream(upstream) => pass_through(req, &upstream, &inner.client).await,
+        Resolved::NotFound => json_response(
+            StatusCode::NOT_FOUND,
+            json!({
+                "error": format!("没有匹配 {} {} 的 Mock 规则", req.method(), req.uri().path())
+            }),
+        ),
+    };
+
+    Ok(response)
+}
+
+/// 读取规则目录内的静态文件；经符号链接等指向目录外的文件一律拒绝，
+/// 错误信息只带规则中的相对路径
+async fn read_static_file(base_dir: &Path, file: &str) -> Result<String, (StatusCode, String)> {
+    let read_failed = |e: std::io::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("读取 Mock 文件 {} 失败: {}", file, e),
+        )
+    };
+    let base_dir = tokio::fs::canonicalize(base_dir)
+        .await
+        .map_err(read_failed)?;
+    let path = tokio::fs::canonicalize(base_dir.join(file))
+        .await
+        .map_err(read_failed)?;
+    if !path.starts_with(&base_dir) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            format!("Mock 文件 {} 不在规则目录内", file),
+        ));
+    }
+    tokio::fs::read_to_string(&path).await.map_err(read_failed)
+}
+
+async fn static_response(action: MockAction, base_dir: &Path) -> Response<Body> {
+    let MockAction::Static {
+        file,
+        body,
+        status,
+        delay_ms,
+        headers,
+    } = action
+    else {
+        return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({}));
+    };
+
+    if delay_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+    }
+
+    // 每次请求都重新读取文件，修改 JSON 后无需重新加载规则
+    let content = match (file, body) {
+        (Some(file), _) => match read_static_file(base_dir, &file).await {
+            Ok(content) => content,
+            Err((status, message)) => return json_response(status, json!({ "error": message })),
+        },
+        (None, Some(body)) => body.to_string(),
+        (None, None) => String::new(),
+    };
+
+    let mut response = Response::new(Body::from(content));
+    *response.status_mut() = StatusCode::from_u16(status).unwrap_or(StatusCode::OK);
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("application/json; charset=utf-8"),
+    );
+    response_headers.insert(
+        HeaderName::from_static("access-control-allow-origin"),
+        HeaderValue::from_static("*"),
+    );
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            response_headers.insert(name, value);
+        }
+    }
+
+    response
+}
+
+async fn pass_through(
+    mut req: Request<Body>,
+    upstream: &str,
+    client: &UpstreamClient,
+) -> Response<Body> {
+    let path_and_query = req
+        .uri()
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+    let uri: hyper::Uri =
+        match format!("{}{}", upstream.trim_end_matches('/'), path_and_query).parse() {
+            Ok(uri) => uri,
+            Err(e) => {
+                return json_response(
+                    StatusCode::BAD_GATEWAY,
+                    json!({ "error": format!("上游地址无效: {}", e) }),
+                )
+            }
+        };
+
+    let mut upstream_req = Request::builder()
+        .method(req.method().clone())
+        .uri(uri)
+        .body(std::mem::take(req.body_mut()))
+        .unwrap_or_else(|_| Request::new(Body::empty()));
+    let headers = upstream_req.headers_mut();
+    for (name, value) in req.headers() {
+        // Host 由客户端按上游地址生成
+        if name == HOST || HOP_BY_HOP_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        headers.append(name.clone(), value.clone());
+    }
+
+    match client.request(upstream_req).await {
+        Ok(mut response) => {
+            let headers = response.headers_mut();
+            for name in HOP_BY_HOP_HEADERS {
+                headers.remove(name);
+            }
+            response
+        }
+        Err(e) => json_response(
+            StatusCode::BAD_GATEWAY,
+            json!({ "error": format!("无法连接上游 {}: {}", upstream, e) }),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TempDir;
+
+    async fn get(port: u16, method: &str, path: &str) -> (u16, String) {
+        let req = Request::builder()
+            .method(method)
+            .uri(format!("http://127.0.0.1:{}{}", port, path))
+            .body(Body::empty())
+            .unwrap();
+        let response = Client::new().request(req).await.unwrap();
+        let status = response.status().as_u16();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    async fn spawn_upstream() -> u16 {
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|req: Request<Body>| async move {
+                Ok::<_, Infallible>(Response::new(Body::from(format!(
+                    "upstream {}",
+                    req.uri().path()
+                ))))
+            }))
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let port = server.local_addr().port();
+        tokio::spawn(server);
+        port
+    }
+
+    #[test]
+    fn match_path_supports_params_and_wildcards() {
+        assert!(match_path("/api/orders/:id", "/api/orders/42"));
+        assert!(match_path("/api/orders/:id", "/api/orders/42/"));
+        assert!(!match_path("/api/orders/:id", "/api/orders"));
+        assert!(!match_path("/api/orders/:id", "/api/orders/42/items"));
+        assert!(match_path("/api/*/list", "/api/users/list"));
+        assert!(match_path("/api/**", "/api/users/1/roles"));
+        assert!(!match_path("/api/**", "/static/app.js"));
+    }
+
+    #[test]
+    fn validate_rejects_duplicate_ports_and_pass_through_without_upstream() {
+        let set = |id: &str, port: u16, rules: Vec<MockRule>| MockRuleSet {
+            id: id.to_string(),
+            name: id.to_string(),
+            project_id: None,
+            port,
+            upstream: None,
+            rules,
+        };
+        let pass_through = MockRule {
+            method: None,
+            path: "/api/**".to_string(),
+            action: MockAction::PassThrough { upstream: None },
+            enabled: true,
+        };
+
+        assert!(
+            MockRuleStore::validate(&[set("a", 9100, vec![]), set("b", 9100, vec![])]).is_err()
+        );
+        assert!(MockRuleStore::validate(&[set("a", 9100, vec![pass_through])]).is_err());
+        assert!(MockRuleStore::validate(&[set("a", 9100, vec![]), set("b", 9101, vec![])]).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_static_files_outside_rule_directory() {
+        let with_file = |file: &str| MockRuleSet {
+            id: "a".to_string(),
+            name: "a".to_string(),
+            project_id: None,
+            port: 9100,
+            upstream: None,
+            rules: vec![MockRule {
+                method: None,
+                path: "/api/orders".to_string(),
+                action: MockAction::Static {
+                    file: Some(file.to_string()),
+                    body: None,
+                    status: 200,
+                    delay_ms: 0,
+                    headers: HashMap::new(),
+                },
+                enabled: true,
+            }],
+        };
+
+        assert!(MockRuleStore::validate(&[with_file("data/orders.json")]).is_ok());
+        assert!(MockRuleStore::validate(&[with_file("./orders.json")]).is_ok());
+        assert!(MockRuleStore::validate(&[with_file("../secret.json")]).is_err());
+        assert!(MockRuleStore::validate(&[with_file("data/../../secret.json")]).is_err());
+        assert!(MockRuleStore::validate(&[with_file("/etc/passwd")]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn static_file_must_resolve_inside_rule_directory() {
+        let outside = TempDir::with_files("mocks-outside", &[("secret.json", r#"{"token":"x"}"#)]);
+        let dir = TempDir::with_files("mocks", &[("data/orders.json", "[]")]);
+        std::os::unix::fs::symlink(outside.join("secret.json"), dir.join("data/link.json"))
+            .unwrap();
+
+        assert_eq!(
+            read_static_file(&dir, "data/orders.json").await.unwrap(),
+            "[]"
+        );
+        // 经符号链接指向目录外的文件被拒绝，错误中不暴露实际路径
+        let (status, message) = read_static_file(&dir, "data/link.json").await.unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(message.contains("data/link.json"));
+        assert!(!message.contains(&*outside.to_string_lossy()));
+        let (_, message) = read_static_file(&dir, "data/missing.json")
+            .await
+            .unwrap_err();
+        assert!(!message.contains(&*dir.to_string_lossy()));
+    }
+
+    #[tokio::test]
+    async fn serves_rules_with_fallback_and_hot_reload() {
+        let upstream_port = spawn_upstream().await;
+        let dir = TempDir::new("mocks");
+        fs::create_dir_all(dir.join("data")).unwrap();
+        fs::write(
+            dir.join("data").join("order-detail.json"),
+            r#"{"id":42,"status":"paid"}"#,
+        )
+        .unwrap();
+
+        let workspace_rules = json!({
+            "id": "shared",
+            "name": "公共",
+            "port": 0,
+            "rules": [
+                { "method": "GET", "path": "/api/user/current", "action": { "type": "static", "body": { "name": "dev" } } }
+            ]
+        });
+        let order_rules = json!({
+            "id": "order",
+            "name": "订单",
+            "project_id": "order",
+            "port": 0,
+            "upstream": format!("http://127.0.0.1:{}", upstream_port),
+            "rules": [
+                { "method": "GET", "path": "/api/orders/:id", "action": { "type": "static", "file": "data/order-detail.json" } },
+                { "method": "POST", "path": "/api/orders", "action": { "type": "static", "status": 503, "delay_ms": 50, "body": { "error": "busy" } } }
+            ]
+        });
+        fs::write(dir.join("shared.json"), workspace_rules.to_string()).unwrap();
+        fs::write(dir.join("order.json"), order_rules.to_string()).unwrap();
+
+        let server = MockServer::new();
+        server
+            .start_source(
+                "ws".to_string(),
+                MockRuleSource::Directory(dir.to_path_buf()),
+            )
+            .await
+            .unwrap();
+        let port_of = |id: &str| {
+            let inner = server.inner.clone();
+            let id = id.to_string();
+            async move { inner.listeners.lock().await.get(&id).unwrap().port }
+        };
+        let order_port = port_of("order").await;
+
+        assert_eq!(
+            get(order_port, "GET", "/api/orders/42").await,
+            (200, r#"{"id":42,"status":"paid"}"#.to_string())
+        );
+        let started = std::time::Instant::now();
+        assert_eq!(get(order_port, "POST", "/api/orders").await.0, 503);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        // 项目规则未命中时匹配工作区级规则，再透传到上游
+        assert_eq!(
+            get(order_port, "GET", "/api/user/current").await,
+            (200, r#"{"name":"dev"}"#.to_string())
+        );
+        assert_eq!(
+            get(order_port, "GET", "/api/products").await,
+            (200, "upstream /api/products".to_string())
+        );
+        let shared_port = port_of("shared").await;
+        assert_eq!(get(shared_port, "GET", "/api/orders/42").await.0, 404);
+
+        // 修改规则文件后自动重新加载
+        let mut reloaded = order_rules.clone();
+        reloaded["rules"][0]["action"] = json!({ "type": "static", "status": 404, "body": {} });
+        fs::write(dir.join("order.json"), reloaded.to_string()).unwrap();
+        let mut status = 0;
+        for _ in 0..40 {
+            status = get(order_port, "GET", "/api/orders/42").await.0;
+            if status == 404 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(status, 404);
+
+        server.stop().await;
+        assert!(server.status().await.ports.is_empty());
+    }
+}
@@ -1,18 +1,53 @@
+pub mod app_registry;
+pub mod capture_proxy;
+pub mod cert_manager;
+pub mod config_layers;
+pub mod config_migration;
 pub mod config_parser;
+pub mod config_tree;
+pub mod config_validator;
+pub mod dev_gateway;
 pub mod git_manager;
+pub mod health_checker;
 pub mod managed_project_service;
+pub mod mock_server;
+pub mod port_backup;
+pub mod port_inspector;
 pub mod port_manager;
+pub mod port_registry;
 pub mod process_manager;
+pub mod project_detector;
 pub mod project_scanner;
+pub mod scan_cache;
+pub mod scan_filter;
+pub mod template_service;
 pub mod terminal_manager;
 pub mod workspace_list;
 pub mod workspace_service;
+pub mod workspace_watcher;
 
+pub use app_registry::*;
+pub use capture_proxy::*;
+pub use cert_manager::*;
+pub use config_layers::*;
+pub use config_migration::*;
+pub use config_tree::*;
+pub use dev_gateway::*;
 pub use git_manager::*;
+pub use health_checker::*;
 pub use managed_project_service::*;
+pub use mock_server::*;
+pub use port_backup::*;
+pub use port_inspector::*;
 pub use port_manager::*;
+pub use port_registry::*;
 pub use process_manager::*;
+pub use project_detector::*;
 pub use project_scanner::*;
+pub use scan_cache::*;
+pub use scan_filter::*;
+pub use template_service::*;
 pub use terminal_manager::*;
 pub use workspace_list::*;
 pub use workspace_service::*;
+pub use workspace_watcher::*;
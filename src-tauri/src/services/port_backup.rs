@@ -0,0 +1,60 @@
+use crate::models::PortChangeBackup;
+use std::fs;
+use std::path::PathBuf;
+
+/// 端口变更备份，保存在 ~/.zebras-launcher/port-backups/<id>.json
+pub struct PortBackupStore;
+
+impl PortBackupStore {
+    fn get_backup_dir() -> Result<PathBuf, String> {
+        let home = dirs_next::home_dir().ok_or("无法获取用户主目录".to_string())?;
+
+        let backup_dir = home.join(".zebras-launcher").join("port-backups");
+
+        // 确保目录存在
+        fs::create_dir_all(&backup_dir).map_err(|e| format!("创建备份目录失败: {}", e))?;
+
+        Ok(backup_dir)
+    }
+
+    fn get_backup_path(id: &str) -> Result<PathBuf, String> {
+        // 备份 ID 由启动器生成（UUID），拒绝可能跳出备份目录的值
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err("无效的备份 ID".to_string());
+        }
+
+        Ok(Self::get_backup_dir()?.join(format!("{}.json", id)))
+    }
+
+    pub fn save(backup: &PortChangeBackup) -> Result<(), String> {
+        let path = Self::get_backup_path(&backup.id)?;
+
+        let json = serde_json::to_string_pretty(backup)
+            .map_err(|e| format!("序列化端口变更备份失败: {}", e))?;
+
+        fs::write(&path, json).map_err(|e| format!("写入端口变更备份失败: {}", e))
+    }
+
+    pub fn load(id: &str) -> Result<PortChangeBackup, String> {
+        let path = Self::get_backup_path(id)?;
+
+        if !path.exists() {
+            return Err("端口变更备份不存在或已回滚".to_string());
+        }
+
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("读取端口变更备份失败: {}", e))?;
+
+        serde_json::from_str(&content).map_err(|e| format!("解析端口变更备份失败: {}", e))
+    }
+
+    pub fn delete(id: &str) -> Result<(), String> {
+        let path = Self::get_backup_path(id)?;
+
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("删除端口变更备份失败: {}", e))?;
+        }
+
+        Ok(())
+    }
+}
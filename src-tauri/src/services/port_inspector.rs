@@ -0,0 +1,142 @@
+use crate::models::{PortInspection, PortOwner, PortOwnership, ProcessInfo, Workspace};
+use crate::utils::port_checker::is_port_available;
+use crate::utils::port_inspector::{find_listening_pids, process_ancestors, process_details};
+
+pub struct PortInspector;
+
+impl PortInspector {
+    /// 查询端口的占用者，并判断其是否属于启动器进程或已知项目
+    pub fn inspect(port: u16, running: &[ProcessInfo], workspaces: &[Workspace]) -> PortInspection {
+        let owners: Vec<PortOwner> = find_listening_pids(port)
+            .into_iter()
+            .map(|pid| {
+                let details = process_details(pid);
+                let parent_pid = details.as_ref().and_then(|d| d.parent_pid);
+                let command_line = details.as_ref().and_then(|d| d.command_line.clone());
+                let cwd = details.and_then(|d| d.cwd);
+
+                let ownership = Self::match_launcher_process(pid, running)
+                    .or_else(|| {
+                        cwd.as_ref()
+                            .and_then(|cwd| Self::match_known_project(cwd, workspaces))
+                    })
+                    .unwrap_or(PortOwnership::External);
+
+                PortOwner {
+                    pid,
+                    parent_pid,
+                    command_line,
+                    cwd,
+                    ownership,
+                }
+            })
+            .collect();
+
+        PortInspection {
+            port,
+            available: owners.is_empty() && is_port_available(port),
+            owners,
+        }
+    }
+
+    /// 占用进程本身或其祖先是启动器启动的进程
+    fn match_launcher_process(pid: u32, running: &[ProcessInfo]) -> Option<PortOwnership> {
+        std::iter::once(pid)
+            .chain(process_ancestors(pid))
+            .find_map(|candidate| running.iter().find(|p| p.pid == Some(candidate)))
+            .map(|process| PortOwnership::LauncherProcess {
+                process_id: process.process_id.clone(),
+                project_id: process.project_id.clone(),
+                project_name: process.project_name.clone(),
+            })
+    }
+
+    /// 工作目录位于已知项目内（取路径最深的匹配）
+    fn match_known_project(
+        cwd: &std::path::Path,
+        workspaces: &[Workspace],
+    ) -> Option<PortOwnership> {
+        workspaces
+            .iter()
+            .flat_map(|workspace| {
+                workspace
+                    .projects
+                    .iter()
+                    .map(move |project| (workspace, project))
+            })
+            .filter(|(_, project)| cwd.starts_with(&project.path))
+            .max_by_key(|(_, project)| project.path.components().count())
+            .map(|(workspace, project)| PortOwnership::KnownProject {
+                workspace_id: workspace.id.clone(),
+                workspace_name: workspace.name.clone(),
+                project_id: project.id.clone(),
+                project_name: project.name.clone(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProcessStatus, ProjectInfo};
+    use std::path::PathBuf;
+
+    #[test]
+    fn match_known_project_prefers_deepest_path() {
+        let mut workspace = Workspace::new("ws".to_string(), PathBuf::from("/code"));
+        let mut outer = ProjectInfo::new(PathBuf::from("/code/mono"), "mono".to_string());
+        outer.id = "outer".to_string();
+        let mut inner =
+            ProjectInfo::new(PathBuf::from("/code/mono/apps/order"), "order".to_string());
+        inner.id = "inner".to_string();
+        workspace.projects = vec![outer, inner];
+
+        let ownership = PortInspector::match_known_project(
+            &PathBuf::from("/code/mono/apps/order/src"),
+            std::slice::from_ref(&workspace),
+        );
+
+        assert_eq!(
+            ownership,
+            Some(PortOwnership::KnownProject {
+                workspace_id: workspace.id.clone(),
+                workspace_name: "ws".to_string(),
+                project_id: "inner".to_string(),
+                project_name: "order".to_string(),
+            })
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn inspect_marks_launcher_process() {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let running = vec![ProcessInfo {
+            process_id: "proc-1".to_string(),
+            project_id: "project-1".to_string(),
+            project_name: "demo".to_string(),
+            status: ProcessStatus::Running,
+            started_at: chrono::Utc::now(),
+            pid: Some(std::process::id()),
+            health: None,
+        }];
+
+        let inspection = PortInspector::inspect(port, &running, &[]);
+        assert!(!inspection.available);
+        let owner = inspection
+            .owners
+            .iter()
+            .find(|o| o.pid == std::process::id())
+            .unwrap();
+        assert_eq!(
+            owner.ownership,
+            PortOwnership::LauncherProcess {
+                process_id: "proc-1".to_string(),
+                project_id: "project-1".to_string(),
+                project_name: "demo".to_string(),
+            }
+        );
+    }
+}
@@ -1,12 +1,20 @@
-use crate::models::{PortChange, ProjectInfo};
+use crate::models::{
+    ConfigFileBackup, PortAvailability, PortChange, PortChangeReason, PortCheckMode,
+    PortOverrideBackup, PortStrategy, ProjectInfo, WorkspaceSettings,
+};
 use crate::services::config_parser::ConfigParser;
-use crate::utils::port_checker::is_port_available;
+use crate::services::PortRegistry;
+use crate::utils::hash::fnv1a;
+use crate::utils::port_checker::check_port;
 use std::collections::HashSet;
+use std::fs;
 
 pub struct PortManager {
     used_ports: HashSet<u16>,
     range_start: u16,
     range_end: u16,
+    check_mode: PortCheckMode,
+    strategy: PortStrategy,
 }
 
 impl PortManager {
@@ -15,85 +23,295 @@ impl PortManager {
             used_ports: HashSet::new(),
             range_start,
             range_end,
+            check_mode: PortCheckMode::default(),
+            strategy: PortStrategy::Sequential,
         }
     }
 
-    /// 解决端口冲突，返回需要修改的端口变更列表
-    /// global_used_ports: 全局已使用的端口（来自所有工作区）
+    /// 设置端口分配策略
+    pub fn set_strategy(&mut self, strategy: PortStrategy) {
+        self.strategy = strategy;
+    }
+
+    /// 设置端口检测方式
+    pub fn set_check_mode(&mut self, mode: PortCheckMode) {
+        self.check_mode = mode;
+    }
+
+    /// 解决端口冲突，返回需要修改的端口变更列表（只修改传入的项目，不写配置文件）
+    /// registry: 全局端口登记表，其他工作区的租约和预留端口段不会被分配
     pub fn resolve_conflicts(
         &mut self,
-        projects: &mut Vec<ProjectInfo>,
-        global_used_ports: &HashSet<u16>,
+        projects: &mut [ProjectInfo],
+        registry: &PortRegistry,
+        workspace_id: &str,
     ) -> Result<Vec<PortChange>, String> {
         let mut changes = Vec::new();
+        let mut conflicts = Vec::new();
         self.used_ports.clear();
 
-        // 初始化已使用端口集合，包含全局端口
-        self.used_ports.extend(global_used_ports);
+        // 工作区预留了端口段时，只在该段内分配新端口
+        let range = registry
+            .range_for(workspace_id)
+            .map(|r| (r.start, r.end))
+            .unwrap_or((self.range_start, self.range_end));
 
         for project in projects.iter_mut() {
             let requested_port = project.port;
+            let availability = check_port(requested_port, self.check_mode);
+
+            // 项目自己正在运行时，端口被占用是正常的
+            let conflict = if self.used_ports.contains(&requested_port) {
+                Some(PortChangeReason::DuplicateInWorkspace)
+            } else if registry.is_claimed_by_others(workspace_id, requested_port) {
+                Some(PortChangeReason::ClaimedByOtherWorkspace)
+            } else if !availability.available
+                && !registry.is_running_project(&project.id, requested_port)
+            {
+                Some(PortChangeReason::OsOccupied)
+            } else {
+                None
+            };
+
+            let new_port = match self.strategy {
+                PortStrategy::Fixed => {
+                    if let Some(reason) = conflict {
+                        conflicts.push(format!(
+                            "{} 的端口 {} {}",
+                            project.name,
+                            requested_port,
+                            Self::describe_reason(reason)
+                        ));
+                    }
+                    self.used_ports.insert(requested_port);
+                    continue;
+                }
+                PortStrategy::Sequential => {
+                    if conflict.is_none() {
+                        self.used_ports.insert(requested_port);
+                        continue;
+                    }
+                    self.find_next_port(requested_port, range, registry, workspace_id)
+                }
+                PortStrategy::Hashed => {
+                    let preferred = hashed_port(project, range);
+                    if preferred == requested_port && conflict.is_none() {
+                        self.used_ports.insert(requested_port);
+                        continue;
+                    }
+                    self.find_next_port(preferred, range, registry, workspace_id)
+                }
+            };
 
-            // 检查端口是否可用
-            if !is_port_available(requested_port) || self.used_ports.contains(&requested_port) {
-                // 查找下一个可用端口
-                match self.find_next_port(requested_port) {
-                    Some(new_port) => {
+            match new_port {
+                Some(new_port) => {
+                    if new_port != requested_port {
                         changes.push(PortChange {
+                            project_id: project.id.clone(),
                             project_name: project.name.clone(),
                             old_port: requested_port,
                             new_port,
+                            reason: conflict.unwrap_or(PortChangeReason::HashedPreference),
+                            availability: Self::occupied_report(availability),
                         });
-
                         project.port = new_port;
-                        self.used_ports.insert(new_port);
-                    }
-                    None => {
-                        return Err(format!("无法为项目 {} 找到可用端口", project.name));
                     }
+                    self.used_ports.insert(new_port);
+                }
+                None => {
+                    return Err(format!("无法为项目 {} 找到可用端口", project.name));
                 }
-            } else {
-                self.used_ports.insert(requested_port);
             }
         }
 
+        if !conflicts.is_empty() {
+            return Err(format!(
+                "固定端口策略下不会自动修改端口，以下项目存在冲突: {}",
+                conflicts.join("; ")
+            ));
+        }
+
         Ok(changes)
     }
 
-    /// 顺序递增查找下一个可用端口
-    fn find_next_port(&self, start: u16) -> Option<u16> {
-        let mut port = start.max(self.range_start);
+    /// 为新项目分配端口：避开工作区内已有项目的端口和其他工作区的租约，
+    /// 从工作区端口段（未预留时为 range_start..=range_end）的起点开始查找
+    pub fn allocate_port(
+        &mut self,
+        projects: &[ProjectInfo],
+        registry: &PortRegistry,
+        workspace_id: &str,
+    ) -> Option<u16> {
+        self.used_ports = projects.iter().map(|project| project.port).collect();
+        let range = registry
+            .range_for(workspace_id)
+            .map(|r| (r.start, r.end))
+            .unwrap_or((self.range_start, self.range_end));
+        self.find_next_port(range.0, range, registry, workspace_id)
+    }
 
-        while port <= self.range_end {
-            if is_port_available(port) && !self.used_ports.contains(&port) {
-                return Some(port);
-            }
-            port += 1;
+    fn describe_reason(reason: PortChangeReason) -> &'static str {
+        match reason {
+            PortChangeReason::OsOccupied => "已被系统中的其他进程占用",
+            PortChangeReason::ClaimedByOtherWorkspace => "已被其他工作区使用",
+            PortChangeReason::DuplicateInWorkspace => "与工作区内其他项目重复",
+            PortChangeReason::HashedPreference => "不是首选端口",
+        }
+    }
+
+    /// 仅在端口确实被系统占用时保留检测报告
+    fn occupied_report(availability: PortAvailability) -> Option<PortAvailability> {
+        if availability.available {
+            None
+        } else {
+            Some(availability)
+        }
+    }
+
+    /// 从 start 开始在端口段内递增查找可用端口，到达段尾后从段首继续
+    fn find_next_port(
+        &self,
+        start: u16,
+        (range_start, range_end): (u16, u16),
+        registry: &PortRegistry,
+        workspace_id: &str,
+    ) -> Option<u16> {
+        if range_start > range_end {
+            return None;
+        }
+
+        let mut start = start.max(range_start);
+        if start > range_end {
+            start = range_start;
         }
 
-        None
+        (start..=range_end).chain(range_start..start).find(|&port| {
+            !self.used_ports.contains(&port)
+                && !registry.is_claimed_by_others(workspace_id, port)
+                && check_port(port, self.check_mode).available
+        })
     }
 
-    /// 应用端口变更到本地配置文件
+    /// 应用端口变更到本地配置文件，返回修改前的文件备份
+    /// 任意一个文件写入失败时，已写入的文件会恢复原状
     pub fn apply_port_changes(
         changes: &[PortChange],
         projects: &[ProjectInfo],
-    ) -> Result<(), String> {
+        strict_json: bool,
+    ) -> Result<Vec<ConfigFileBackup>, String> {
+        let mut backups: Vec<ConfigFileBackup> = Vec::new();
+
         for change in changes {
             // 找到对应的项目
-            if let Some(project) = projects.iter().find(|p| p.name == change.project_name) {
-                ConfigParser::update_port(project, change.new_port)
-                    .map_err(|e| format!("更新端口配置失败: {:?}", e))?;
+            let Some(project) = projects.iter().find(|p| p.id == change.project_id) else {
+                continue;
+            };
+            let Some(path) = ConfigParser::local_config_path(project) else {
+                continue;
+            };
+
+            if !backups.iter().any(|b| b.path == path) {
+                let original = if path.exists() {
+                    Some(
+                        fs::read_to_string(&path)
+                            .map_err(|e| format!("备份 {} 失败: {}", path.display(), e))?,
+                    )
+                } else {
+                    None
+                };
+                backups.push(ConfigFileBackup { path, original });
+            }
+
+            if let Err(e) = ConfigParser::update_port(project, change.new_port, strict_json) {
+                // 已写入的文件恢复原状，不留下半完成的修改
+                let _ = Self::restore_backups(&backups);
+                return Err(format!("更新端口配置失败: {:?}", e));
+            }
+        }
+
+        Ok(backups)
+    }
+
+    /// 没有本地配置文件可写的项目（自动识别的项目）把新端口记入工作区设置，返回原来的值用于回滚
+    pub fn apply_port_overrides(
+        changes: &[PortChange],
+        projects: &[ProjectInfo],
+        settings: &mut WorkspaceSettings,
+    ) -> Vec<PortOverrideBackup> {
+        changes
+            .iter()
+            .filter_map(|change| projects.iter().find(|p| p.id == change.project_id))
+            .filter(|project| ConfigParser::local_config_path(project).is_none())
+            .map(|project| {
+                let project_path = project.path.to_string_lossy().to_string();
+                let original = settings
+                    .port_overrides
+                    .insert(project_path.clone(), project.port);
+                PortOverrideBackup {
+                    project_path,
+                    port: project.port,
+                    original,
+                }
+            })
+            .collect()
+    }
+
+    /// 按备份恢复工作区设置中的端口覆盖，后写入的先恢复
+    pub fn restore_port_overrides(
+        backups: &[PortOverrideBackup],
+        settings: &mut WorkspaceSettings,
+    ) {
+        for backup in backups.iter().rev() {
+            match backup.original {
+                Some(port) => settings
+                    .port_overrides
+                    .insert(backup.project_path.clone(), port),
+                None => settings.port_overrides.remove(&backup.project_path),
+            };
+        }
+    }
+
+    /// 将配置文件恢复为备份时的内容；备份时不存在的文件会被删除
+    pub fn restore_backups(backups: &[ConfigFileBackup]) -> Result<(), String> {
+        for backup in backups {
+            match &backup.original {
+                Some(content) => fs::write(&backup.path, content)
+                    .map_err(|e| format!("恢复 {} 失败: {}", backup.path.display(), e))?,
+                None if backup.path.exists() => fs::remove_file(&backup.path)
+                    .map_err(|e| format!("删除 {} 失败: {}", backup.path.display(), e))?,
+                None => {}
             }
         }
         Ok(())
     }
 }
 
+/// 由项目名和项目目录名计算稳定的首选端口（FNV-1a）；不使用随机生成的工作区 ID，
+/// 重建工作区、换机器克隆后结果一致
+fn hashed_port(project: &ProjectInfo, (range_start, range_end): (u16, u16)) -> u16 {
+    let dir_name = project
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let hash = fnv1a(
+        project
+            .name
+            .bytes()
+            .chain(std::iter::once(0))
+            .chain(dir_name.bytes()),
+    );
+
+    let span = u64::from(range_end.saturating_sub(range_start)) + 1;
+    range_start + (hash % span) as u16
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::ZebrasVersion;
+    use crate::services::ProjectScanner;
+    use crate::utils::test_dir::TempDir;
     use std::path::PathBuf;
 
     #[test]
@@ -119,10 +337,222 @@ mod tests {
             debug: None,
             enabled: None,
             runnable: true,
+            health_check: None,
+            capture: None,
+            diagnostics: Vec::new(),
+            config_env: None,
+            version_candidates: Vec::new(),
+            start_command: None,
         }];
 
-        let global_ports = HashSet::new();
-        let result = manager.resolve_conflicts(&mut projects, &global_ports);
+        let registry = PortRegistry::default();
+        let result = manager.resolve_conflicts(&mut projects, &registry, "ws");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn resolve_conflicts_skips_ports_claimed_by_other_workspace() {
+        let mut manager = PortManager::new(59100, 59200);
+
+        let mut other = ProjectInfo::new(PathBuf::from("/other"), "other".to_string());
+        other.port = 59100;
+        let mut registry = PortRegistry::default();
+        registry.sync_configured("ws-other", &[other]);
+        registry.reserve_range("ws-other", 59101, 59110).unwrap();
+
+        let mut mine = ProjectInfo::new(PathBuf::from("/mine"), "mine".to_string());
+        mine.port = 59100;
+        let mut projects = vec![mine];
+
+        let changes = manager
+            .resolve_conflicts(&mut projects, &registry, "ws")
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(projects[0].port > 59110);
+    }
+
+    #[test]
+    fn hashed_port_is_stable_and_within_range() {
+        let order = ProjectInfo::new(PathBuf::from("/code/shop/order"), "order".to_string());
+        let port = hashed_port(&order, (8000, 9000));
+        assert_eq!(port, hashed_port(&order, (8000, 9000)));
+        assert!((8000..=9000).contains(&port));
+        assert_eq!(hashed_port(&order, (8500, 8500)), 8500);
+
+        // 重建工作区（工作区 ID 重新生成）或在其他目录克隆后首选端口不变
+        let recreated =
+            ProjectInfo::new(PathBuf::from("/home/dev/shop/order"), "order".to_string());
+        assert_ne!(recreated.id, order.id);
+        assert_eq!(hashed_port(&recreated, (8000, 9000)), port);
+        let mut manager = PortManager::new(8000, 9000);
+        manager.set_strategy(PortStrategy::Hashed);
+        let assigned: Vec<u16> = ["ws-1", "ws-2"]
+            .into_iter()
+            .map(|workspace_id| {
+                let mut projects = vec![recreated.clone()];
+                manager
+                    .resolve_conflicts(&mut projects, &PortRegistry::default(), workspace_id)
+                    .unwrap();
+                projects[0].port
+            })
+            .collect();
+        assert_eq!(assigned[0], assigned[1]);
+    }
+
+    #[test]
+    fn hashed_strategy_assigns_preferred_port_and_probes_on_collision() {
+        let range = (59300, 59399);
+        let mut manager = PortManager::new(range.0, range.1);
+        manager.set_strategy(PortStrategy::Hashed);
+
+        let mut first = ProjectInfo::new(PathBuf::from("/a/order"), "order".to_string());
+        first.port = 1;
+        let mut second = ProjectInfo::new(PathBuf::from("/b/order"), "order".to_string());
+        second.port = 2;
+        let mut projects = vec![first, second];
+
+        manager
+            .resolve_conflicts(&mut projects, &PortRegistry::default(), "ws")
+            .unwrap();
+
+        let preferred = hashed_port(&projects[0], range);
+        assert_eq!(projects[0].port, preferred);
+        assert_ne!(projects[1].port, preferred);
+        assert!((range.0..=range.1).contains(&projects[1].port));
+
+        // 再次解析时端口保持不变
+        let changes = manager
+            .resolve_conflicts(&mut projects[..1], &PortRegistry::default(), "ws")
+            .unwrap();
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn fixed_strategy_reports_conflicts_without_reassigning() {
+        let listener = std::net::TcpListener::bind(("0.0.0.0", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let mut manager = PortManager::new(8000, 9000);
+        manager.set_strategy(PortStrategy::Fixed);
+
+        let mut project = ProjectInfo::new(PathBuf::from("/a"), "order".to_string());
+        project.port = port;
+        let mut projects = vec![project];
+
+        let err = manager
+            .resolve_conflicts(&mut projects, &PortRegistry::default(), "ws")
+            .unwrap_err();
+        assert!(err.contains("order"));
+        assert_eq!(projects[0].port, port);
+    }
+
+    #[test]
+    fn apply_port_changes_backs_up_and_restores_exactly() {
+        let dir = TempDir::new("port-backup");
+        let v3_dir = dir.join("v3");
+        let v2_dir = dir.join("v2");
+        fs::create_dir_all(&v3_dir).unwrap();
+        fs::create_dir_all(&v2_dir).unwrap();
+
+        let original = "// 本地配置\nexport default {\n  port: 8000,\n};\n";
+        fs::write(v3_dir.join("zebras.config.local.ts"), original).unwrap();
+
+        // 同名项目按 id 区分
+        let mut v3 = ProjectInfo::new(v3_dir.clone(), "order".to_string());
+        v3.version = ZebrasVersion::V3;
+        let mut v2 = ProjectInfo::new(v2_dir.clone(), "order".to_string());
+        v2.version = ZebrasVersion::V2;
+
+        let change = |project: &ProjectInfo, new_port| PortChange {
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            old_port: 8000,
+            new_port,
+            reason: PortChangeReason::DuplicateInWorkspace,
+            availability: None,
+        };
+        let backups = PortManager::apply_port_changes(
+            &[change(&v3, 8001), change(&v2, 8002)],
+            &[v3, v2],
+            false,
+        )
+        .unwrap();
+
+        assert!(fs::read_to_string(v3_dir.join("zebras.config.local.ts"))
+            .unwrap()
+            .contains("8001"));
+        assert!(v2_dir.join("zebra.local.json").exists());
+
+        PortManager::restore_backups(&backups).unwrap();
+        assert_eq!(
+            fs::read_to_string(v3_dir.join("zebras.config.local.ts")).unwrap(),
+            original
+        );
+        assert!(!v2_dir.join("zebra.local.json").exists());
+    }
+
+    #[test]
+    fn detected_projects_keep_their_new_port_across_rescans() {
+        let dir = TempDir::new("port-override");
+        fs::write(
+            dir.join("package.json"),
+            r#"{ "scripts": { "dev": "vite" }, "devDependencies": { "vite": "^5" } }"#,
+        )
+        .unwrap();
+
+        let mut settings = WorkspaceSettings::default();
+        let mut project = ProjectScanner::rescan_project(&dir, &settings).unwrap();
+        assert_eq!(project.port, 5173);
+        project.port = 5174;
+        let change = PortChange {
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            old_port: 5173,
+            new_port: 5174,
+            reason: PortChangeReason::OsOccupied,
+            availability: None,
+        };
+
+        // 没有配置文件可写，端口记入工作区设置
+        let projects = [project];
+        let files =
+            PortManager::apply_port_changes(std::slice::from_ref(&change), &projects, false)
+                .unwrap();
+        assert!(files.is_empty());
+        let overrides = PortManager::apply_port_overrides(&[change], &projects, &mut settings);
+        assert_eq!(overrides[0].original, None);
+        assert_eq!(
+            ProjectScanner::rescan_project(&dir, &settings)
+                .unwrap()
+                .port,
+            5174
+        );
+
+        PortManager::restore_port_overrides(&overrides, &mut settings);
+        assert!(settings.port_overrides.is_empty());
+        assert_eq!(
+            ProjectScanner::rescan_project(&dir, &settings)
+                .unwrap()
+                .port,
+            5173
+        );
+    }
+
+    #[test]
+    fn resolve_conflicts_reports_duplicate_reason() {
+        let mut manager = PortManager::new(59400, 59499);
+
+        let mut a = ProjectInfo::new(PathBuf::from("/a"), "a".to_string());
+        a.port = 59400;
+        let mut b = ProjectInfo::new(PathBuf::from("/b"), "b".to_string());
+        b.port = 59400;
+        let mut projects = vec![a, b];
+
+        let changes = manager
+            .resolve_conflicts(&mut projects, &PortRegistry::default(), "ws")
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].project_name, "b");
+        assert_eq!(changes[0].reason, PortChangeReason::DuplicateInWorkspace);
+    }
 }
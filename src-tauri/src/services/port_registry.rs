@@ -0,0 +1,336 @@
+use crate::models::{LeaseType, PortLease, PortRangeReservation, ProjectInfo, Workspace};
+use crate::services::{WorkspaceList, WorkspaceService};
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// 配置租约在多久未刷新后视为过期
+const CONFIGURED_LEASE_TTL_DAYS: i64 = 30;
+
+/// 多个命令可能并发修改登记表，读改写需要串行
+static REGISTRY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// 全局端口登记表，持久化在 ~/.zebras-launcher/ports.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PortRegistry {
+    #[serde(default)]
+    pub leases: Vec<PortLease>,
+    #[serde(default)]
+    pub ranges: Vec<PortRangeReservation>,
+}
+
+impl PortRegistry {
+    /// 获取登记表文件路径
+    fn get_registry_path() -> Result<PathBuf, String> {
+        let home = dirs_next::home_dir().ok_or("无法获取用户主目录".to_string())?;
+
+        let config_dir = home.join(".zebras-launcher");
+
+        // 确保目录存在
+        fs::create_dir_all(&config_dir).map_err(|e| format!("创建配置目录失败: {}", e))?;
+
+        Ok(config_dir.join("ports.json"))
+    }
+
+    /// 加载登记表；首次使用时从已有工作区导入配置端口
+    pub fn load() -> Result<Self, String> {
+        let registry_path = Self::get_registry_path()?;
+
+        if !registry_path.exists() {
+            return Ok(Self::seed_from_workspaces());
+        }
+
+        let content =
+            fs::read_to_string(&registry_path).map_err(|e| format!("读取端口登记表失败: {}", e))?;
+
+        serde_json::from_str(&content).map_err(|e| format!("解析端口登记表失败: {}", e))
+    }
+
+    /// 保存登记表
+    pub fn save(&mut self) -> Result<(), String> {
+        let registry_path = Self::get_registry_path()?;
+
+        self.leases
+            .sort_by(|a, b| a.port.cmp(&b.port).then(a.project_id.cmp(&b.project_id)));
+
+        let json = serde_json::to_string_pretty(&self)
+            .map_err(|e| format!("序列化端口登记表失败: {}", e))?;
+
+        fs::write(&registry_path, json).map_err(|e| format!("写入端口登记表失败: {}", e))?;
+
+        Ok(())
+    }
+
+    /// 加锁读取、清理过期租约、修改并保存
+    pub fn update<T>(f: impl FnOnce(&mut Self) -> Result<T, String>) -> Result<T, String> {
+        let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut registry = Self::load()?;
+        registry.prune_stale(Utc::now());
+        let result = f(&mut registry)?;
+        registry.save()?;
+
+        Ok(result)
+    }
+
+    fn seed_from_workspaces() -> Self {
+        let mut registry = Self::default();
+
+        let list = WorkspaceList::load().unwrap_or_else(|_| WorkspaceList {
+            workspaces: Vec::new(),
+        });
+        for workspace_ref in list.workspaces.iter() {
+            if let Ok(workspace) = WorkspaceService::load_workspace(&workspace_ref.config_path) {
+                registry.sync_workspace(&workspace);
+            }
+        }
+
+        registry
+    }
+
+    /// 移除过期的配置租约，以及进程已退出的运行租约
+    pub fn prune_stale(&mut self, now: DateTime<Utc>) {
+        self.leases.retain(|lease| {
+            if lease.expires_at.is_some_and(|expires_at| expires_at <= now) {
+                return false;
+            }
+
+            match (lease.lease_type, lease.pid) {
+                (LeaseType::Running, Some(pid)) => is_process_alive(pid),
+                _ => true,
+            }
+        });
+    }
+
+    /// 用工作区当前的项目列表刷新该工作区的配置租约
+    pub fn sync_workspace(&mut self, workspace: &Workspace) {
+        self.sync_configured(&workspace.id, &workspace.projects);
+    }
+
+    pub fn sync_configured(&mut self, workspace_id: &str, projects: &[ProjectInfo]) {
+        let now = Utc::now();
+
+        self.leases.retain(|lease| {
+            !(lease.workspace_id == workspace_id && lease.lease_type == LeaseType::Configured)
+        });
+
+        for project in projects.iter().filter(|p| p.is_valid && p.port > 0) {
+            self.leases.push(PortLease {
+                port: project.port,
+                workspace_id: workspace_id.to_string(),
+                project_id: project.id.clone(),
+                project_name: project.name.clone(),
+                lease_type: LeaseType::Configured,
+                pid: None,
+                updated_at: now,
+                expires_at: Some(now + Duration::days(CONFIGURED_LEASE_TTL_DAYS)),
+            });
+        }
+    }
+
+    /// 记录正在运行的项目端口
+    pub fn lease_running(&mut self, workspace_id: &str, project: &ProjectInfo, pid: Option<u32>) {
+        self.release_running(&project.id);
+
+        if project.port == 0 {
+            return;
+        }
+
+        self.leases.push(PortLease {
+            port: project.port,
+            workspace_id: workspace_id.to_string(),
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            lease_type: LeaseType::Running,
+            pid,
+            updated_at: Utc::now(),
+            expires_at: None,
+        });
+    }
+
+    pub fn release_running(&mut self, project_id: &str) {
+        self.leases.retain(|lease| {
+            !(lease.project_id == project_id && lease.lease_type == LeaseType::Running)
+        });
+    }
+
+    /// 启动器启动时调用：上次会话的进程都已在退出时停止
+    pub fn release_all_running(&mut self) {
+        self.leases
+            .retain(|lease| lease.lease_type != LeaseType::Running);
+    }
+
+    /// 丢弃已不在工作区列表中的工作区的租约和端口段
+    pub fn retain_workspaces(&mut self, workspace_ids: &[String], current_workspace_id: &str) {
+        let known = |id: &str| id == current_workspace_id || workspace_ids.iter().any(|w| w == id);
+        self.leases.retain(|lease| known(&lease.workspace_id));
+        self.ranges.retain(|range| known(&range.workspace_id));
+    }
+
+    /// 删除工作区时释放其全部租约和端口段
+    pub fn remove_workspace(&mut self, workspace_id: &str) {
+        self.leases
+            .retain(|lease| lease.workspace_id != workspace_id);
+        self.release_range(workspace_id);
+    }
+
+    /// 为工作区预留端口段，不允许与其他工作区的端口段重叠
+    pub fn reserve_range(
+        &mut self,
+        workspace_id: &str,
+        start: u16,
+        end: u16,
+    ) -> Result<(), String> {
+        if start == 0 || start > end {
+            return Err("端口段无效".to_string());
+        }
+
+        if let Some(other) = self
+            .ranges
+            .iter()
+            .find(|r| r.workspace_id != workspace_id && r.overlaps(start, end))
+        {
+            return Err(format!(
+                "端口段 {}-{} 与其他工作区的端口段 {}-{} 重叠",
+                start, end, other.start, other.end
+            ));
+        }
+
+        self.release_range(workspace_id);
+        self.ranges.push(PortRangeReservation {
+            workspace_id: workspace_id.to_string(),
+            start,
+            end,
+        });
+        self.ranges.sort_by_key(|r| r.start);
+
+        Ok(())
+    }
+
+    pub fn release_range(&mut self, workspace_id: &str) {
+        self.ranges.retain(|r| r.workspace_id != workspace_id);
+    }
+
+    pub fn range_for(&self, workspace_id: &str) -> Option<&PortRangeReservation> {
+        self.ranges.iter().find(|r| r.workspace_id == workspace_id)
+    }
+
+    /// 端口被其他工作区的租约占用，或位于其他工作区预留的端口段内
+    pub fn claimed_by_other(&self, workspace_id: &str, port: u16) -> Option<&PortLease> {
+        self.leases
+            .iter()
+            .find(|lease| lease.port == port && lease.workspace_id != workspace_id)
+    }
+
+    pub fn reserved_by_other(
+        &self,
+        workspace_id: &str,
+        port: u16,
+    ) -> Option<&PortRangeReservation> {
+        self.ranges
+            .iter()
+            .find(|r| r.workspace_id != workspace_id && r.contains(port))
+    }
+
+    pub fn is_claimed_by_others(&self, workspace_id: &str, port: u16) -> bool {
+        self.claimed_by_other(workspace_id, port).is_some()
+            || self.reserved_by_other(workspace_id, port).is_some()
+    }
+
+    /// 端口当前由该项目自己运行的进程占用
+    pub fn is_running_project(&self, project_id: &str, port: u16) -> bool {
+        self.leases.iter().any(|lease| {
+            lease.port == port
+                && lease.project_id == project_id
+                && lease.lease_type == LeaseType::Running
+        })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_process_alive(pid: u32) -> bool {
+    crate::utils::port_inspector::process_details(pid).is_some()
+}
+
+/// Windows 下无法可靠判断，依赖停止进程时的主动释放
+#[cfg(target_os = "windows")]
+fn is_process_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(id: &str, port: u16) -> ProjectInfo {
+        let mut project = ProjectInfo::new(PathBuf::from(format!("/code/{}", id)), id.to_string());
+        project.id = id.to_string();
+        project.port = port;
+        project
+    }
+
+    #[test]
+    fn sync_configured_replaces_workspace_leases() {
+        let mut registry = PortRegistry::default();
+        registry.sync_configured("ws1", &[project("a", 8000), project("b", 8001)]);
+        registry.sync_configured("ws2", &[project("c", 8002)]);
+        registry.sync_configured("ws1", &[project("a", 8005)]);
+
+        let ws1_ports: Vec<u16> = registry
+            .leases
+            .iter()
+            .filter(|l| l.workspace_id == "ws1")
+            .map(|l| l.port)
+            .collect();
+        assert_eq!(ws1_ports, vec![8005]);
+        assert!(registry.is_claimed_by_others("ws1", 8002));
+        assert!(!registry.is_claimed_by_others("ws2", 8002));
+    }
+
+    #[test]
+    fn prune_stale_drops_expired_configured_leases() {
+        let mut registry = PortRegistry::default();
+        registry.sync_configured("ws1", &[project("a", 8000)]);
+
+        registry.prune_stale(Utc::now());
+        assert_eq!(registry.leases.len(), 1);
+
+        registry.prune_stale(Utc::now() + Duration::days(CONFIGURED_LEASE_TTL_DAYS + 1));
+        assert!(registry.leases.is_empty());
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn prune_stale_drops_running_lease_of_dead_process() {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let dead_pid = child.id();
+        child.wait().unwrap();
+
+        let mut registry = PortRegistry::default();
+        registry.lease_running("ws1", &project("a", 8000), Some(dead_pid));
+        registry.lease_running("ws1", &project("b", 8001), Some(std::process::id()));
+        registry.prune_stale(Utc::now());
+
+        let ports: Vec<u16> = registry.leases.iter().map(|l| l.port).collect();
+        assert_eq!(ports, vec![8001]);
+        assert!(registry.is_running_project("b", 8001));
+    }
+
+    #[test]
+    fn reserve_range_rejects_overlap_with_other_workspace() {
+        let mut registry = PortRegistry::default();
+        registry.reserve_range("ws1", 8000, 8099).unwrap();
+
+        assert!(registry.reserve_range("ws2", 8050, 8150).is_err());
+        registry.reserve_range("ws2", 8100, 8199).unwrap();
+        // 调整自己的端口段不算冲突
+        registry.reserve_range("ws1", 8000, 8049).unwrap();
+
+        assert!(registry.is_claimed_by_others("ws1", 8100));
+        assert!(!registry.is_claimed_by_others("ws1", 8049));
+        assert_eq!(registry.range_for("ws1").map(|r| r.end), Some(8049));
+    }
+}
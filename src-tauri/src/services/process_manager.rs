@@ -1,4 +1,4 @@
-use crate::models::{ProcessInfo, ProcessStatus};
+use crate::models::{ProcessInfo, ProcessStatus, StartCommand};
 use chrono::Utc;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -61,22 +61,51 @@ impl ProcessManager {
         Ok("pnpm.cmd".to_string())
     }
 
-    /// 启动项目
+    /// 启动命令对应的 Windows 可执行文件：npm/pnpm/yarn 使用 .cmd 包装，mvnw/gradlew 使用项目中的脚本
+    #[cfg(target_os = "windows")]
+    fn windows_program(program: &str, project_path: &str) -> Result<String, String> {
+        let program = match program {
+            "npm" => Self::find_npm_command()?,
+            "pnpm" => Self::find_pnpm_command()?,
+            "yarn" => "yarn.cmd".to_string(),
+            "./mvnw" => Self::project_program("./mvnw.cmd", project_path),
+            "./gradlew" => Self::project_program("./gradlew.bat", project_path),
+            "mvn" => "mvn.cmd".to_string(),
+            "gradle" => "gradle.bat".to_string(),
+            _ => Self::project_program(program, project_path),
+        };
+        Ok(program)
+    }
+
+    /// 以 ./ 开头的程序相对于项目目录
+    fn project_program(program: &str, project_path: &str) -> String {
+        match program.strip_prefix("./") {
+            Some(relative) => std::path::Path::new(project_path)
+                .join(relative)
+                .to_string_lossy()
+                .to_string(),
+            None => program.to_string(),
+        }
+    }
+
+    /// 启动项目，start_command 中的 {port} 已替换为项目端口
     pub async fn start_project(
         &self,
         project_id: String,
         project_name: String,
         project_path: String,
+        start_command: StartCommand,
     ) -> Result<ProcessInfo, String> {
         let process_id = uuid::Uuid::new_v4().to_string();
 
         // 创建命令（跨平台处理）
         #[cfg(target_os = "windows")]
         let mut child = {
-            let npm_cmd = Self::find_npm_command()?;
-            let mut command = Command::new(&npm_cmd);
+            let program = Self::windows_program(&start_command.program, &project_path)?;
+            let mut command = Command::new(&program);
             command
-                .args(&["run", "start"])
+                .args(&start_command.args)
+                .envs(&start_command.env)
                 .current_dir(&project_path)
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped());
@@ -93,11 +122,16 @@ impl ProcessManager {
         let mut child = {
             // macOS/Linux: 使用缓存的用户 PATH 环境变量
             // 这个 PATH 是从用户的 login shell 获取的，包含了所有全局命令路径
-            let program_path =
-                resolve_program_in_user_path("npm").unwrap_or_else(|| "npm".to_string());
+            let program_path = if start_command.program.starts_with("./") {
+                Self::project_program(&start_command.program, &project_path)
+            } else {
+                resolve_program_in_user_path(&start_command.program)
+                    .unwrap_or_else(|| start_command.program.clone())
+            };
             let mut command = Command::new(program_path);
             command
-                .args(&["run", "start"])
+                .args(&start_command.args)
+                .envs(&start_command.env)
                 .current_dir(&project_path)
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
@@ -178,6 +212,7 @@ impl ProcessManager {
             status: ProcessStatus::Running,
             started_at: Utc::now(),
             pid: Some(pid),
+            health: None,
         })
     }
 
@@ -0,0 +1,516 @@
+use crate::models::{ProjectInfo, ProjectSourceType, StartCommand, ZebrasVersion};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 识别一种非 Zebras 项目，新增项目类型时实现该 trait 并加入 PROJECT_DETECTORS
+pub trait ProjectDetector: Sync {
+    /// 不是该类型的项目时返回 None
+    fn detect(&self, dir: &Path) -> Option<ProjectInfo>;
+}
+
+/// 按顺序尝试，第一个识别成功的生效
+const PROJECT_DETECTORS: [&dyn ProjectDetector; 3] = [
+    &SpringBootDetector,
+    &PackageScriptDetector,
+    &NodeServerDetector,
+];
+
+/// Spring Boot 配置文件所在目录
+const SPRING_RESOURCES: &str = "src/main/resources";
+
+/// Vite 配置文件，从中读取 server.port
+const VITE_CONFIGS: [&str; 4] = [
+    "vite.config.ts",
+    "vite.config.js",
+    "vite.config.mts",
+    "vite.config.mjs",
+];
+
+/// 项目根目录下影响识别结果的文件（package.json 由 ConfigLayers::is_config_file_name 覆盖）
+const DETECTOR_FILES: [&str; 6] = [
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+    ".env",
+];
+
+/// 没有 dev/start 脚本时，Node 服务的入口文件候选
+const NODE_ENTRIES: [&str; 4] = ["server.js", "app.js", "index.js", "main.js"];
+
+/// 存在其中之一的目录是 monorepo 根目录，本身不作为项目
+const MONOREPO_MARKERS: [&str; 5] = [
+    "pnpm-workspace.yaml",
+    "lerna.json",
+    "nx.json",
+    "turbo.json",
+    "rush.json",
+];
+
+/// 依赖其中之一才认为是 Node 服务
+const NODE_SERVER_PACKAGES: [&str; 5] = ["express", "koa", "fastify", "@nestjs/core", "hapi"];
+
+pub struct ProjectDetectors;
+
+impl ProjectDetectors {
+    pub fn detect(dir: &Path) -> Option<ProjectInfo> {
+        PROJECT_DETECTORS
+            .iter()
+            .find_map(|detector| detector.detect(dir))
+    }
+
+    /// 文件变化可能改变识别结果时返回对应的项目目录：
+    /// 根目录下的构建文件、Vite 配置、.env 和 monorepo 标记，以及 src/main/resources/application.*
+    pub fn project_dir_of(path: &Path) -> Option<PathBuf> {
+        let name = path.file_name()?.to_string_lossy();
+        let parent = path.parent()?;
+        if DETECTOR_FILES
+            .iter()
+            .chain(VITE_CONFIGS.iter())
+            .chain(MONOREPO_MARKERS.iter())
+            .any(|file| *file == name)
+        {
+            return Some(parent.to_path_buf());
+        }
+        if name.starts_with("application.") && parent.ends_with(SPRING_RESOURCES) {
+            return parent.ancestors().nth(3).map(Path::to_path_buf);
+        }
+        None
+    }
+
+    /// Spring Boot 配置文件目录，存在时需要单独监听
+    pub fn resources_dir(dir: &Path) -> Option<PathBuf> {
+        Some(dir.join(SPRING_RESOURCES)).filter(|resources| resources.is_dir())
+    }
+
+    /// 识别出的项目：版本记为 Managed，不读写 Zebras 配置
+    fn project(
+        dir: &Path,
+        name: String,
+        type_: &str,
+        framework: &str,
+        port: u16,
+        start_command: StartCommand,
+    ) -> ProjectInfo {
+        let mut project = ProjectInfo::new(dir.to_path_buf(), name);
+        project.version = ZebrasVersion::Managed;
+        project.source_type = ProjectSourceType::Detected;
+        project.platform = if type_ == "frontend_app" {
+            "frontend".to_string()
+        } else {
+            "backend".to_string()
+        };
+        project.type_ = type_.to_string();
+        project.framework = Some(framework.to_string());
+        project.port = port;
+        project.start_command = Some(start_command);
+        project
+    }
+
+    fn dir_name(dir: &Path) -> String {
+        dir.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| dir.display().to_string())
+    }
+
+    fn capture_port(pattern: &str, text: &str) -> Option<u16> {
+        Regex::new(pattern)
+            .ok()?
+            .captures(text)?
+            .get(1)?
+            .as_str()
+            .parse()
+            .ok()
+    }
+}
+
+/// Maven 或 Gradle 的 Spring Boot 服务，端口读取 application.properties/yml 中的 server.port
+pub struct SpringBootDetector;
+
+impl ProjectDetector for SpringBootDetector {
+    fn detect(&self, dir: &Path) -> Option<ProjectInfo> {
+        let (name, program, task) = if let Ok(pom) = fs::read_to_string(dir.join("pom.xml")) {
+            // 带 <modules> 的父 pom 只是聚合子模块
+            if !pom.contains("spring-boot") || pom.contains("<modules>") {
+                return None;
+            }
+            // 跳过 <parent> 中的 artifactId
+            let without_parent = Regex::new(r"(?s)<parent>.*?</parent>")
+                .ok()?
+                .replace(&pom, "")
+                .to_string();
+            let name = Regex::new(r"<artifactId>\s*([^<\s]+)\s*</artifactId>")
+                .ok()?
+                .captures(&without_parent)
+                .map(|captures| captures[1].to_string());
+            (name, Self::wrapper(dir, "mvnw", "mvn"), "spring-boot:run")
+        } else {
+            let build = ["build.gradle", "build.gradle.kts"]
+                .iter()
+                .find_map(|file| fs::read_to_string(dir.join(file)).ok())?;
+            if !build.contains("org.springframework.boot") {
+                return None;
+            }
+            let settings = ["settings.gradle", "settings.gradle.kts"]
+                .iter()
+                .find_map(|file| fs::read_to_string(dir.join(file)).ok());
+            // include 了子项目的多项目构建根目录
+            if settings.as_deref().is_some_and(|settings| {
+                Regex::new(r"(?m)^\s*include[\s(]").is_ok_and(|include| include.is_match(settings))
+            }) {
+                return None;
+            }
+            let name = settings.and_then(|settings| {
+                Regex::new(r#"rootProject\.name\s*=\s*["']([^"']+)["']"#)
+                    .ok()?
+                    .captures(&settings)
+                    .map(|captures| captures[1].to_string())
+            });
+            (name, Self::wrapper(dir, "gradlew", "gradle"), "bootRun")
+        };
+
+        let command = StartCommand {
+            program,
+            args: vec![task.to_string()],
+            env: HashMap::from([("SERVER_PORT".to_string(), "{port}".to_string())]),
+        };
+        Some(ProjectDetectors::project(
+            dir,
+            name.unwrap_or_else(|| ProjectDetectors::dir_name(dir)),
+            "backend_service",
+            "Spring Boot",
+            Self::server_port(dir).unwrap_or(8080),
+            command,
+        ))
+    }
+}
+
+impl SpringBootDetector {
+    /// 项目自带 mvnw/gradlew 时优先使用
+    fn wrapper(dir: &Path, wrapper: &str, fallback: &str) -> String {
+        if dir.join(wrapper).is_file() {
+            format!("./{}", wrapper)
+        } else {
+            fallback.to_string()
+        }
+    }
+
+    fn server_port(dir: &Path) -> Option<u16> {
+        let resources = dir.join(SPRING_RESOURCES);
+        if let Ok(properties) = fs::read_to_string(resources.join("application.properties")) {
+            if let Some(port) =
+                ProjectDetectors::capture_port(r"(?m)^\s*server\.port\s*[=:]\s*(\d+)", &properties)
+            {
+                return Some(port);
+            }
+        }
+        ["application.yml", "application.yaml"]
+            .iter()
+            .find_map(|file| fs::read_to_string(resources.join(file)).ok())
+            .and_then(|yaml| Self::yaml_server_port(&yaml))
+    }
+
+    /// 支持 `server.port: 8080` 和 server 下缩进的 `port: 8080`
+    fn yaml_server_port(yaml: &str) -> Option<u16> {
+        let mut in_server = false;
+        for line in yaml.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indented = line.starts_with(' ') || line.starts_with('\t');
+            if !indented {
+                if let Some(port) =
+                    ProjectDetectors::capture_port(r"^server\.port:\s*(\d+)", trimmed)
+                {
+                    return Some(port);
+                }
+                in_server = trimmed == "server:";
+            } else if in_server {
+                if let Some(port) = ProjectDetectors::capture_port(r"^port:\s*(\d+)", trimmed) {
+                    return Some(port);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 带 dev 或 start 脚本的 package.json：Vite、Next.js、Create React App 作为前端应用，其余作为 Node 服务
+pub struct PackageScriptDetector;
+
+impl ProjectDetector for PackageScriptDetector {
+    fn detect(&self, dir: &Path) -> Option<ProjectInfo> {
+        let package = PackageJson::read(dir)?;
+        let script = ["dev", "start"]
+            .into_iter()
+            .find(|script| package.script(script).is_some())?;
+        let script_body = package.script(script).unwrap_or_default();
+        let manager = PackageJson::manager(dir);
+
+        let (type_, framework, port, command) = if package.has_dependency("next") {
+            let port = ProjectDetectors::capture_port(r"(?:-p|--port)[\s=]+(\d+)", script_body)
+                .unwrap_or(3000);
+            let command = PackageJson::run_script(manager, script, &[], true);
+            ("frontend_app", "Next.js", port, command)
+        } else if package.has_dependency("vite") {
+            let port = ProjectDetectors::capture_port(r"--port[\s=]+(\d+)", script_body)
+                .or_else(|| Self::vite_config_port(dir))
+                .unwrap_or(5173);
+            // vite 不读取 PORT 环境变量
+            let command = PackageJson::run_script(manager, script, &["--port", "{port}"], false);
+            ("frontend_app", "Vite", port, command)
+        } else if package.has_dependency("react-scripts") {
+            let port = dotenv_port(dir).unwrap_or(3000);
+            let command = PackageJson::run_script(manager, script, &[], true);
+            ("frontend_app", "Create React App", port, command)
+        } else {
+            let port = dotenv_port(dir).unwrap_or(3000);
+            let command = PackageJson::run_script(manager, script, &[], true);
+            ("backend_service", "Node", port, command)
+        };
+
+        Some(ProjectDetectors::project(
+            dir,
+            package.name(dir),
+            type_,
+            framework,
+            port,
+            command,
+        ))
+    }
+}
+
+impl PackageScriptDetector {
+    fn vite_config_port(dir: &Path) -> Option<u16> {
+        VITE_CONFIGS
+            .iter()
+            .find_map(|file| fs::read_to_string(dir.join(file)).ok())
+            .and_then(|config| ProjectDetectors::capture_port(r"\bport\s*:\s*(\d+)", &config))
+    }
+}
+
+/// 没有 dev/start 脚本、但依赖 express 等服务端框架并有入口文件的 Node 服务，直接用 node 启动
+pub struct NodeServerDetector;
+
+impl ProjectDetector for NodeServerDetector {
+    fn detect(&self, dir: &Path) -> Option<ProjectInfo> {
+        let package = PackageJson::read(dir)?;
+        if !NODE_SERVER_PACKAGES
+            .iter()
+            .any(|name| package.has_dependency(name))
+        {
+            return None;
+        }
+        let entry = package
+            .value
+            .get("main")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| {
+                NODE_ENTRIES
+                    .iter()
+                    .find(|entry| dir.join(entry).is_file())
+                    .map(|entry| entry.to_string())
+            })
+            .filter(|entry| dir.join(entry).is_file())?;
+
+        let port = dotenv_port(dir)
+            .or_else(|| {
+                let source = fs::read_to_string(dir.join(&entry)).ok()?;
+                ProjectDetectors::capture_port(r"\blisten\(\s*(\d+)", &source)
+            })
+            .unwrap_or(3000);
+        let command = StartCommand {
+            program: "node".to_string(),
+            args: vec![entry],
+            env: HashMap::from([("PORT".to_string(), "{port}".to_string())]),
+        };
+
+        Some(ProjectDetectors::project(
+            dir,
+            package.name(dir),
+            "backend_service",
+            "Node",
+            port,
+            command,
+        ))
+    }
+}
+
+/// 非 monorepo 根目录的 package.json
+struct PackageJson {
+    value: Value,
+}
+
+impl PackageJson {
+    /// 声明了 workspaces 或有 lerna.json 等文件的目录是 monorepo 根目录，不作为项目
+    fn read(dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(dir.join("package.json")).ok()?;
+        let value: Value = serde_json::from_str(&content).ok()?;
+        if value.get("workspaces").is_some()
+            || MONOREPO_MARKERS
+                .iter()
+                .any(|marker| dir.join(marker).exists())
+        {
+            return None;
+        }
+        Some(Self { value })
+    }
+
+    fn name(&self, dir: &Path) -> String {
+        self.value
+            .get("name")
+            .and_then(Value::as_str)
+            .filter(|name| !name.trim().is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| ProjectDetectors::dir_name(dir))
+    }
+
+    fn script(&self, name: &str) -> Option<&str> {
+        self.value.get("scripts")?.get(name)?.as_str()
+    }
+
+    fn has_dependency(&self, name: &str) -> bool {
+        ["dependencies", "devDependencies"].iter().any(|field| {
+            self.value
+                .get(field)
+                .and_then(|deps| deps.get(name))
+                .is_some()
+        })
+    }
+
+    /// 按锁文件选择包管理器
+    fn manager(dir: &Path) -> &'static str {
+        if dir.join("pnpm-lock.yaml").exists() {
+            "pnpm"
+        } else if dir.join("yarn.lock").exists() {
+            "yarn"
+        } else {
+            "npm"
+        }
+    }
+
+    /// extra_args 追加到脚本命令后；port_env 为 true 时通过 PORT 环境变量传入端口
+    fn run_script(
+        manager: &str,
+        script: &str,
+        extra_args: &[&str],
+        port_env: bool,
+    ) -> StartCommand {
+        let mut args = vec!["run".to_string(), script.to_string()];
+        if !extra_args.is_empty() {
+            // npm 需要用 -- 把参数传给脚本
+            if manager == "npm" {
+                args.push("--".to_string());
+            }
+            args.extend(extra_args.iter().map(|arg| arg.to_string()));
+        }
+        let mut env = HashMap::new();
+        if port_env {
+            env.insert("PORT".to_string(), "{port}".to_string());
+        }
+        StartCommand {
+            program: manager.to_string(),
+            args,
+            env,
+        }
+    }
+}
+
+/// .env 中的 PORT
+fn dotenv_port(dir: &Path) -> Option<u16> {
+    let content = fs::read_to_string(dir.join(".env")).ok()?;
+    ProjectDetectors::capture_port(r"(?m)^\s*PORT\s*=\s*(\d+)", &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TempDir;
+
+    #[test]
+    fn detects_frontend_node_and_spring_boot_projects() {
+        let root = TempDir::new("detector");
+        root.write(
+            "web/package.json",
+            r#"{ "name": "web", "scripts": { "dev": "vite" }, "devDependencies": { "vite": "^5" } }"#,
+        );
+        root.write(
+            "web/vite.config.ts",
+            "export default { server: { port: 5300 } };\n",
+        );
+        root.write("web/pnpm-lock.yaml", "");
+        root.write(
+            "api/package.json",
+            r#"{ "name": "api", "main": "src/server.js", "dependencies": { "express": "^4" } }"#,
+        );
+        root.write("api/src/server.js", "app.listen(4000);\n");
+        root.write(
+            "order/pom.xml",
+            "<project><parent><artifactId>spring-boot-starter-parent</artifactId></parent>\n<artifactId>order-service</artifactId></project>",
+        );
+        root.write(
+            "order/src/main/resources/application.yml",
+            "spring:\n  application:\n    name: order\nserver:\n  port: 8088\n",
+        );
+        root.write(
+            "mono/package.json",
+            r#"{ "workspaces": ["packages/*"], "scripts": { "dev": "turbo dev" } }"#,
+        );
+
+        let web = ProjectDetectors::detect(&root.join("web")).unwrap();
+        assert_eq!(web.source_type, ProjectSourceType::Detected);
+        assert_eq!(web.framework.as_deref(), Some("Vite"));
+        assert_eq!(web.port, 5300);
+        assert_eq!(
+            web.start_command.unwrap().with_port(5400).args,
+            vec!["run", "dev", "--port", "5400"]
+        );
+
+        let api = ProjectDetectors::detect(&root.join("api")).unwrap();
+        assert_eq!(api.port, 4000);
+        let command = api.start_command.unwrap().with_port(4100);
+        assert_eq!(command.program, "node");
+        assert_eq!(command.env.get("PORT").map(String::as_str), Some("4100"));
+
+        let order = ProjectDetectors::detect(&root.join("order")).unwrap();
+        assert_eq!(order.name, "order-service");
+        assert_eq!(order.port, 8088);
+        assert_eq!(order.start_command.unwrap().args, vec!["spring-boot:run"]);
+
+        assert!(ProjectDetectors::detect(&root.join("mono")).is_none());
+    }
+
+    #[test]
+    fn maps_build_files_to_their_project_dir() {
+        let dir = Path::new("/work/order");
+        for file in [
+            "pom.xml",
+            "build.gradle.kts",
+            "vite.config.ts",
+            ".env",
+            "lerna.json",
+            "src/main/resources/application.yml",
+            "src/main/resources/application.properties",
+        ] {
+            assert_eq!(
+                ProjectDetectors::project_dir_of(&dir.join(file)).as_deref(),
+                Some(dir),
+                "{}",
+                file
+            );
+        }
+        for file in [
+            "README.md",
+            "src/main/java/Application.java",
+            "src/application.yml",
+        ] {
+            assert!(ProjectDetectors::project_dir_of(&dir.join(file)).is_none());
+        }
+    }
+}
@@ -1,110 +1,785 @@
-use crate::models::ProjectInfo;
-use crate::services::config_parser::ConfigParser;
-use std::cell::RefCell;
+use crate::models::{
+    DiagnosticSeverity, ProjectInfo, ProjectSourceType, RescanEntry, RescanReport, ScanStats,
+    VersionCandidate, WorkspaceSettings, ZebrasVersion,
+};
+use crate::services::config_layers::ConfigLayers;
+use crate::services::config_parser::{ConfigParser, ParseError};
+use crate::services::config_validator::ConfigValidator;
+use crate::services::{CachedProject, FileStamp, ProjectDetectors, ScanCache, ScanFilter};
+use ignore::gitignore::Gitignore;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 pub struct ProjectScanner;
 
 impl ProjectScanner {
-    /// 递归扫描目录查找 Zebras 项目
-    /// max_depth: 最大递归深度
-    pub fn scan_directory(root_path: &Path, max_depth: usize) -> Vec<ProjectInfo> {
-        let mut projects = Vec::new();
-        let skip_paths: RefCell<Vec<PathBuf>> = RefCell::new(Vec::new());
-
-        for entry in WalkDir::new(root_path)
-            .max_depth(max_depth)
-            .into_iter()
-            .filter_entry(|e| {
-                let path = e.path();
+    /// 递归扫描工作区文件夹 folder 下的 dir 查找 Zebras 项目，不使用持久化的扫描缓存
+    /// settings: 工作区设置，提供扫描深度、排除规则以及每个项目选择的配置环境和固定的版本
+    pub fn scan_directory(
+        folder: &Path,
+        dir: &Path,
+        settings: &WorkspaceSettings,
+    ) -> Vec<ProjectInfo> {
+        let cache = ScanCache::default();
+        Scan::new(settings, &cache).walk_from(folder, dir)
+    }
 
-                // 跳过 node_modules 目录
-                if path.components().any(|c| c.as_os_str() == "node_modules") {
-                    return false;
-                }
+    /// 扫描指定的文件夹列表，配置文件没有变化的项目直接使用 ~/.zebras-launcher/cache 中的结果
+    pub fn scan_folders(
+        folders: &[String],
+        settings: &WorkspaceSettings,
+    ) -> (Vec<ProjectInfo>, ScanStats) {
+        let mut cache = ScanCache::load();
+        let result = Self::scan_with_cache(folders, settings, &mut cache);
+        if let Err(e) = cache.save() {
+            eprintln!("[ProjectScanner] 保存扫描缓存失败: {}", e);
+        }
+        result
+    }
 
-                // 跳过已识别项目的子目录
-                let skip_list = skip_paths.borrow();
-                for skip_path in skip_list.iter() {
-                    if path.starts_with(skip_path) && path != *skip_path {
-                        return false;
-                    }
-                }
+    /// 并行扫描各文件夹及其子目录，并用本次结果更新缓存中这些文件夹下的条目
+    pub fn scan_with_cache(
+        folders: &[String],
+        settings: &WorkspaceSettings,
+        cache: &mut ScanCache,
+    ) -> (Vec<ProjectInfo>, ScanStats) {
+        let started = Instant::now();
+        let roots: Vec<PathBuf> = folders
+            .iter()
+            .map(PathBuf::from)
+            .filter(|path| path.is_dir())
+            .collect();
 
-                true
-            })
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
+        let scan = Scan::new(settings, cache);
+        let projects: Vec<ProjectInfo> = roots
+            .par_iter()
+            .flat_map_iter(|root| scan.walk_from(root, root))
+            .collect();
+
+        let stats = ScanStats {
+            dirs_visited: scan.dirs_visited.into_inner(),
+            projects: projects.len(),
+            cache_hits: scan.cache_hits.into_inner(),
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+        let fresh = scan.fresh.into_inner().unwrap_or_else(|e| e.into_inner());
+        cache.replace_under(&roots, fresh);
+
+        (projects, stats)
+    }
 
-            // 只检查目录
-            if !path.is_dir() {
+    /// 检查单个路径是否是 Zebras 项目
+    pub fn is_zebras_project(path: &Path) -> bool {
+        ConfigParser::parse_project(path).is_ok()
+    }
+
+    /// 重新扫描单个项目
+    pub fn rescan_project(
+        path: &Path,
+        settings: &WorkspaceSettings,
+    ) -> Result<ProjectInfo, String> {
+        Self::load_project(path, settings)
+            .ok_or_else(|| format!("扫描项目失败: {:?}", ParseError::NotAZebrasProject))
+    }
+
+    /// 把扫描结果合并到已有项目：按规范化路径匹配，匹配到的项目沿用原 ID 和用户设置，
+    /// 文件夹重叠导致重复扫描到的项目只保留一个
+    pub fn merge(
+        existing: &[ProjectInfo],
+        scanned: Vec<ProjectInfo>,
+    ) -> (Vec<ProjectInfo>, RescanReport) {
+        let mut previous: HashMap<String, &ProjectInfo> = existing
+            .iter()
+            .map(|project| (ProjectInfo::stable_id(&project.path), project))
+            .collect();
+        let mut seen = HashSet::new();
+        let mut report = RescanReport::default();
+        let mut merged = Vec::new();
+
+        for mut project in scanned {
+            let key = ProjectInfo::stable_id(&project.path);
+            if !seen.insert(key.clone()) {
                 continue;
             }
+            match previous.remove(&key) {
+                Some(old) => {
+                    project.inherit_user_state(old);
+                    let fields = Self::changed_fields(old, &project);
+                    if !fields.is_empty() {
+                        report.changed.push(Self::report_entry(&project, fields));
+                    }
+                }
+                None => report.added.push(Self::report_entry(&project, Vec::new())),
+            }
+            merged.push(project);
+        }
+
+        // 按原顺序报告消失的项目
+        report.removed = existing
+            .iter()
+            .filter(|project| previous.contains_key(&ProjectInfo::stable_id(&project.path)))
+            .map(|project| Self::report_entry(project, Vec::new()))
+            .collect();
+
+        (merged, report)
+    }
 
-            // 快速检查：只有包含配置文件的目录才尝试解析
-            let has_config =
-                path.join("zebras.config.ts").exists() || path.join("zebra.json").exists();
+    /// 只重新扫描 dirs 下的项目，其余项目原样保留，用于文件监听触发的增量扫描；
+    /// dirs 中新出现的目录按所在文件夹的扫描设置扫描，Zebras 项目的子目录不会被当作新项目
+    pub fn rescan_dirs(
+        existing: &[ProjectInfo],
+        folders: &[String],
+        dirs: &HashSet<PathBuf>,
+        settings: &WorkspaceSettings,
+    ) -> (Vec<ProjectInfo>, RescanReport) {
+        let mut scanned = Vec::new();
+        for project in existing {
+            if !dirs.iter().any(|dir| project.path.starts_with(dir)) {
+                scanned.push(project.clone());
+            } else if let Some(project) = Self::load_project(&project.path, settings) {
+                scanned.push(project);
+            }
+        }
 
-            if !has_config {
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            let Some(root) = folders
+                .iter()
+                .map(Path::new)
+                .filter(|folder| dir.starts_with(folder))
+                .max_by_key(|folder| folder.components().count())
+            else {
                 continue;
+            };
+            // 自动识别的项目下仍可能有其他项目
+            let nested = existing.iter().any(|project| {
+                project.source_type != ProjectSourceType::Detected
+                    && dir.starts_with(&project.path)
+                    && *dir != project.path
+            });
+            if !nested {
+                scanned.extend(Self::scan_directory(root, dir, settings));
             }
+        }
 
-            // 尝试解析项目配置
-            match ConfigParser::parse_project(path) {
-                Ok(project) => {
-                    // 记录这个项目路径，避免扫描其子目录
-                    skip_paths.borrow_mut().push(path.to_path_buf());
-                    projects.push(project);
-                }
-                Err(_) => {
-                    // 配置文件存在但解析失败，跳过
-                    continue;
-                }
+        Self::merge(existing, scanned)
+    }
+
+    fn changed_fields(old: &ProjectInfo, new: &ProjectInfo) -> Vec<String> {
+        [
+            ("name", old.name != new.name),
+            ("version", old.version != new.version),
+            ("type", old.type_ != new.type_),
+            ("platform", old.platform != new.platform),
+            ("domain", old.domain != new.domain),
+            ("port", old.port != new.port),
+            ("framework", old.framework != new.framework),
+            ("debug", old.debug != new.debug),
+            ("config_env", old.config_env != new.config_env),
+            ("is_valid", old.is_valid != new.is_valid),
+            ("error", old.error != new.error),
+        ]
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(field, _)| field.to_string())
+        .collect()
+    }
+
+    fn report_entry(project: &ProjectInfo, fields: Vec<String>) -> RescanEntry {
+        RescanEntry {
+            id: project.id.clone(),
+            name: project.name.clone(),
+            path: project.path.clone(),
+            fields,
+        }
+    }
+
+    /// 识别非 Zebras 项目，工作区设置中有端口覆盖时使用覆盖的端口
+    fn detect_project(path: &Path, settings: &WorkspaceSettings) -> Option<ProjectInfo> {
+        let mut project = ProjectDetectors::detect(path)?;
+        if let Some(port) = settings.port_overrides.get(path.to_string_lossy().as_ref()) {
+            project.port = *port;
+        }
+        Some(project)
+    }
+
+    /// 解析项目并附加配置诊断；不是 Zebras 项目时交给 ProjectDetectors 识别，都不是时返回 None
+    fn load_project(path: &Path, settings: &WorkspaceSettings) -> Option<ProjectInfo> {
+        let key = path.to_string_lossy();
+        let env = settings.config_envs.get(key.as_ref()).map(String::as_str);
+        // 固定的版本只在对应配置文件存在时生效
+        let pinned = settings
+            .version_pins
+            .get(key.as_ref())
+            .filter(|version| ConfigLayers::has_config(path, version));
+
+        let version = match pinned {
+            Some(version) => version.clone(),
+            None => match ConfigParser::detect_version(path) {
+                Some(version) => version,
+                None => return Self::detect_project(path, settings),
+            },
+        };
+        let mut project = match ConfigParser::parse_version(path, &version, env) {
+            Ok(project) => project,
+            Err(ParseError::NotAZebrasProject) => return Self::detect_project(path, settings),
+            Err(err) => Self::broken_project(path, version, env, err),
+        };
+
+        project.diagnostics = ConfigValidator::validate(path, &project.version, env);
+        if pinned.is_none() && ConfigParser::is_version_ambiguous(path) {
+            project.version_candidates = [ZebrasVersion::V2, ZebrasVersion::V3]
+                .into_iter()
+                .map(|version| Self::version_candidate(path, version, env))
+                .collect();
+            let main_file = ConfigLayers::main_file(path, &project.version);
+            project
+                .diagnostics
+                .insert(0, ConfigValidator::ambiguity_diagnostic(path, &main_file));
+        }
+        if !project.is_valid
+            && !project
+                .diagnostics
+                .iter()
+                .any(|d| d.severity == DiagnosticSeverity::Error)
+        {
+            // 校验没有覆盖到的解析错误，整体挂在主配置文件上
+            let main_file = ConfigLayers::main_file(path, &project.version);
+            let file_name = main_file.file_name().unwrap_or_default().to_string_lossy();
+            let message = project.error.clone().unwrap_or_default();
+            project
+                .diagnostics
+                .push(ConfigValidator::file_diagnostic(&file_name, message));
+        }
+
+        Some(project)
+    }
+
+    fn version_candidate(
+        path: &Path,
+        version: ZebrasVersion,
+        env: Option<&str>,
+    ) -> VersionCandidate {
+        let main_file = ConfigLayers::main_file(path, &version);
+        let mut candidate = VersionCandidate {
+            version: version.clone(),
+            config_file: main_file
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            name: None,
+            type_: None,
+            port: None,
+            domain: None,
+            error: None,
+        };
+
+        match ConfigParser::parse_version(path, &version, env) {
+            Ok(project) => {
+                candidate.name = Some(project.name);
+                candidate.type_ = Some(project.type_);
+                candidate.port = Some(project.port);
+                candidate.domain = project.domain;
             }
+            Err(err) => candidate.error = Some(err.to_string()),
+        }
+        candidate
+    }
+
+    /// 配置无法解析时用目录名占位，保证项目仍出现在列表中
+    fn broken_project(
+        path: &Path,
+        version: ZebrasVersion,
+        env: Option<&str>,
+        err: ParseError,
+    ) -> ProjectInfo {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+
+        let mut project = ProjectInfo::new(path.to_path_buf(), name);
+        project.version = version;
+        project.config_env = env.map(str::to_string);
+        project.is_valid = false;
+        project.error = Some(err.to_string());
+        project
+    }
+}
+
+/// 一次扫描的共享状态：扫描规则、只读的旧缓存、本次的解析结果和统计，供并行遍历的各线程使用
+struct Scan<'a> {
+    settings: &'a WorkspaceSettings,
+    filter: ScanFilter,
+    cache: &'a ScanCache,
+    /// 已进入过的符号链接目标（规范化路径），同一目标只进入一次
+    linked: Mutex<HashSet<PathBuf>>,
+    fresh: Mutex<HashMap<String, CachedProject>>,
+    dirs_visited: AtomicUsize,
+    cache_hits: AtomicUsize,
+}
+
+impl<'a> Scan<'a> {
+    fn new(settings: &'a WorkspaceSettings, cache: &'a ScanCache) -> Self {
+        Self {
+            settings,
+            filter: ScanFilter::new(&settings.scan),
+            cache,
+            linked: Mutex::new(HashSet::new()),
+            fresh: Mutex::new(HashMap::new()),
+            dirs_visited: AtomicUsize::new(0),
+            cache_hits: AtomicUsize::new(0),
+        }
+    }
+
+    /// 从工作区文件夹 root 下的 dir 开始遍历；dir 超出深度、被排除或被忽略时不扫描
+    fn walk_from(&self, root: &Path, dir: &Path) -> Vec<ProjectInfo> {
+        let Ok(relative) = dir.strip_prefix(root) else {
+            return Vec::new();
+        };
+        let depth = relative.components().count();
+        if depth > self.settings.scan.max_depth {
+            return Vec::new();
+        }
+        if let Ok(target) = root.canonicalize() {
+            self.linked
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(target);
         }
 
-        projects
+        // 收集 root 到 dir 之间各级目录的忽略规则，并逐级检查是否被排除
+        let Some(rules) = self.filter.ancestor_rules(root, dir) else {
+            return Vec::new();
+        };
+        self.walk(root, dir, depth, &rules)
     }
 
-    /// 扫描指定的文件夹列表
-    pub fn scan_folders(folders: &[String], max_depth: usize) -> Vec<ProjectInfo> {
-        let mut all_projects = Vec::new();
+    /// 识别到 Zebras 项目后不再进入其子目录，自动识别的通用项目继续向下查找；子目录按名称排序后并行遍历，结果顺序保持稳定
+    fn walk(
+        &self,
+        root: &Path,
+        dir: &Path,
+        depth: usize,
+        parent_rules: &[Arc<Gitignore>],
+    ) -> Vec<ProjectInfo> {
+        self.dirs_visited.fetch_add(1, Ordering::Relaxed);
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
 
-        for folder in folders {
-            let path = Path::new(folder);
-            if path.exists() && path.is_dir() {
-                let projects = Self::scan_directory(path, max_depth);
-                all_projects.extend(projects);
+        // (子目录, 是否为符号链接)
+        let mut children: Vec<(PathBuf, bool)> = Vec::new();
+        let mut files = Vec::new();
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            if file_type.is_dir() {
+                children.push((path, false));
+            } else if file_type.is_symlink() && path.is_dir() {
+                children.push((path, true));
+            } else if ConfigLayers::is_config_file_name(&name) {
+                files.extend(FileStamp::read(&path));
             }
         }
 
-        all_projects
+        // 快速检查：只有包含配置文件且符合 include 的目录才尝试解析
+        let relative = dir.strip_prefix(root).unwrap_or(dir);
+        let has_config = ConfigLayers::has_config(dir, &ZebrasVersion::V3)
+            || ConfigLayers::has_config(dir, &ZebrasVersion::V2);
+        let mut found = Vec::new();
+        if self.filter.is_included(relative) {
+            let project = if has_config {
+                files.sort_by(|a, b| a.name.cmp(&b.name));
+                // 配置解析失败的项目也会返回，标记为无效并附带诊断信息
+                self.load(dir, files)
+            } else {
+                // 不是 Zebras 项目时尝试识别 Vite、Spring Boot 等通用项目，结果不缓存
+                ProjectScanner::detect_project(dir, self.settings)
+            };
+            match project {
+                Some(project) if project.source_type != ProjectSourceType::Detected => {
+                    return vec![project];
+                }
+                // 通用项目可能是包含 Zebras 项目的仓库根目录，继续向下查找
+                Some(project) => found.push(project),
+                None => {}
+            }
+        }
+
+        if depth >= self.settings.scan.max_depth {
+            return found;
+        }
+
+        let mut rules = parent_rules.to_vec();
+        rules.extend(self.filter.ignore_rules(dir));
+        children.retain(|(child, _)| {
+            let child_relative = child.strip_prefix(root).unwrap_or(child);
+            !self.filter.is_excluded(child_relative) && !ScanFilter::is_ignored(&rules, child)
+        });
+        children.sort();
+
+        let nested: Vec<ProjectInfo> = children
+            .par_iter()
+            .flat_map_iter(|(child, is_link)| {
+                // 不进入的符号链接只检查本身是否是项目
+                let child_depth = if *is_link && !self.enter_link(dir, child) {
+                    self.settings.scan.max_depth
+                } else {
+                    depth + 1
+                };
+                self.walk(root, child, child_depth, &rules)
+            })
+            .collect();
+        found.extend(nested);
+        found
     }
 
-    /// 检查单个路径是否是 Zebras 项目
-    pub fn is_zebras_project(path: &Path) -> bool {
-        ConfigParser::parse_project(path).is_ok()
+    /// 是否进入符号链接：需开启 follow_symlinks，且目标不是当前目录的上级、之前也没有进入过
+    fn enter_link(&self, dir: &Path, link: &Path) -> bool {
+        if !self.settings.scan.follow_symlinks {
+            return false;
+        }
+        let (Ok(target), Ok(current)) = (link.canonicalize(), dir.canonicalize()) else {
+            return false;
+        };
+        !current.starts_with(&target)
+            && self
+                .linked
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(target)
     }
 
-    /// 重新扫描单个项目
-    pub fn rescan_project(path: &Path) -> Result<ProjectInfo, String> {
-        ConfigParser::parse_project(path).map_err(|e| format!("扫描项目失败: {:?}", e))
+    fn load(&self, dir: &Path, files: Vec<FileStamp>) -> Option<ProjectInfo> {
+        let key = dir.to_string_lossy().to_string();
+        let env = self.settings.config_envs.get(&key).cloned();
+        let pinned = self.settings.version_pins.get(&key).cloned();
+
+        let project = match self
+            .cache
+            .lookup(dir, &files, env.as_deref(), pinned.as_ref())
+        {
+            Some(cached) => {
+                self.cache_hits.fetch_add(1, Ordering::Relaxed);
+                cached.clone()
+            }
+            None => ProjectScanner::load_project(dir, self.settings),
+        };
+
+        let cached = CachedProject {
+            files,
+            env,
+            pinned,
+            project: project.clone(),
+        };
+        self.fresh
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, cached);
+        project
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::test_dir::TempDir;
     use std::path::PathBuf;
 
+    #[test]
+    fn scan_keeps_broken_projects_with_diagnostics() {
+        let root = TempDir::new("scanner");
+        let broken = root.join("order");
+        std::fs::create_dir_all(&broken).unwrap();
+        std::fs::write(
+            broken.join("zebras.config.ts"),
+            "export default {\n  port: 8003,\n};\n",
+        )
+        .unwrap();
+
+        let projects = ProjectScanner::scan_directory(&root, &root, &WorkspaceSettings::default());
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name, "order");
+        assert!(!projects[0].is_valid);
+        assert!(projects[0]
+            .diagnostics
+            .iter()
+            .any(|d| d.message.contains("缺少 name")));
+    }
+
+    #[test]
+    fn ambiguous_projects_report_both_versions_until_pinned() {
+        let root = TempDir::new("scanner");
+        let dir = root.join("user");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("zebra.json"),
+            r#"{ "name": "user", "type": "sub", "port": 8101 }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("zebras.config.ts"),
+            "export default {\n  name: 'user',\n  type: 'app',\n  port: 8201,\n};\n",
+        )
+        .unwrap();
+
+        let projects = ProjectScanner::scan_directory(&root, &root, &WorkspaceSettings::default());
+        assert_eq!(projects.len(), 1);
+        let ports: Vec<Option<u16>> = projects[0]
+            .version_candidates
+            .iter()
+            .map(|candidate| candidate.port)
+            .collect();
+        assert_eq!(ports, vec![Some(8101), Some(8201)]);
+        assert!(projects[0]
+            .diagnostics
+            .iter()
+            .any(|d| d.message.contains("无法判断版本")));
+
+        let mut settings = WorkspaceSettings::default();
+        settings
+            .version_pins
+            .insert(dir.to_string_lossy().to_string(), ZebrasVersion::V3);
+        let pinned = ProjectScanner::rescan_project(&dir, &settings).unwrap();
+        assert_eq!(pinned.version, ZebrasVersion::V3);
+        assert_eq!(pinned.port, 8201);
+        assert!(pinned.version_candidates.is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_ids_and_user_state_and_reports_changes() {
+        let root = TempDir::new("scanner");
+        for (name, port) in [("order", 8101), ("user", 8102)] {
+            let dir = root.join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("zebra.json"),
+                format!(r#"{{ "name": "{}", "port": {} }}"#, name, port),
+            )
+            .unwrap();
+        }
+        let folders = vec![root.to_string_lossy().to_string()];
+        let settings = WorkspaceSettings::default();
+
+        let mut existing =
+            ProjectScanner::scan_with_cache(&folders, &settings, &mut ScanCache::default()).0;
+        // 旧工作区中保存的随机 ID 和用户设置
+        for project in existing.iter_mut() {
+            project.id = uuid::Uuid::new_v4().to_string();
+            project.enabled = Some(false);
+        }
+
+        std::fs::write(
+            root.join("order").join("zebra.json"),
+            r#"{ "name": "order", "port": 8201 }"#,
+        )
+        .unwrap();
+        std::fs::remove_dir_all(root.join("user")).unwrap();
+        std::fs::create_dir_all(root.join("pay")).unwrap();
+        std::fs::write(root.join("pay").join("zebra.json"), r#"{ "name": "pay" }"#).unwrap();
+
+        let scanned =
+            ProjectScanner::scan_with_cache(&folders, &settings, &mut ScanCache::default()).0;
+        let (merged, report) = ProjectScanner::merge(&existing, scanned);
+
+        let order = merged.iter().find(|p| p.name == "order").unwrap();
+        let old_order = existing.iter().find(|p| p.name == "order").unwrap();
+        assert_eq!(order.id, old_order.id);
+        assert_eq!(order.enabled, Some(false));
+        assert_eq!(order.port, 8201);
+        assert_eq!(report.changed.len(), 1);
+        assert_eq!(report.changed[0].fields, vec!["port".to_string()]);
+
+        let pay = merged.iter().find(|p| p.name == "pay").unwrap();
+        assert_eq!(pay.id, ProjectInfo::stable_id(&root.join("pay")));
+        assert_eq!(report.added[0].name, "pay");
+        assert_eq!(report.removed[0].name, "user");
+    }
+
+    #[test]
+    fn rescan_dirs_only_touches_affected_projects() {
+        let root = TempDir::new("scanner");
+        for name in ["order", "user"] {
+            let dir = root.join("apps").join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("zebra.json"),
+                format!(r#"{{ "name": "{}", "port": 8101 }}"#, name),
+            )
+            .unwrap();
+        }
+        let folders = vec![root.to_string_lossy().to_string()];
+        let settings = WorkspaceSettings::default();
+        let existing = ProjectScanner::scan_directory(&root, &root, &settings);
+        assert_eq!(existing.len(), 2);
+
+        // order 的配置变化、user 被删除、新克隆了 pay，只有 order 和 pay 所在目录触发了事件
+        std::fs::write(
+            root.join("apps/order/zebra.json"),
+            r#"{ "name": "order", "port": 8201 }"#,
+        )
+        .unwrap();
+        std::fs::remove_dir_all(root.join("apps/user")).unwrap();
+        std::fs::create_dir_all(root.join("apps/pay")).unwrap();
+        std::fs::write(root.join("apps/pay/zebra.json"), r#"{ "name": "pay" }"#).unwrap();
+
+        let dirs: HashSet<PathBuf> = [root.join("apps/order"), root.join("apps/pay")]
+            .into_iter()
+            .collect();
+        let (projects, report) = ProjectScanner::rescan_dirs(&existing, &folders, &dirs, &settings);
+        let mut names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["order", "pay", "user"]);
+        assert_eq!(report.changed[0].fields, vec!["port".to_string()]);
+        assert_eq!(report.added[0].name, "pay");
+        assert!(report.removed.is_empty());
+
+        let dirs: HashSet<PathBuf> = [root.join("apps/user")].into_iter().collect();
+        let (projects, report) = ProjectScanner::rescan_dirs(&projects, &folders, &dirs, &settings);
+        assert_eq!(projects.len(), 2);
+        assert_eq!(report.removed[0].name, "user");
+    }
+
+    #[test]
+    fn scan_reuses_cached_projects_until_config_changes() {
+        let root = TempDir::new("scanner");
+        for name in ["order", "user", "pay"] {
+            let dir = root.join("apps").join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("zebra.json"),
+                format!(r#"{{ "name": "{}", "port": 8101 }}"#, name),
+            )
+            .unwrap();
+        }
+        let folders = vec![root.to_string_lossy().to_string()];
+        let settings = WorkspaceSettings::default();
+        let mut cache = ScanCache::default();
+
+        let (projects, stats) = ProjectScanner::scan_with_cache(&folders, &settings, &mut cache);
+        let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["order", "pay", "user"]);
+        assert_eq!(stats.projects, 3);
+        assert_eq!(stats.cache_hits, 0);
+        assert_eq!(stats.dirs_visited, 5);
+
+        std::fs::write(
+            root.join("apps/user/zebra.json"),
+            r#"{ "name": "user", "port": 18101 }"#,
+        )
+        .unwrap();
+        let (projects, stats) = ProjectScanner::scan_with_cache(&folders, &settings, &mut cache);
+        assert_eq!(stats.cache_hits, 2);
+        assert_eq!(projects[2].port, 18101);
+    }
+
+    #[test]
+    fn scan_settings_apply_depth_globs_ignore_files_and_symlinks() {
+        let root = TempDir::new("scanner");
+        for dir in [
+            "apps/order",
+            "apps/legacy",
+            "dist/order",
+            "tmp/user",
+            "libs/deep/a/b/pay",
+        ] {
+            let path = root.join(dir);
+            std::fs::create_dir_all(&path).unwrap();
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            std::fs::write(
+                path.join("zebra.json"),
+                format!(r#"{{ "name": "{}" }}"#, name),
+            )
+            .unwrap();
+        }
+        std::fs::write(root.join(".gitignore"), "tmp/\n").unwrap();
+        // 指向上级目录的链接不会导致无限遍历
+        std::os::unix::fs::symlink(&root, root.join("apps/loop")).unwrap();
+        let external = TempDir::new("scanner");
+        std::fs::create_dir_all(external.join("svc")).unwrap();
+        std::fs::write(external.join("svc/zebra.json"), r#"{ "name": "svc" }"#).unwrap();
+        std::fs::create_dir_all(root.join("links")).unwrap();
+        std::os::unix::fs::symlink(&external, root.join("links/external")).unwrap();
+
+        let scan_names = |settings: &WorkspaceSettings| {
+            let mut names: Vec<String> = ProjectScanner::scan_directory(&root, &root, settings)
+                .into_iter()
+                .map(|p| p.path.strip_prefix(&root).unwrap().display().to_string())
+                .collect();
+            names.sort();
+            names.dedup();
+            names
+        };
+
+        let mut settings = WorkspaceSettings::default();
+        assert_eq!(scan_names(&settings), vec!["apps/legacy", "apps/order"]);
+
+        settings.scan.max_depth = 5;
+        settings.scan.exclude.push("apps/legacy".to_string());
+        settings.scan.respect_ignore_files = false;
+        settings.scan.follow_symlinks = true;
+        assert_eq!(
+            scan_names(&settings),
+            vec![
+                "apps/order",
+                "libs/deep/a/b/pay",
+                "links/external/svc",
+                "tmp/user"
+            ]
+        );
+
+        settings.scan.include = vec!["apps/*".to_string()];
+        assert_eq!(scan_names(&settings), vec!["apps/order"]);
+        assert!(ScanFilter::validate(&settings.scan).is_ok());
+        settings.scan.exclude.push("apps/[".to_string());
+        assert!(ScanFilter::validate(&settings.scan).is_err());
+    }
+
+    #[test]
+    fn zebras_projects_under_generic_roots_are_still_found() {
+        let root = TempDir::new("scanner");
+        // lerna 仓库根目录带 dev 脚本
+        root.write(
+            "mono/package.json",
+            r#"{ "scripts": { "dev": "lerna run dev" }, "devDependencies": { "lerna": "^8" } }"#,
+        );
+        root.write("mono/lerna.json", "{}");
+        root.write("mono/order/zebra.json", r#"{ "name": "order" }"#);
+        // Spring 父 pom 聚合子模块
+        root.write(
+            "java/pom.xml",
+            "<project><parent><artifactId>spring-boot-starter-parent</artifactId></parent><modules><module>user</module></modules></project>",
+        );
+        root.write("java/user/zebra.json", r#"{ "name": "user" }"#);
+        // 普通 Vite 应用中嵌套的 Zebras 项目
+        root.write(
+            "web/package.json",
+            r#"{ "name": "web", "scripts": { "dev": "vite" }, "devDependencies": { "vite": "^5" } }"#,
+        );
+        root.write("web/pay/zebra.json", r#"{ "name": "pay" }"#);
+
+        let folders = vec![root.to_string_lossy().to_string()];
+        let (projects, _) = ProjectScanner::scan_folders(&folders, &WorkspaceSettings::default());
+        let mut names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["order", "pay", "user", "web"]);
+    }
+
     #[test]
     fn test_scan_directory() {
         // 这个测试需要实际的 Zebras 项目目录才能运行
         // 这里只是示例
         let path = PathBuf::from(".");
-        let _projects = ProjectScanner::scan_directory(&path, 2);
+        let _projects = ProjectScanner::scan_directory(&path, &path, &WorkspaceSettings::default());
         // 断言会根据实际情况而定
     }
 }
@@ -0,0 +1,163 @@
+use crate::models::{ProjectInfo, ZebrasVersion};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// 缓存格式版本，ProjectInfo 结构变化时递增，旧缓存直接丢弃
+const CACHE_VERSION: u32 = 2;
+
+/// 多个工作区可能同时扫描，写缓存文件需要串行
+static SAVE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// 配置文件的修改时间和大小，任一变化都视为需要重新解析
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub name: String,
+    /// 修改时间，UNIX 纳秒
+    pub modified: u64,
+    pub size: u64,
+}
+
+impl FileStamp {
+    pub fn read(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_nanos() as u64;
+        Some(Self {
+            name: path.file_name()?.to_string_lossy().to_string(),
+            modified,
+            size: metadata.len(),
+        })
+    }
+}
+
+/// 一个目录的解析结果；project 为 None 表示有配置文件但不是 Zebras 项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedProject {
+    pub files: Vec<FileStamp>,
+    pub env: Option<String>,
+    pub pinned: Option<ZebrasVersion>,
+    pub project: Option<ProjectInfo>,
+}
+
+/// 项目扫描缓存，保存在 ~/.zebras-launcher/cache/projects.json
+/// 以项目目录为键，目录中配置文件的路径、修改时间和大小都没变时复用上次的解析结果
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    version: u32,
+    entries: HashMap<String, CachedProject>,
+}
+
+impl ScanCache {
+    fn get_cache_path() -> Result<PathBuf, String> {
+        let home = dirs_next::home_dir().ok_or("无法获取用户主目录".to_string())?;
+
+        let cache_dir = home.join(".zebras-launcher").join("cache");
+
+        // 确保目录存在
+        fs::create_dir_all(&cache_dir).map_err(|e| format!("创建缓存目录失败: {}", e))?;
+
+        Ok(cache_dir.join("projects.json"))
+    }
+
+    /// 加载缓存；文件不存在、损坏或版本不一致时返回空缓存
+    pub fn load() -> Self {
+        let Ok(content) = Self::get_cache_path().and_then(|path| {
+            fs::read_to_string(path).map_err(|e| format!("读取扫描缓存失败: {}", e))
+        }) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str::<Self>(&content) {
+            Ok(cache) if cache.version == CACHE_VERSION => cache,
+            _ => Self::default(),
+        }
+    }
+
+    pub fn save(&mut self) -> Result<(), String> {
+        let cache_path = Self::get_cache_path()?;
+        self.write_to(&cache_path)
+    }
+
+    /// 先写临时文件再重命名，写到一半中断也不会留下损坏的缓存
+    fn write_to(&mut self, cache_path: &Path) -> Result<(), String> {
+        self.version = CACHE_VERSION;
+        let json =
+            serde_json::to_string(&self).map_err(|e| format!("序列化扫描缓存失败: {}", e))?;
+
+        let _guard = SAVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let temp_file = cache_path.with_extension("json.tmp");
+        fs::write(&temp_file, json).map_err(|e| format!("写入扫描缓存失败: {}", e))?;
+        fs::rename(&temp_file, cache_path).map_err(|e| format!("写入扫描缓存失败: {}", e))?;
+
+        Ok(())
+    }
+
+    /// 配置文件、环境和固定版本都与缓存一致时返回缓存的解析结果
+    pub fn lookup(
+        &self,
+        dir: &Path,
+        files: &[FileStamp],
+        env: Option<&str>,
+        pinned: Option<&ZebrasVersion>,
+    ) -> Option<&Option<ProjectInfo>> {
+        let cached = self.entries.get(dir.to_string_lossy().as_ref())?;
+        (cached.files == files && cached.env.as_deref() == env && cached.pinned.as_ref() == pinned)
+            .then_some(&cached.project)
+    }
+
+    /// 用本次扫描的结果替换 roots 下的所有条目，其余文件夹的条目保留
+    pub fn replace_under(&mut self, roots: &[PathBuf], fresh: HashMap<String, CachedProject>) {
+        self.entries
+            .retain(|dir, _| !roots.iter().any(|root| Path::new(dir).starts_with(root)));
+        self.entries.extend(fresh);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TempDir;
+
+    #[test]
+    fn concurrent_saves_leave_a_complete_cache_file() {
+        let dir = TempDir::new("scan-cache");
+        let cache_path = dir.join("projects.json");
+
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let cache_path = &cache_path;
+                scope.spawn(move || {
+                    let mut cache = ScanCache::default();
+                    let fresh = (0..50)
+                        .map(|n| {
+                            let entry = CachedProject {
+                                files: Vec::new(),
+                                env: Some(format!("env-{}", i)),
+                                pinned: None,
+                                project: None,
+                            };
+                            (format!("/code/{}/{}", i, n), entry)
+                        })
+                        .collect();
+                    cache.replace_under(&[], fresh);
+                    cache.write_to(cache_path).unwrap();
+                });
+            }
+        });
+
+        let content = fs::read_to_string(&cache_path).unwrap();
+        let cache: ScanCache = serde_json::from_str(&content).unwrap();
+        assert_eq!(cache.version, CACHE_VERSION);
+        assert_eq!(cache.entries.len(), 50);
+        assert!(!cache_path.with_extension("json.tmp").exists());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::models::ScanSettings;
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+use std::sync::Arc;
+
+/// 按目录读取的忽略文件，内层目录的规则优先
+const IGNORE_FILES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// 扫描设置中的 include/exclude glob 和 .gitignore/.ignore 规则
+pub struct ScanFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    respect_ignore_files: bool,
+}
+
+impl ScanFilter {
+    /// 无效的 glob 会被跳过，保存设置时应先用 validate 检查
+    pub fn new(settings: &ScanSettings) -> Self {
+        let include = (!settings.include.is_empty()).then(|| Self::build_set(&settings.include));
+        Self {
+            include,
+            exclude: Self::build_set(&settings.exclude),
+            respect_ignore_files: settings.respect_ignore_files,
+        }
+    }
+
+    pub fn validate(settings: &ScanSettings) -> Result<(), String> {
+        if settings.max_depth == 0 {
+            return Err("扫描深度至少为 1".to_string());
+        }
+        for pattern in settings.include.iter().chain(settings.exclude.iter()) {
+            Self::glob(pattern).map_err(|e| format!("无效的 glob '{}': {}", pattern, e))?;
+        }
+        Ok(())
+    }
+
+    /// relative 为相对于工作区文件夹的目录路径
+    pub fn is_excluded(&self, relative: &Path) -> bool {
+        self.exclude.is_match(relative)
+    }
+
+    pub fn is_included(&self, relative: &Path) -> bool {
+        self.include
+            .as_ref()
+            .is_none_or(|include| include.is_match(relative))
+    }
+
+    /// 读取 dir 中的忽略文件；不启用或没有忽略文件时返回 None
+    pub fn ignore_rules(&self, dir: &Path) -> Option<Arc<Gitignore>> {
+        if !self.respect_ignore_files {
+            return None;
+        }
+
+        let mut builder = GitignoreBuilder::new(dir);
+        let mut found = false;
+        for name in IGNORE_FILES {
+            let path = dir.join(name);
+            if path.is_file() {
+                found = true;
+                if let Some(e) = builder.add(&path) {
+                    eprintln!("[ScanFilter] 读取 {} 失败: {}", path.display(), e);
+                }
+            }
+        }
+        if !found {
+            return None;
+        }
+        builder.build().ok().map(Arc::new)
+    }
+
+    /// 工作区文件夹 root 到 dir 之间各级目录（不含 dir 本身）的忽略规则，用于从中间目录开始遍历；
+    /// dir 或其上级目录被排除、被忽略时返回 None
+    pub fn ancestor_rules(&self, root: &Path, dir: &Path) -> Option<Vec<Arc<Gitignore>>> {
+        let relative = dir.strip_prefix(root).ok()?;
+        let mut rules = Vec::new();
+        let mut current = root.to_path_buf();
+        for component in relative.components() {
+            rules.extend(self.ignore_rules(&current));
+            current.push(component);
+            let current_relative = current.strip_prefix(root).unwrap_or(relative);
+            if self.is_excluded(current_relative) || Self::is_ignored(&rules, &current) {
+                return None;
+            }
+        }
+        Some(rules)
+    }
+
+    /// 从内到外查找第一条匹配的规则，`!` 开头的规则可以重新包含目录
+    pub fn is_ignored(rules: &[Arc<Gitignore>], dir: &Path) -> bool {
+        rules
+            .iter()
+            .rev()
+            .map(|rules| rules.matched(dir, true))
+            .find(|matched| !matched.is_none())
+            .is_some_and(|matched| matched.is_ignore())
+    }
+
+    fn build_set(patterns: &[String]) -> GlobSet {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            match Self::glob(pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => eprintln!("[ScanFilter] 跳过无效的 glob '{}': {}", pattern, e),
+            }
+        }
+        builder.build().unwrap_or_else(|_| GlobSet::empty())
+    }
+
+    /// `*` 不跨越路径分隔符，`**` 匹配任意层目录
+    fn glob(pattern: &str) -> Result<Glob, globset::Error> {
+        GlobBuilder::new(pattern).literal_separator(true).build()
+    }
+}
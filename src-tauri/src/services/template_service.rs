@@ -0,0 +1,330 @@
+use crate::models::{ProjectTemplate, ScaffoldRequest, TemplateSource, ZebrasVersion};
+use crate::services::ConfigLayers;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 内置模板：(id, 名称, 版本, [(目标路径, 内容)])
+/// .gitignore 在仓库中保存为 gitignore，避免对模板目录本身生效
+type BundledTemplate = (
+    &'static str,
+    &'static str,
+    ZebrasVersion,
+    &'static [(&'static str, &'static str)],
+);
+
+const BUNDLED_TEMPLATES: [BundledTemplate; 2] = [
+    (
+        "v3-app",
+        "Zebras v3 子应用",
+        ZebrasVersion::V3,
+        &[
+            (
+                "zebras.config.ts",
+                include_str!("../../templates/v3-app/zebras.config.ts"),
+            ),
+            (
+                "package.json",
+                include_str!("../../templates/v3-app/package.json"),
+            ),
+            (
+                "src/index.tsx",
+                include_str!("../../templates/v3-app/src/index.tsx"),
+            ),
+            (
+                ".gitignore",
+                include_str!("../../templates/v3-app/gitignore"),
+            ),
+        ],
+    ),
+    (
+        "v2-sub",
+        "Zebras v2 子应用",
+        ZebrasVersion::V2,
+        &[
+            (
+                "zebra.json",
+                include_str!("../../templates/v2-sub/zebra.json"),
+            ),
+            (
+                "package.json",
+                include_str!("../../templates/v2-sub/package.json"),
+            ),
+            (
+                "src/index.js",
+                include_str!("../../templates/v2-sub/src/index.js"),
+            ),
+            (
+                ".gitignore",
+                include_str!("../../templates/v2-sub/gitignore"),
+            ),
+        ],
+    ),
+];
+
+/// 复制用户模板时跳过的目录
+const SKIPPED_DIRS: [&str; 2] = [".git", "node_modules"];
+
+pub struct TemplateService;
+
+impl TemplateService {
+    /// 用户模板目录 ~/.zebras-launcher/templates，每个子目录是一个模板
+    fn get_user_template_dir() -> Result<PathBuf, String> {
+        let home = dirs_next::home_dir().ok_or("无法获取用户主目录".to_string())?;
+
+        let template_dir = home.join(".zebras-launcher").join("templates");
+
+        // 确保目录存在，方便用户放入自己的模板
+        fs::create_dir_all(&template_dir).map_err(|e| format!("创建模板目录失败: {}", e))?;
+
+        Ok(template_dir)
+    }
+
+    /// 列出内置模板和用户模板；用户模板按其中的配置文件判断版本
+    pub fn list() -> Vec<ProjectTemplate> {
+        let mut templates: Vec<ProjectTemplate> = BUNDLED_TEMPLATES
+            .iter()
+            .map(|(id, name, version, _)| ProjectTemplate {
+                id: id.to_string(),
+                name: name.to_string(),
+                version: version.clone(),
+                source: TemplateSource::Bundled,
+                path: None,
+            })
+            .collect();
+
+        let Ok(entries) = Self::get_user_template_dir()
+            .and_then(|dir| fs::read_dir(dir).map_err(|e| format!("读取模板目录失败: {}", e)))
+        else {
+            return templates;
+        };
+
+        let mut user_templates: Vec<ProjectTemplate> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .filter_map(|path| {
+                let version = [ZebrasVersion::V3, ZebrasVersion::V2]
+                    .into_iter()
+                    .find(|version| ConfigLayers::has_config(&path, version))?;
+                let id = path.file_name()?.to_string_lossy().to_string();
+                Some(ProjectTemplate {
+                    name: id.clone(),
+                    id,
+                    version,
+                    source: TemplateSource::User,
+                    path: Some(path),
+                })
+            })
+            .collect();
+        user_templates.sort_by(|a, b| a.id.cmp(&b.id));
+        templates.extend(user_templates);
+        templates
+    }
+
+    pub fn find(id: &str, source: TemplateSource) -> Result<ProjectTemplate, String> {
+        Self::list()
+            .into_iter()
+            .find(|template| template.id == id && template.source == source)
+            .ok_or_else(|| format!("未找到模板 {}", id))
+    }
+
+    /// 项目名会作为目录名，只允许字母、数字、`-` 和 `_`
+    pub fn validate_name(name: &str) -> Result<(), String> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("无效的项目名: {}，只允许字母、数字、- 和 _", name));
+        }
+        Ok(())
+    }
+
+    /// domain 会写入 zebra.json 和 zebras.config.ts 的字符串中，只允许字母、数字、`-`、`_` 和 `.`
+    pub fn validate_domain(domain: Option<&str>) -> Result<(), String> {
+        let Some(domain) = domain else {
+            return Ok(());
+        };
+        if !domain
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        {
+            return Err(format!(
+                "无效的 domain: {}，只允许字母、数字、-、_ 和 .",
+                domain
+            ));
+        }
+        Ok(())
+    }
+
+    /// 把模板渲染到 request.folder/request.name，返回新项目目录；失败时删除已写入的目录
+    pub fn scaffold(
+        template: &ProjectTemplate,
+        request: &ScaffoldRequest,
+        port: u16,
+    ) -> Result<PathBuf, String> {
+        Self::validate_name(&request.name)?;
+        Self::validate_domain(request.domain.as_deref())?;
+        let target = Path::new(&request.folder).join(&request.name);
+        if target.exists() {
+            return Err(format!("目录已存在: {}", target.display()));
+        }
+
+        Self::render_into(template, request, port, &target).inspect_err(|_| {
+            let _ = fs::remove_dir_all(&target);
+        })?;
+        Ok(target)
+    }
+
+    fn render_into(
+        template: &ProjectTemplate,
+        request: &ScaffoldRequest,
+        port: u16,
+        target: &Path,
+    ) -> Result<(), String> {
+        let port = port.to_string();
+        let values = [
+            ("name", request.name.as_str()),
+            ("domain", request.domain.as_deref().unwrap_or_default()),
+            ("port", port.as_str()),
+        ];
+
+        for (relative, bytes) in Self::template_files(template)? {
+            let path = target.join(Self::render(&relative.to_string_lossy(), &values));
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+            }
+            // 二进制文件原样复制
+            let content = match String::from_utf8(bytes) {
+                Ok(text) => Self::render(&text, &values).into_bytes(),
+                Err(err) => err.into_bytes(),
+            };
+            fs::write(&path, content)
+                .map_err(|e| format!("写入 {} 失败: {}", path.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// 模板中的文件，路径相对于模板根目录
+    fn template_files(template: &ProjectTemplate) -> Result<Vec<(PathBuf, Vec<u8>)>, String> {
+        match template.source {
+            TemplateSource::Bundled => {
+                let (_, _, _, files) = BUNDLED_TEMPLATES
+                    .iter()
+                    .find(|(id, _, _, _)| *id == template.id)
+                    .ok_or_else(|| format!("未找到模板 {}", template.id))?;
+                Ok(files
+                    .iter()
+                    .map(|(path, content)| (PathBuf::from(path), content.as_bytes().to_vec()))
+                    .collect())
+            }
+            TemplateSource::User => {
+                let root = template
+                    .path
+                    .as_ref()
+                    .ok_or_else(|| format!("模板 {} 缺少目录", template.id))?;
+                let mut files = Vec::new();
+                let walker = WalkDir::new(root).into_iter().filter_entry(|entry| {
+                    !(entry.file_type().is_dir()
+                        && SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+                });
+                for entry in walker {
+                    let entry = entry.map_err(|e| format!("读取模板失败: {}", e))?;
+                    if !entry.file_type().is_file() {
+                        continue;
+                    }
+                    let relative = entry
+                        .path()
+                        .strip_prefix(root)
+                        .map_err(|e| e.to_string())?
+                        .to_path_buf();
+                    let bytes = fs::read(entry.path())
+                        .map_err(|e| format!("读取 {} 失败: {}", entry.path().display(), e))?;
+                    files.push((relative, bytes));
+                }
+                Ok(files)
+            }
+        }
+    }
+
+    /// 替换 {{key}} 占位符；值为空的占位符所在行整行删除，例如未填写 domain
+    fn render(text: &str, values: &[(&str, &str)]) -> String {
+        let mut rendered = String::with_capacity(text.len());
+        for line in text.split_inclusive('\n') {
+            let mut line = line.to_string();
+            let mut drop_line = false;
+            for (key, value) in values {
+                let placeholder = format!("{{{{{}}}}}", key);
+                if line.contains(&placeholder) {
+                    drop_line |= value.is_empty();
+                    line = line.replace(&placeholder, value);
+                }
+            }
+            if !drop_line {
+                rendered.push_str(&line);
+            }
+        }
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::config_parser::ConfigParser;
+    use crate::utils::test_dir::TempDir;
+
+    #[test]
+    fn scaffolds_bundled_templates_with_placeholders() {
+        let folder = TempDir::new("template");
+
+        for (id, domain) in [("v3-app", Some("yilu")), ("v2-sub", None)] {
+            let template = TemplateService::find(id, TemplateSource::Bundled).unwrap();
+            let request = ScaffoldRequest {
+                template_id: id.to_string(),
+                source: TemplateSource::Bundled,
+                folder: folder.to_string_lossy().to_string(),
+                name: format!("order-{}", id),
+                domain: domain.map(str::to_string),
+                git_init: false,
+            };
+            let path = TemplateService::scaffold(&template, &request, 8123).unwrap();
+
+            let project = ConfigParser::parse_version(&path, &template.version, None).unwrap();
+            assert_eq!(project.name, request.name);
+            assert_eq!(project.port, 8123);
+            assert_eq!(project.domain.as_deref(), domain);
+            assert!(path.join(".gitignore").exists());
+            assert!(TemplateService::scaffold(&template, &request, 8123).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_domains_that_break_config_strings_and_cleans_up() {
+        let folder = TempDir::new("template");
+        let template = TemplateService::find("v3-app", TemplateSource::Bundled).unwrap();
+        let mut request = ScaffoldRequest {
+            template_id: "v3-app".to_string(),
+            source: TemplateSource::Bundled,
+            folder: folder.to_string_lossy().to_string(),
+            name: "order".to_string(),
+            domain: Some("yilu', port: 1, x: '".to_string()),
+            git_init: false,
+        };
+        assert!(TemplateService::scaffold(&template, &request, 8123).is_err());
+        assert!(!folder.join("order").exists());
+
+        // 模板文件写入失败时不留下半成品目录
+        let broken = ProjectTemplate {
+            id: "missing".to_string(),
+            name: "missing".to_string(),
+            version: ZebrasVersion::V3,
+            source: TemplateSource::User,
+            path: Some(folder.join("no-such-template")),
+        };
+        request.domain = Some("yilu.order".to_string());
+        assert!(TemplateService::scaffold(&broken, &request, 8123).is_err());
+        assert!(!folder.join("order").exists());
+    }
+}
@@ -76,9 +76,9 @@ impl WorkspaceList {
             if old_path.exists() {
                 // 尝试从旧路径加载工作区
                 match WorkspaceService::load_workspace(old_path) {
-                    Ok(workspace) => {
+                    Ok(mut workspace) => {
                         // 保存到新位置
-                        if let Err(e) = WorkspaceService::save_workspace(&workspace) {
+                        if let Err(e) = WorkspaceService::save_workspace(&mut workspace) {
                             eprintln!("迁移工作区 {} 失败: {}", workspace_ref.name, e);
                             continue;
                         }
@@ -1,6 +1,11 @@
 use crate::models::Workspace;
+use crate::services::ProjectScanner;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 串行化工作区文件的读-改-写，文件监听的增量扫描和命令保存不会互相覆盖
+static WORKSPACE_LOCK: Mutex<()> = Mutex::new(());
 
 pub struct WorkspaceService;
 
@@ -35,14 +40,54 @@ impl WorkspaceService {
         serde_json::from_str(&content).map_err(|e| format!("解析工作区配置失败: {}", e))
     }
 
-    /// 保存工作区配置到用户目录
-    pub fn save_workspace(workspace: &Workspace) -> Result<(), String> {
+    /// 保存工作区配置到用户目录。磁盘上的版本比 workspace 新（期间被文件监听等其他写入方保存过）时，
+    /// 项目列表以磁盘为准合并，保留 workspace 中的项目用户设置；保存后 workspace 即写入的内容
+    pub fn save_workspace(workspace: &mut Workspace) -> Result<(), String> {
+        let _guard = WORKSPACE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
         let workspace_file = Self::get_workspace_config_path(&workspace.id)?;
 
+        if let Ok(saved) = Self::load_workspace(&workspace_file) {
+            Self::merge_saved(workspace, saved);
+        }
+        workspace.revision += 1;
+        Self::write(&workspace_file, workspace)
+    }
+
+    /// 加锁读取磁盘上最新的工作区并修改，f 返回 None 时不保存；f 中看到的 revision 已是保存后的值
+    pub fn update<T>(
+        workspace_id: &str,
+        f: impl FnOnce(&mut Workspace) -> Result<Option<T>, String>,
+    ) -> Result<Option<T>, String> {
+        let _guard = WORKSPACE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let workspace_file = Self::get_workspace_config_path(workspace_id)?;
+
+        let mut workspace = Self::load_workspace(&workspace_file)?;
+        workspace.revision += 1;
+        let result = f(&mut workspace)?;
+        if result.is_some() {
+            Self::write(&workspace_file, &workspace)?;
+        }
+        Ok(result)
+    }
+
+    /// saved 比 workspace 新时，项目列表和扫描结果以 saved 为准，项目 ID 和用户设置沿用 workspace
+    fn merge_saved(workspace: &mut Workspace, saved: Workspace) {
+        if saved.revision <= workspace.revision {
+            return;
+        }
+        let (projects, _) = ProjectScanner::merge(&workspace.projects, saved.projects);
+        workspace.projects = projects;
+        workspace.revision = saved.revision;
+    }
+
+    /// 先写临时文件再重命名，读取方不会读到写了一半的文件
+    fn write(workspace_file: &Path, workspace: &Workspace) -> Result<(), String> {
         let json = serde_json::to_string_pretty(workspace)
             .map_err(|e| format!("序列化工作区配置失败: {}", e))?;
 
-        fs::write(&workspace_file, json).map_err(|e| format!("写入工作区文件失败: {}", e))?;
+        let temp_file = workspace_file.with_extension("json.tmp");
+        fs::write(&temp_file, json).map_err(|e| format!("写入工作区文件失败: {}", e))?;
+        fs::rename(&temp_file, workspace_file).map_err(|e| format!("写入工作区文件失败: {}", e))?;
 
         Ok(())
     }
@@ -62,3 +107,41 @@ impl WorkspaceService {
         Self::get_workspace_config_path(workspace_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectInfo;
+
+    #[test]
+    fn saving_a_stale_workspace_keeps_projects_found_by_the_watcher() {
+        let mut saved = Workspace::new("demo".to_string(), PathBuf::from("/work"));
+        saved.projects = vec![ProjectInfo::new(
+            PathBuf::from("/work/order"),
+            "order".to_string(),
+        )];
+        saved.revision = 3;
+
+        // 前端拿到的是第 3 版，之后文件监听扫描到了 pay
+        let mut stale = saved.clone();
+        stale.projects[0].enabled = Some(false);
+        let mut pay = ProjectInfo::new(PathBuf::from("/work/pay"), "pay".to_string());
+        pay.port = 8200;
+        saved.projects.push(pay);
+        saved.revision = 4;
+
+        let mut current = stale.clone();
+        WorkspaceService::merge_saved(&mut current, saved.clone());
+        let names: Vec<&str> = current.projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["order", "pay"]);
+        assert_eq!(current.projects[0].enabled, Some(false));
+        assert_eq!(current.projects[1].port, 8200);
+        assert_eq!(current.revision, 4);
+
+        // 不比磁盘旧时原样保存
+        let mut fresh = saved.clone();
+        fresh.projects.truncate(1);
+        WorkspaceService::merge_saved(&mut fresh, saved);
+        assert_eq!(fresh.projects.len(), 1);
+    }
+}
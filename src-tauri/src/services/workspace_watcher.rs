@@ -0,0 +1,315 @@
+use crate::models::{RescanResult, Workspace, WorkspaceSourceType};
+use crate::services::{
+    ConfigLayers, PortRegistry, ProjectDetectors, ProjectScanner, ScanFilter, WorkspaceService,
+};
+use ignore::gitignore::Gitignore;
+use notify::{recommended_watcher, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+/// 最后一次变化后等待多久再重新扫描，避免 git checkout、npm install 等批量写入时反复扫描
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 监听当前工作区的文件夹，配置文件、构建文件变化或目录增删时只重新扫描受影响的项目，
+/// 保存工作区后发送 workspace_changed 事件
+pub struct WorkspaceWatcher {
+    active: Mutex<Option<Arc<Mutex<RecommendedWatcher>>>>,
+    window: tauri::Window,
+}
+
+impl WorkspaceWatcher {
+    pub fn new(window: tauri::Window) -> Self {
+        Self {
+            active: Mutex::new(None),
+            window,
+        }
+    }
+
+    /// 开始监听工作区，替换之前的监听
+    pub fn watch(&self, workspace: &Workspace) -> Result<(), String> {
+        self.unwatch();
+        if workspace.source_type == WorkspaceSourceType::ManagedProject {
+            return Ok(());
+        }
+
+        let (tx, rx) = channel();
+        let watcher = recommended_watcher(move |result: notify::Result<Event>| {
+            if let Ok(event) = result {
+                let _ = tx.send(event);
+            }
+        })
+        .map_err(|e| format!("创建文件监听失败: {}", e))?;
+        let watcher = Arc::new(Mutex::new(watcher));
+
+        let scope = WatchScope {
+            folders: workspace.folders.clone(),
+            max_depth: workspace.settings.scan.max_depth,
+            filter: ScanFilter::new(&workspace.settings.scan),
+        };
+        {
+            let mut guard = watcher.lock().unwrap_or_else(|e| e.into_inner());
+            for folder in scope.folders.iter() {
+                scope.watch_tree(&mut guard, Path::new(folder));
+            }
+        }
+
+        // 工作线程只持有弱引用：监听被替换或停止后事件通道关闭，线程随之退出
+        let weak = Arc::downgrade(&watcher);
+        let workspace_id = workspace.id.clone();
+        let window = self.window.clone();
+        std::thread::spawn(move || Self::run(rx, weak, workspace_id, scope, window));
+
+        *self.active.lock().unwrap_or_else(|e| e.into_inner()) = Some(watcher);
+        Ok(())
+    }
+
+    pub fn unwatch(&self) {
+        self.active.lock().unwrap_or_else(|e| e.into_inner()).take();
+    }
+
+    fn run(
+        rx: Receiver<Event>,
+        watcher: Weak<Mutex<RecommendedWatcher>>,
+        workspace_id: String,
+        scope: WatchScope,
+        window: tauri::Window,
+    ) {
+        let mut pending: HashSet<PathBuf> = HashSet::new();
+        loop {
+            let event = if pending.is_empty() {
+                rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                rx.recv_timeout(DEBOUNCE)
+            };
+
+            match event {
+                Ok(event) => {
+                    for dir in scope.affected_dirs(&event) {
+                        // 新克隆的仓库：监听其中扫描深度以内的目录
+                        if matches!(event.kind, EventKind::Create(_)) && dir.is_dir() {
+                            if let Some(watcher) = watcher.upgrade() {
+                                let mut guard = watcher.lock().unwrap_or_else(|e| e.into_inner());
+                                scope.watch_tree(&mut guard, &dir);
+                            }
+                        }
+                        pending.insert(dir);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let dirs = std::mem::take(&mut pending);
+                    if let Err(e) = Self::rescan(&workspace_id, &dirs, &window) {
+                        eprintln!("[WorkspaceWatcher] 重新扫描工作区失败: {}", e);
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    }
+
+    /// 在磁盘上最新的工作区上只重新扫描受影响的目录，有变化时保存并通知前端
+    fn rescan(
+        workspace_id: &str,
+        dirs: &HashSet<PathBuf>,
+        window: &tauri::Window,
+    ) -> Result<(), String> {
+        let changed = WorkspaceService::update(workspace_id, |workspace| {
+            let (projects, report) = ProjectScanner::rescan_dirs(
+                &workspace.projects,
+                &workspace.folders,
+                dirs,
+                &workspace.settings,
+            );
+            if report.added.is_empty() && report.removed.is_empty() && report.changed.is_empty() {
+                return Ok(None);
+            }
+            workspace.projects = projects;
+            workspace.last_modified = chrono::Utc::now();
+            Ok(Some((workspace.clone(), report)))
+        })?;
+        let Some((workspace, report)) = changed else {
+            return Ok(());
+        };
+
+        // 释放工作区锁后再更新端口登记表，登记表初始化时会读取工作区
+        if let Err(e) = PortRegistry::update(|registry| {
+            registry.sync_workspace(&workspace);
+            Ok(())
+        }) {
+            eprintln!("[PortRegistry] 同步工作区 '{}' 失败: {}", workspace.name, e);
+        }
+
+        let _ = window.emit("workspace_changed", &RescanResult { workspace, report });
+        Ok(())
+    }
+}
+
+/// 监听范围：工作区文件夹、扫描深度和排除规则，与扫描项目时一致
+struct WatchScope {
+    folders: Vec<String>,
+    max_depth: usize,
+    filter: ScanFilter,
+}
+
+impl WatchScope {
+    /// path 所在的工作区文件夹，在多个文件夹中时取最近的一个
+    fn folder(&self, path: &Path) -> Option<&Path> {
+        self.folders
+            .iter()
+            .map(Path::new)
+            .filter(|folder| path.starts_with(folder))
+            .max_by_key(|folder| folder.components().count())
+    }
+
+    /// 相对所在工作区文件夹的路径
+    fn relative<'a>(&self, path: &'a Path) -> Option<&'a Path> {
+        path.strip_prefix(self.folder(path)?).ok()
+    }
+
+    /// 监听 dir 及扫描深度以内未被排除、未被 .gitignore/.ignore 忽略的子目录，
+    /// 每个目录单独监听，深层目录中的变化不会触发事件
+    fn watch_tree(&self, watcher: &mut RecommendedWatcher, dir: &Path) {
+        let Some(root) = self.folder(dir) else {
+            return;
+        };
+        let Some(rules) = self.filter.ancestor_rules(root, dir) else {
+            return;
+        };
+        let depth = dir
+            .strip_prefix(root)
+            .map_or(0, |relative| relative.components().count());
+        let mut dirs = Vec::new();
+        self.collect_dirs(root, dir, depth, &rules, &mut dirs);
+
+        // Spring Boot 的 application.* 在 src/main/resources 中，通常超出扫描深度
+        let resources: Vec<PathBuf> = dirs
+            .iter()
+            .filter_map(|dir| ProjectDetectors::resources_dir(dir))
+            .filter(|resources| !dirs.contains(resources))
+            .collect();
+        for dir in dirs.iter().chain(resources.iter()) {
+            if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                eprintln!("[WorkspaceWatcher] 监听 {} 失败: {}", dir.display(), e);
+            }
+        }
+    }
+
+    /// 与扫描项目时相同的遍历规则：不进入符号链接，跳过排除和忽略的目录
+    fn collect_dirs(
+        &self,
+        root: &Path,
+        dir: &Path,
+        depth: usize,
+        parent_rules: &[Arc<Gitignore>],
+        dirs: &mut Vec<PathBuf>,
+    ) {
+        dirs.push(dir.to_path_buf());
+        if depth >= self.max_depth {
+            return;
+        }
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+
+        let mut rules = parent_rules.to_vec();
+        rules.extend(self.filter.ignore_rules(dir));
+        let mut children: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+            .map(|entry| entry.path())
+            .filter(|child| {
+                let relative = child.strip_prefix(root).unwrap_or(child);
+                !self.filter.is_excluded(relative) && !ScanFilter::is_ignored(&rules, child)
+            })
+            .collect();
+        children.sort();
+        for child in children {
+            self.collect_dirs(root, &child, depth + 1, &rules, dirs);
+        }
+    }
+
+    /// 事件涉及的目录：配置文件和通用项目构建文件的变化对应其所在的项目目录，
+    /// 扫描深度以内的目录增删（新克隆或删除的仓库）对应目录本身
+    fn affected_dirs(&self, event: &Event) -> Vec<PathBuf> {
+        if matches!(event.kind, EventKind::Access(_)) {
+            return Vec::new();
+        }
+
+        event
+            .paths
+            .iter()
+            .filter_map(|path| {
+                if path
+                    .file_name()
+                    .is_some_and(|name| ConfigLayers::is_config_file_name(&name.to_string_lossy()))
+                {
+                    return path.parent().map(Path::to_path_buf);
+                }
+                if let Some(dir) = ProjectDetectors::project_dir_of(path) {
+                    return Some(dir);
+                }
+                let structural = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_)
+                ) && (path.is_dir() || !path.exists());
+                let relative = self.relative(path)?;
+                let depth = relative.components().count();
+                (structural
+                    && depth > 0
+                    && depth <= self.max_depth
+                    && !self.filter.is_excluded(relative))
+                .then(|| path.clone())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ScanSettings;
+    use crate::utils::test_dir::TempDir;
+
+    #[test]
+    fn watched_dirs_skip_gitignored_and_excluded_dirs() {
+        let root = TempDir::new("watcher");
+        for dir in [
+            "apps/order",
+            "apps/order/build",
+            "node_modules/vite",
+            "tmp/cache",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(".gitignore"), "tmp/\n").unwrap();
+        fs::write(root.join("apps/order/.gitignore"), "build\n").unwrap();
+
+        let settings = ScanSettings::default();
+        let scope = WatchScope {
+            folders: vec![root.to_string_lossy().to_string()],
+            max_depth: settings.max_depth,
+            filter: ScanFilter::new(&settings),
+        };
+        let mut dirs = Vec::new();
+        scope.collect_dirs(&root, &root, 0, &[], &mut dirs);
+        let relative: Vec<PathBuf> = dirs
+            .iter()
+            .map(|dir| dir.strip_prefix(&root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            relative,
+            vec![
+                PathBuf::new(),
+                PathBuf::from("apps"),
+                PathBuf::from("apps/order")
+            ]
+        );
+        assert!(scope
+            .filter
+            .ancestor_rules(&root, &root.join("tmp/cache"))
+            .is_none());
+    }
+}
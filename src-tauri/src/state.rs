@@ -1,5 +1,8 @@
 use crate::models::ProcessInfo;
-use crate::services::{ProcessManager, TerminalManager};
+use crate::services::{
+    AppRegistry, CaptureProxy, DevGateway, HealthMonitor, MockServer, ProcessManager,
+    TerminalManager, WorkspaceWatcher,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -7,7 +10,13 @@ use tokio::sync::Mutex;
 pub struct AppState {
     pub running_processes: Arc<Mutex<HashMap<String, ProcessInfo>>>,
     pub process_manager: ProcessManager,
+    pub health_monitor: HealthMonitor,
+    pub gateway: DevGateway,
+    pub capture: CaptureProxy,
+    pub mock_server: MockServer,
+    pub app_registry: AppRegistry,
     pub terminal_manager: TerminalManager,
+    pub workspace_watcher: WorkspaceWatcher,
 }
 
 impl AppState {
@@ -15,7 +24,13 @@ impl AppState {
         Self {
             running_processes: Arc::new(Mutex::new(HashMap::new())),
             process_manager: ProcessManager::new(window.clone()),
-            terminal_manager: TerminalManager::new(window),
+            health_monitor: HealthMonitor::new(window.clone()),
+            gateway: DevGateway::new(),
+            capture: CaptureProxy::new(),
+            mock_server: MockServer::new(),
+            app_registry: AppRegistry::new(),
+            terminal_manager: TerminalManager::new(window.clone()),
+            workspace_watcher: WorkspaceWatcher::new(window),
         }
     }
 }
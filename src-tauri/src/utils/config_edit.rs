@@ -0,0 +1,895 @@
+use super::js_object::{
+    parse_config_module, parse_value_document, token_spans, JsNode, JsProperty, JsValue, Span,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 被编辑文件的语法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSyntax {
+    /// zebras.config*.ts，编辑默认导出的对象
+    TypeScript,
+    /// zebra*.json，编辑根对象
+    Json,
+}
+
+impl ConfigSyntax {
+    fn default_indent(self) -> &'static str {
+        match self {
+            ConfigSyntax::TypeScript => "    ",
+            ConfigSyntax::Json => "  ",
+        }
+    }
+
+    /// 新建对象时是否在最后一项后加逗号
+    fn trailing_comma(self) -> bool {
+        self == ConfigSyntax::TypeScript
+    }
+}
+
+/// 一次文本替换
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+/// 设置键路径上的值：已存在时只替换值本身，不存在时插入（中间对象按需创建），
+/// 文件中其他内容（注释、缩进、键顺序）保持不变；无法确认结果正确时返回错误，不做修改
+pub fn set_key_path(
+    source: &str,
+    syntax: ConfigSyntax,
+    path: &[&str],
+    value: &Value,
+) -> Result<String, String> {
+    let Some((last, parents)) = path.split_last() else {
+        return Err("键路径为空".to_string());
+    };
+    let root = parse_root(source, syntax)?;
+    let style = Style::detect(source, syntax);
+
+    let mut object = &root;
+    for (depth, key) in parents.iter().enumerate() {
+        match own_property(object, key) {
+            Some(prop) if matches!(prop.value.value, JsValue::Object(_)) => object = &prop.value,
+            // 中间节点不是对象：整体替换为嵌套对象
+            Some(prop) => {
+                let nested = nest(&path[depth + 1..], value);
+                let text = style.render(&nested, &line_indent(source, prop.span.start));
+                let edits = vec![replace_value(source, prop, text)];
+                return apply_checked(source, syntax, path, edits);
+            }
+            None => {
+                let nested = nest(&path[depth + 1..], value);
+                let edits = insert_property(source, &style, object, key, &nested);
+                return apply_checked(source, syntax, path, edits);
+            }
+        }
+    }
+
+    let edits = match own_property(object, last) {
+        Some(prop) => {
+            let text = style.render(value, &line_indent(source, prop.span.start));
+            vec![replace_value(source, prop, text)]
+        }
+        None => insert_property(source, &style, object, last, value),
+    };
+    apply_checked(source, syntax, path, edits)
+}
+
+/// 删除键路径对应的属性（连同它所在的整行和行尾注释），不存在时原样返回；
+/// 无法确认结果正确时返回错误，不做修改
+pub fn remove_key_path(
+    source: &str,
+    syntax: ConfigSyntax,
+    path: &[&str],
+) -> Result<String, String> {
+    let Some((last, parents)) = path.split_last() else {
+        return Err("键路径为空".to_string());
+    };
+    let root = parse_root(source, syntax)?;
+
+    let mut object = &root;
+    for key in parents {
+        match own_property(object, key) {
+            Some(prop) if matches!(prop.value.value, JsValue::Object(_)) => object = &prop.value,
+            _ => return Ok(source.to_string()),
+        }
+    }
+
+    let props = own_properties(object);
+    let Some(index) = props.iter().rposition(|prop| prop.key == *last) else {
+        return Ok(source.to_string());
+    };
+    // 存在同名键时只删除最后一个
+    let remaining = props.iter().filter(|prop| prop.key == *last).count() - 1;
+    let prop = props[index];
+    let previous = index.checked_sub(1).map(|i| props[i]);
+    let mut edits = Vec::new();
+
+    let after = prop.comma.map_or(prop.span.end, |comma| comma + 1);
+    if starts_line(source, prop.span.start) {
+        let line_end = skip_line_rest(source, after);
+        if let Some(newline) = newline_at(source, line_end) {
+            edits.push(Edit {
+                start: line_start(source, prop.span.start),
+                end: line_end + newline.len(),
+                text: String::new(),
+            });
+        } else {
+            edits.push(Edit {
+                start: prop.span.start,
+                end: after,
+                text: String::new(),
+            });
+        }
+        // 最后一项没有逗号时，去掉前一项的逗号保持原有风格（JSON 也不会留下尾随逗号）
+        if prop.comma.is_none() {
+            if let Some(comma) = previous.and_then(|p| p.comma) {
+                edits.push(Edit {
+                    start: comma,
+                    end: comma + 1,
+                    text: String::new(),
+                });
+            }
+        }
+    } else {
+        let (start, end) = match (prop.comma, previous.and_then(|p| p.comma)) {
+            (Some(comma), _) => (prop.span.start, skip_spaces(source, comma + 1)),
+            (None, Some(previous_comma)) => (previous_comma, prop.span.end),
+            (None, None) => (prop.span.start, prop.span.end),
+        };
+        edits.push(Edit {
+            start,
+            end,
+            text: String::new(),
+        });
+    }
+
+    let updated = apply_checked(source, syntax, parents, edits)?;
+    let parent = checked_object(&updated, syntax, parents)?;
+    let left = own_properties(&parent)
+        .iter()
+        .filter(|prop| prop.key == *last)
+        .count();
+    if left != remaining {
+        return Err(format!("删除键 '{}' 的结果不正确，已放弃修改", last));
+    }
+    Ok(updated)
+}
+
+/// 把 key 下的对象同步为给定的字符串映射：删除多余的键，更新或新增其余键，
+/// 映射为空时删除整个 key；对象内被注释掉的条目保持不变
+pub fn sync_string_map(
+    source: &str,
+    syntax: ConfigSyntax,
+    key: &str,
+    entries: &HashMap<String, String>,
+) -> Result<String, String> {
+    if entries.is_empty() {
+        return remove_key_path(source, syntax, &[key]);
+    }
+
+    let root = parse_root(source, syntax)?;
+    let existing: Vec<String> = match own_property(&root, key) {
+        Some(prop) if matches!(prop.value.value, JsValue::Object(_)) => own_properties(&prop.value)
+            .iter()
+            .map(|entry| entry.key.clone())
+            .collect(),
+        _ => {
+            let object = entries
+                .iter()
+                .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                .collect();
+            return set_key_path(source, syntax, &[key], &Value::Object(object));
+        }
+    };
+
+    let mut updated = source.to_string();
+    for stale in existing.iter().filter(|k| !entries.contains_key(*k)) {
+        updated = remove_key_path(&updated, syntax, &[key, stale])?;
+    }
+
+    let mut sorted: Vec<_> = entries.iter().collect();
+    sorted.sort();
+    for (entry, url) in sorted {
+        updated = set_key_path(&updated, syntax, &[key, entry], &Value::String(url.clone()))?;
+    }
+    Ok(updated)
+}
+
+/// 去掉 JSONC 中的注释和尾随逗号，得到严格 JSON，其余内容原样保留；
+/// 独占一行的注释连同该行一起删除
+pub fn to_strict_json(source: &str) -> Result<String, String> {
+    parse_value_document(source).map_err(|e| e.to_string())?;
+
+    let bytes = source.as_bytes();
+    let mut edits = Vec::new();
+    let mut at = 0;
+    while at < bytes.len() {
+        match bytes[at] {
+            quote @ (b'"' | b'\'') => {
+                at += 1;
+                while at < bytes.len() && bytes[at] != quote {
+                    at += if bytes[at] == b'\\' { 2 } else { 1 };
+                }
+                at += 1;
+            }
+            b'/' if matches!(bytes.get(at + 1), Some(b'/' | b'*')) => {
+                let end = comment_end(source, at);
+                edits.push(remove_comment(source, at, end));
+                at = end;
+            }
+            b',' => {
+                if matches!(bytes.get(skip_blank(source, at + 1)), Some(b'}' | b']')) {
+                    edits.push(Edit {
+                        start: at,
+                        end: at + 1,
+                        text: String::new(),
+                    });
+                }
+                at += 1;
+            }
+            _ => at += 1,
+        }
+    }
+
+    let strict = apply(source, edits);
+    serde_json::from_str::<Value>(&strict).map_err(|e| format!("无法转换为严格 JSON: {}", e))?;
+    Ok(strict)
+}
+
+/// 注释结束的位置：行注释到换行符之前，块注释到 `*/` 之后
+fn comment_end(source: &str, start: usize) -> usize {
+    let rest = &source[start..];
+    if rest.starts_with("//") {
+        rest.find(['\r', '\n']).map_or(source.len(), |i| start + i)
+    } else {
+        rest[2..]
+            .find("*/")
+            .map_or(source.len(), |i| start + 2 + i + 2)
+    }
+}
+
+/// 删除注释及其前面的空白；注释独占一行时删除整行
+fn remove_comment(source: &str, start: usize, end: usize) -> Edit {
+    let line_end = source[end..].find('\n').map_or(source.len(), |i| end + i);
+    if starts_line(source, start) && source[end..line_end].trim().is_empty() {
+        return Edit {
+            start: line_start(source, start),
+            end: (line_end + 1).min(source.len()),
+            text: String::new(),
+        };
+    }
+
+    Edit {
+        start: source[..start].trim_end_matches([' ', '\t']).len(),
+        end,
+        text: String::new(),
+    }
+}
+
+/// 跳过空白和注释
+fn skip_blank(source: &str, pos: usize) -> usize {
+    let mut at = pos;
+    loop {
+        at = source[at..]
+            .find(|c: char| !c.is_whitespace())
+            .map_or(source.len(), |i| at + i);
+        if source[at..].starts_with("//") || source[at..].starts_with("/*") {
+            at = comment_end(source, at);
+        } else {
+            return at;
+        }
+    }
+}
+
+fn parse_root(source: &str, syntax: ConfigSyntax) -> Result<JsNode, String> {
+    let root = match syntax {
+        ConfigSyntax::TypeScript => parse_config_module(source),
+        ConfigSyntax::Json => parse_value_document(source),
+    }
+    .map_err(|e| e.to_string())?;
+
+    if !matches!(root.value, JsValue::Object(_)) {
+        return Err("配置的根节点不是对象".to_string());
+    }
+    Ok(root)
+}
+
+/// 对象自身书写的属性（不含从 ...spread 展开进来的）
+fn own_properties(object: &JsNode) -> Vec<&JsProperty> {
+    match &object.value {
+        JsValue::Object(props) => props
+            .iter()
+            .filter(|prop| prop.span.start > object.span.start && prop.span.end < object.span.end)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn own_property<'a>(object: &'a JsNode, key: &str) -> Option<&'a JsProperty> {
+    own_properties(object)
+        .into_iter()
+        .rev()
+        .find(|prop| prop.key == key)
+}
+
+/// 把剩余路径包装成嵌套对象
+fn nest(path: &[&str], value: &Value) -> Value {
+    path.iter().rev().fold(value.clone(), |inner, key| {
+        let mut map = serde_json::Map::new();
+        map.insert(key.to_string(), inner);
+        Value::Object(map)
+    })
+}
+
+fn replace_value(source: &str, prop: &JsProperty, text: String) -> Edit {
+    // 简写属性 { port } 改写为 port: 值
+    if prop.span == prop.key_span {
+        return Edit {
+            start: prop.span.start,
+            end: prop.span.end,
+            text: format!(
+                "{}: {}",
+                &source[prop.key_span.start..prop.key_span.end],
+                text
+            ),
+        };
+    }
+
+    Edit {
+        start: prop.value.span.start,
+        end: prop.value.span.end,
+        text,
+    }
+}
+
+fn insert_property(
+    source: &str,
+    style: &Style,
+    object: &JsNode,
+    key: &str,
+    value: &Value,
+) -> Vec<Edit> {
+    let props = own_properties(object);
+    let object_indent = line_indent(source, object.span.start);
+    let multiline = source[object.span.start..object.span.end].contains('\n');
+
+    let indent = match props.first() {
+        Some(first) if starts_line(source, first.span.start) => {
+            line_indent(source, first.span.start)
+        }
+        _ => format!("{}{}", object_indent, style.unit),
+    };
+    let entry = format!(
+        "{}: {}",
+        style.render_key(key),
+        style.render(value, &indent)
+    );
+
+    let Some(last) = props.last() else {
+        let comma = if style.syntax.trailing_comma() {
+            ","
+        } else {
+            ""
+        };
+        let close = object.span.end - 1;
+        // 放在右大括号所在行之前，排在对象内已有的注释之后
+        if multiline && starts_line(source, close) {
+            let at = line_start(source, close);
+            return vec![Edit {
+                start: at,
+                end: at,
+                text: format!("{}{}{}{}", indent, entry, comma, style.eol),
+            }];
+        }
+        if multiline {
+            return vec![Edit {
+                start: object.span.start + 1,
+                end: object.span.start + 1,
+                text: format!("{}{}{}{}", style.eol, indent, entry, comma),
+            }];
+        }
+        return vec![Edit {
+            start: object.span.start + 1,
+            end: object.span.end - 1,
+            text: format!(" {} ", entry),
+        }];
+    };
+
+    let trailing = last.comma.is_some();
+    let mut edits = Vec::new();
+    if !trailing {
+        edits.push(Edit {
+            start: last.span.end,
+            end: last.span.end,
+            text: ",".to_string(),
+        });
+    }
+
+    let anchor = last.comma.map_or(last.span.end, |comma| comma + 1);
+    let comma = if trailing { "," } else { "" };
+    if multiline && starts_line(source, last.span.start) {
+        // 跳过行尾注释，插入到下一行
+        let at = skip_line_rest(source, anchor);
+        edits.push(Edit {
+            start: at,
+            end: at,
+            text: format!("{}{}{}{}", style.eol, indent, entry, comma),
+        });
+    } else {
+        edits.push(Edit {
+            start: anchor,
+            end: anchor,
+            text: format!(" {}{}", entry, comma),
+        });
+    }
+    edits
+}
+
+/// 应用编辑并核对结果：编辑区间以外的 token 必须与原文逐个一致，路径上的每个键只能出现一次。
+/// 核对失败说明文件中有没能正确识别的写法（例如被当成注释的内容），此时拒绝写入
+fn apply_checked(
+    source: &str,
+    syntax: ConfigSyntax,
+    path: &[&str],
+    edits: Vec<Edit>,
+) -> Result<String, String> {
+    let mut before: Vec<Span> = edits
+        .iter()
+        .map(|edit| Span {
+            start: edit.start,
+            end: edit.end,
+        })
+        .collect();
+    before.sort_by_key(|span| span.start);
+    let mut after = Vec::new();
+    let mut delta: isize = 0;
+    let mut sorted: Vec<&Edit> = edits.iter().collect();
+    sorted.sort_by_key(|edit| edit.start);
+    for edit in sorted {
+        let start = (edit.start as isize + delta) as usize;
+        after.push(Span {
+            start,
+            end: start + edit.text.len(),
+        });
+        delta += edit.text.len() as isize - (edit.end - edit.start) as isize;
+    }
+
+    let updated = apply(source, edits);
+    let untouched = |text: &str, edited: &[Span]| -> Result<Vec<String>, String> {
+        Ok(token_spans(text)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .filter(|token| {
+                // 插入点只影响跨过它的 token
+                !edited.iter().any(|edit| {
+                    token.start < edit.end.max(edit.start + 1)
+                        && edit.start < token.end
+                        && (edit.start < edit.end || token.start < edit.start)
+                })
+            })
+            .map(|token| text[token.start..token.end].to_string())
+            .collect())
+    };
+    if untouched(source, &before)? != untouched(&updated, &after)? {
+        return Err(
+            "修改影响了目标以外的内容，配置文件可能包含无法识别的写法，已放弃修改".to_string(),
+        );
+    }
+
+    checked_object(&updated, syntax, path)?;
+    Ok(updated)
+}
+
+/// 在修改后的文本中沿键路径查找对象，键缺失或重复时返回错误
+fn checked_object(updated: &str, syntax: ConfigSyntax, path: &[&str]) -> Result<JsNode, String> {
+    let mut object = parse_root(updated, syntax)?;
+    for key in path {
+        let matches: Vec<&JsProperty> = own_properties(&object)
+            .into_iter()
+            .filter(|prop| prop.key == *key)
+            .collect();
+        match matches.as_slice() {
+            [prop] => object = prop.value.clone(),
+            [] => return Err(format!("修改后找不到键 '{}'，已放弃修改", key)),
+            _ => {
+                return Err(format!(
+                    "修改后出现重复的键 '{}'，配置文件可能包含无法识别的写法，已放弃修改",
+                    key
+                ))
+            }
+        }
+    }
+    Ok(object)
+}
+
+/// 从后往前应用；位置相同的插入按添加顺序出现在结果中
+fn apply(source: &str, edits: Vec<Edit>) -> String {
+    let mut ordered: Vec<(usize, Edit)> = edits.into_iter().enumerate().collect();
+    ordered.sort_by(|(ia, a), (ib, b)| b.start.cmp(&a.start).then(ib.cmp(ia)));
+
+    let mut result = source.to_string();
+    for (_, edit) in ordered {
+        result.replace_range(edit.start..edit.end, &edit.text);
+    }
+    result
+}
+
+fn line_start(source: &str, pos: usize) -> usize {
+    source[..pos].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// pos 之前同一行只有空白
+fn starts_line(source: &str, pos: usize) -> bool {
+    source[line_start(source, pos)..pos]
+        .chars()
+        .all(|c| c == ' ' || c == '\t')
+}
+
+/// pos 所在行的缩进
+fn line_indent(source: &str, pos: usize) -> String {
+    source[line_start(source, pos)..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+fn skip_spaces(source: &str, pos: usize) -> usize {
+    source[pos..]
+        .find(|c: char| c != ' ' && c != '\t')
+        .map_or(source.len(), |i| pos + i)
+}
+
+/// 跳过同一行剩余的空白和注释，返回行尾换行符的位置；遇到其他内容时返回 pos
+fn skip_line_rest(source: &str, pos: usize) -> usize {
+    let mut at = skip_spaces(source, pos);
+    loop {
+        let rest = &source[at..];
+        if rest.starts_with("//") {
+            return rest.find(['\r', '\n']).map_or(source.len(), |i| at + i);
+        }
+        if rest.starts_with("/*") {
+            match rest.find("*/") {
+                Some(end) if !rest[..end].contains('\n') => {
+                    at = skip_spaces(source, at + end + 2);
+                    continue;
+                }
+                _ => return pos,
+            }
+        }
+        if rest.is_empty() || newline_at(source, at).is_some() {
+            return at;
+        }
+        return pos;
+    }
+}
+
+fn newline_at(source: &str, pos: usize) -> Option<&'static str> {
+    let rest = &source[pos..];
+    if rest.starts_with("\r\n") {
+        Some("\r\n")
+    } else if rest.starts_with('\n') {
+        Some("\n")
+    } else {
+        None
+    }
+}
+
+/// 沿用文件已有的缩进单位和换行符
+struct Style {
+    syntax: ConfigSyntax,
+    unit: String,
+    eol: &'static str,
+}
+
+impl Style {
+    fn detect(source: &str, syntax: ConfigSyntax) -> Self {
+        let unit = source
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .take_while(|c| *c == ' ' || *c == '\t')
+                    .collect::<String>()
+            })
+            .find(|indent| !indent.is_empty())
+            .unwrap_or_else(|| syntax.default_indent().to_string());
+        let eol = if source.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        Self { syntax, unit, eol }
+    }
+
+    fn render_key(&self, key: &str) -> String {
+        let is_identifier = key
+            .chars()
+            .next()
+            .is_some_and(|c| c == '_' || c == '$' || c.is_ascii_alphabetic())
+            && key
+                .chars()
+                .all(|c| c == '_' || c == '$' || c.is_ascii_alphanumeric());
+
+        match self.syntax {
+            ConfigSyntax::TypeScript if is_identifier => key.to_string(),
+            _ => self.render_string(key),
+        }
+    }
+
+    fn render_string(&self, text: &str) -> String {
+        match self.syntax {
+            ConfigSyntax::Json => Value::String(text.to_string()).to_string(),
+            ConfigSyntax::TypeScript => {
+                let mut out = String::from("'");
+                for c in text.chars() {
+                    match c {
+                        '\'' => out.push_str("\\'"),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\r' => out.push_str("\\r"),
+                        c => out.push(c),
+                    }
+                }
+                out.push('\'');
+                out
+            }
+        }
+    }
+
+    /// 渲染值；对象和非标量数组按多行书写，缩进基于所在行
+    fn render(&self, value: &Value, indent: &str) -> String {
+        let inner = format!("{}{}", indent, self.unit);
+        match value {
+            Value::String(s) => self.render_string(s),
+            Value::Object(map) if map.is_empty() => "{}".to_string(),
+            Value::Object(map) => {
+                let entries: Vec<String> = map
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", self.render_key(k), self.render(v, &inner)))
+                    .collect();
+                self.render_block('{', '}', entries, indent)
+            }
+            Value::Array(items) if items.iter().all(|v| !v.is_object() && !v.is_array()) => {
+                let items: Vec<String> = items.iter().map(|v| self.render(v, indent)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            Value::Array(items) => {
+                let entries = items.iter().map(|v| self.render(v, &inner)).collect();
+                self.render_block('[', ']', entries, indent)
+            }
+            other => other.to_string(),
+        }
+    }
+
+    fn render_block(&self, open: char, close: char, entries: Vec<String>, indent: &str) -> String {
+        let separator = format!(",{}{}{}", self.eol, indent, self.unit);
+        let trailing = if self.syntax.trailing_comma() {
+            ","
+        } else {
+            ""
+        };
+        format!(
+            "{open}{eol}{indent}{unit}{body}{trailing}{eol}{indent}{close}",
+            eol = self.eol,
+            unit = self.unit,
+            body = entries.join(&separator),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const TS_CONFIG: &str = concat!(
+        "import { defineConfig } from 'zebras';\n",
+        "\n",
+        "// 本地开发配置\n",
+        "export default defineConfig({\n",
+        "    port: '8000', // 端口\n",
+        "    /* 调试地址 */\n",
+        "    debug: {\n",
+        "        // yilu_office: 'http://localhost:7010',\n",
+        "        yilu_filing: 'http://localhost:8633',\n",
+        "    },\n",
+        "});\n",
+    );
+
+    const JSON_CONFIG: &str = "{\n  \"name\": \"order\",\n  \"port\": 8000,\n  \"debug\": {\n    \"user\": \"http://localhost:8001\"\n  }\n}\n";
+
+    #[test]
+    fn replacing_a_value_keeps_every_other_byte() {
+        let updated = set_key_path(
+            TS_CONFIG,
+            ConfigSyntax::TypeScript,
+            &["port"],
+            &json!("8001"),
+        )
+        .unwrap();
+        assert_eq!(updated, TS_CONFIG.replace("'8000'", "'8001'"));
+
+        let updated = set_key_path(
+            JSON_CONFIG,
+            ConfigSyntax::Json,
+            &["debug", "user"],
+            &json!("http://x"),
+        )
+        .unwrap();
+        assert_eq!(
+            updated,
+            JSON_CONFIG.replace("http://localhost:8001", "http://x")
+        );
+    }
+
+    #[test]
+    fn never_duplicates_a_key_hidden_behind_a_regex() {
+        let source = concat!(
+            "export default defineConfig({\n",
+            "    proxy: { '/api': { target: 'http://x', bypass: /^https?:\\/\\// } },\n",
+            "    port: 8001,\n",
+            "});\n",
+        );
+        let updated =
+            set_key_path(source, ConfigSyntax::TypeScript, &["port"], &json!(8002)).unwrap();
+        assert_eq!(updated, source.replace("8001", "8002"));
+        assert_eq!(updated.matches("port:").count(), 1);
+
+        // 无法完整识别的文件不做修改
+        let source = "export default {\n    test: a \\ b,\n    port: 8001,\n};\n";
+        assert!(set_key_path(source, ConfigSyntax::TypeScript, &["port"], &json!(8002)).is_err());
+    }
+
+    #[test]
+    fn insert_then_remove_round_trips_to_the_original_file() {
+        let cases = [
+            (TS_CONFIG, ConfigSyntax::TypeScript, vec!["domain"]),
+            (TS_CONFIG, ConfigSyntax::TypeScript, vec!["debug", "user"]),
+            (JSON_CONFIG, ConfigSyntax::Json, vec!["domain"]),
+            (JSON_CONFIG, ConfigSyntax::Json, vec!["debug", "order"]),
+        ];
+
+        for (source, syntax, path) in cases {
+            let inserted = set_key_path(source, syntax, &path, &json!("value")).unwrap();
+            assert_ne!(inserted, source);
+            assert_eq!(remove_key_path(&inserted, syntax, &path).unwrap(), source);
+        }
+    }
+
+    #[test]
+    fn inserts_with_the_surrounding_indentation_and_comma_style() {
+        let updated = set_key_path(
+            TS_CONFIG,
+            ConfigSyntax::TypeScript,
+            &["devServer", "proxy"],
+            &json!({ "/api": "http://localhost:8080" }),
+        )
+        .unwrap();
+        assert!(updated.ends_with(
+            "    },\n    devServer: {\n        proxy: {\n            '/api': 'http://localhost:8080',\n        },\n    },\n});\n"
+        ));
+
+        let updated = set_key_path(
+            JSON_CONFIG,
+            ConfigSyntax::Json,
+            &["domain"],
+            &json!("order"),
+        )
+        .unwrap();
+        assert!(updated.ends_with("  },\n  \"domain\": \"order\"\n}\n"));
+        serde_json::from_str::<Value>(&updated).unwrap();
+
+        let updated = set_key_path(
+            "export default {\n};\n",
+            ConfigSyntax::TypeScript,
+            &["port"],
+            &json!(8001),
+        )
+        .unwrap();
+        assert_eq!(updated, "export default {\n    port: 8001,\n};\n");
+    }
+
+    #[test]
+    fn removing_keeps_json_valid_and_drops_trailing_comment() {
+        let updated = remove_key_path(JSON_CONFIG, ConfigSyntax::Json, &["debug"]).unwrap();
+        assert_eq!(updated, "{\n  \"name\": \"order\",\n  \"port\": 8000\n}\n");
+        serde_json::from_str::<Value>(&updated).unwrap();
+
+        let updated = remove_key_path(TS_CONFIG, ConfigSyntax::TypeScript, &["port"]).unwrap();
+        assert_eq!(
+            updated,
+            TS_CONFIG.replace("    port: '8000', // 端口\n", "")
+        );
+
+        let inline = "export default { port: 1, name: 'a' };";
+        assert_eq!(
+            remove_key_path(inline, ConfigSyntax::TypeScript, &["name"]).unwrap(),
+            "export default { port: 1 };"
+        );
+        assert_eq!(
+            remove_key_path(inline, ConfigSyntax::TypeScript, &["missing"]).unwrap(),
+            inline
+        );
+    }
+
+    #[test]
+    fn removing_the_last_property_before_a_trailing_comment() {
+        let source = "export default {\n    port: 8000,\n    name: 'order', // 名称\n    // 以下为本地配置\n};\n";
+        assert_eq!(
+            remove_key_path(source, ConfigSyntax::TypeScript, &["name"]).unwrap(),
+            "export default {\n    port: 8000,\n    // 以下为本地配置\n};\n"
+        );
+
+        let source = "{\n  \"proxy\": {\n    \"/api\": \"http://a\",\n    \"/ws\": \"http://b\" // websocket\n  }\n}\n";
+        let updated = remove_key_path(source, ConfigSyntax::Json, &["proxy", "/ws"]).unwrap();
+        assert_eq!(
+            updated,
+            "{\n  \"proxy\": {\n    \"/api\": \"http://a\"\n  }\n}\n"
+        );
+        let updated = remove_key_path(&updated, ConfigSyntax::Json, &["proxy", "/api"]).unwrap();
+        assert_eq!(updated, "{\n  \"proxy\": {\n  }\n}\n");
+    }
+
+    #[test]
+    fn strict_json_drops_comments_and_trailing_commas() {
+        let jsonc = concat!(
+            "{\r\n",
+            "  // 本地端口\r\n",
+            "  \"port\": 9003, // 覆盖\r\n",
+            "  \"proxy\": \"http://a//b\",\r\n",
+            "  \"debug\": {\r\n",
+            "    \"order\": \"http://localhost:8003\", /* 订单 */\r\n",
+            "  },\r\n",
+            "}\r\n",
+        );
+        assert_eq!(
+            to_strict_json(jsonc).unwrap(),
+            concat!(
+                "{\r\n",
+                "  \"port\": 9003,\r\n",
+                "  \"proxy\": \"http://a//b\",\r\n",
+                "  \"debug\": {\r\n",
+                "    \"order\": \"http://localhost:8003\"\r\n",
+                "  }\r\n",
+                "}\r\n",
+            )
+        );
+    }
+
+    #[test]
+    fn editing_jsonc_keeps_comments_and_trailing_commas() {
+        let jsonc = "{\n  // 端口\n  \"port\": 8000,\n}\n";
+        let updated =
+            set_key_path(jsonc, ConfigSyntax::Json, &["port"], &Value::from(9000)).unwrap();
+        assert_eq!(updated, "{\n  // 端口\n  \"port\": 9000,\n}\n");
+
+        let mut entries = HashMap::new();
+        entries.insert("user".to_string(), "http://localhost:8004".to_string());
+        let updated = sync_string_map(&updated, ConfigSyntax::Json, "debug", &entries).unwrap();
+        assert!(updated.starts_with("{\n  // 端口\n  \"port\": 9000,\n"));
+        assert_eq!(
+            parse_value_document(&updated).unwrap().to_json()["debug"]["user"],
+            json!("http://localhost:8004")
+        );
+    }
+
+    #[test]
+    fn sync_string_map_keeps_commented_entries() {
+        let mut entries = HashMap::new();
+        entries.insert("user".to_string(), "http://localhost:8004".to_string());
+
+        let updated =
+            sync_string_map(TS_CONFIG, ConfigSyntax::TypeScript, "debug", &entries).unwrap();
+        assert!(updated.contains("        // yilu_office: 'http://localhost:7010',\n"));
+        assert!(!updated.contains("yilu_filing"));
+        assert!(updated.contains("        user: 'http://localhost:8004',\n    },"));
+
+        let removed =
+            sync_string_map(&updated, ConfigSyntax::TypeScript, "debug", &HashMap::new()).unwrap();
+        assert!(!removed.contains("debug"));
+        assert!(removed.contains("/* 调试地址 */"));
+    }
+}
@@ -0,0 +1,1322 @@
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::fmt;
+
+/// 源码中的字节区间 [start, end)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsValue {
+    Object(Vec<JsProperty>),
+    Array(Vec<JsNode>),
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Null,
+    /// 无法静态求值的表达式（函数调用、环境变量、运算等），保留原文
+    Expression(String),
+}
+
+/// 带位置信息的值，区间覆盖值在源码中的完整文本（对象包含两侧大括号）
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsNode {
+    pub value: JsValue,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsProperty {
+    pub key: String,
+    pub key_span: Span,
+    pub value: JsNode,
+    /// 从键开始到值结束
+    pub span: Span,
+    /// 属性后逗号的位置
+    pub comma: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsSyntaxError {
+    pub message: String,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for JsSyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "第 {} 行第 {} 列: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for JsSyntaxError {}
+
+impl JsNode {
+    /// 读取对象属性，重复的键以最后一个为准
+    pub fn get(&self, key: &str) -> Option<&JsNode> {
+        self.property(key).map(|prop| &prop.value)
+    }
+
+    pub fn property(&self, key: &str) -> Option<&JsProperty> {
+        match &self.value {
+            JsValue::Object(props) => props.iter().rev().find(|prop| prop.key == key),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            JsValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// 字符串、数字和布尔值转成字符串，其余返回 None
+    pub fn as_scalar_string(&self) -> Option<String> {
+        match &self.value {
+            JsValue::String(s) => Some(s.clone()),
+            JsValue::Number(n) => Some(format_number(*n)),
+            JsValue::Bool(b) => Some(b.to_string()),
+            _ => None,
+        }
+    }
+
+    /// 转成 JSON，无法求值的表达式转为 null
+    pub fn to_json(&self) -> Value {
+        match &self.value {
+            JsValue::Object(props) => {
+                let mut map = Map::new();
+                for prop in props {
+                    map.insert(prop.key.clone(), prop.value.to_json());
+                }
+                Value::Object(map)
+            }
+            JsValue::Array(items) => Value::Array(items.iter().map(JsNode::to_json).collect()),
+            JsValue::String(s) => Value::String(s.clone()),
+            JsValue::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => {
+                Value::Number((*n as i64).into())
+            }
+            JsValue::Number(n) => Number::from_f64(*n).map_or(Value::Null, Value::Number),
+            JsValue::Bool(b) => Value::Bool(*b),
+            JsValue::Null | JsValue::Expression(_) => Value::Null,
+        }
+    }
+}
+
+fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
+    } else {
+        n.to_string()
+    }
+}
+
+/// 把字节偏移转换为行号和列号（均从 1 开始，列按字符计）
+pub fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// 解析配置模块，返回顶层默认导出的对象：
+/// 支持 `export default {...}`、`export default defineConfig({...})`、
+/// `export default config`（引用同文件的 const）以及 `module.exports = {...}`
+pub fn parse_config_module(source: &str) -> Result<JsNode, JsSyntaxError> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser::new(source, &tokens);
+    let export = parser.parse_module()?;
+
+    let node =
+        export.ok_or_else(|| parser.error_at(source.len(), "未找到 export default 导出的配置"))?;
+    if !matches!(node.value, JsValue::Object(_)) {
+        return Err(parser.error_at(node.span.start, "默认导出的不是对象字面量"));
+    }
+    Ok(node)
+}
+
+/// 解析单个值组成的文档（JSON），允许注释和尾随逗号
+pub fn parse_value_document(source: &str) -> Result<JsNode, JsSyntaxError> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser::new(source, &tokens);
+    if parser.peek().is_none() {
+        return Err(parser.error_at(source.len(), "文件为空"));
+    }
+    let node = parser.parse_value()?;
+    if let Some(token) = parser.peek() {
+        return Err(parser.error_at(token.span.start, "值之后存在多余内容"));
+    }
+    Ok(node)
+}
+
+/// 源码中每个 token 的区间，注释和空白不计入；无法完整识别时返回错误
+pub fn token_spans(source: &str) -> Result<Vec<Span>, JsSyntaxError> {
+    Ok(tokenize(source)?.into_iter().map(|t| t.span).collect())
+}
+
+// ---------------------------------------------------------------------------
+// 词法分析
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    String(String),
+    /// 模板字符串；包含 ${} 插值时无法求值
+    Template {
+        cooked: String,
+        interpolated: bool,
+    },
+    Number(f64),
+    /// 正则表达式字面量，保留原文
+    Regex,
+    Punct(char),
+    Spread,
+    Arrow,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    span: Span,
+    /// 与上一个 token 之间有换行，用于判断自动分号
+    newline_before: bool,
+}
+
+fn syntax_error(source: &str, offset: usize, message: &str) -> JsSyntaxError {
+    let (line, column) = line_col(source, offset);
+    JsSyntaxError {
+        message: message.to_string(),
+        offset,
+        line,
+        column,
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, JsSyntaxError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut newline_before = false;
+
+    while pos < bytes.len() {
+        let c = bytes[pos];
+
+        if c == b'\n' {
+            newline_before = true;
+            pos += 1;
+            continue;
+        }
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        // 跳过 BOM 和其他非 ASCII 空白
+        if c >= 0x80 {
+            let ch = source[pos..].chars().next().unwrap_or(' ');
+            if ch.is_whitespace() || ch == '\u{feff}' {
+                pos += ch.len_utf8();
+                continue;
+            }
+        }
+
+        if source[pos..].starts_with("//") {
+            pos = source[pos..].find('\n').map_or(bytes.len(), |i| pos + i);
+            continue;
+        }
+        if source[pos..].starts_with("/*") {
+            let end = source[pos + 2..]
+                .find("*/")
+                .ok_or_else(|| syntax_error(source, pos, "块注释没有结束"))?;
+            if source[pos..pos + 2 + end].contains('\n') {
+                newline_before = true;
+            }
+            pos += 2 + end + 2;
+            continue;
+        }
+
+        let start = pos;
+        let kind = match c {
+            b'\'' | b'"' => {
+                let (value, end) = read_string(source, pos)?;
+                pos = end;
+                TokenKind::String(value)
+            }
+            b'`' => {
+                let (cooked, interpolated, end) = read_template(source, pos)?;
+                pos = end;
+                TokenKind::Template {
+                    cooked,
+                    interpolated,
+                }
+            }
+            b'0'..=b'9' => {
+                let (value, end) = read_number(source, pos)?;
+                pos = end;
+                TokenKind::Number(value)
+            }
+            b'.' if bytes.get(pos + 1).is_some_and(u8::is_ascii_digit) => {
+                let (value, end) = read_number(source, pos)?;
+                pos = end;
+                TokenKind::Number(value)
+            }
+            b'.' if source[pos..].starts_with("...") => {
+                pos += 3;
+                TokenKind::Spread
+            }
+            b'=' if bytes.get(pos + 1) == Some(&b'>') => {
+                pos += 2;
+                TokenKind::Arrow
+            }
+            b'/' if regex_allowed(tokens.last()) => {
+                pos = read_regex(source, pos)?;
+                TokenKind::Regex
+            }
+            _ if is_ident_start(source, pos) => {
+                let end = read_ident(source, pos);
+                let ident = source[pos..end].to_string();
+                pos = end;
+                TokenKind::Ident(ident)
+            }
+            // 字符串和正则之外不会出现反斜杠，出现说明前面的内容没有被正确识别
+            b'\\' => return Err(syntax_error(source, pos, "无法识别的 `\\`")),
+            _ => {
+                let ch = source[pos..].chars().next().unwrap_or(' ');
+                pos += ch.len_utf8();
+                TokenKind::Punct(ch)
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            span: Span { start, end: pos },
+            newline_before,
+        });
+        newline_before = false;
+    }
+
+    Ok(tokens)
+}
+
+/// 按上一个 token 区分正则表达式和除号：值（标识符、字面量、右括号）之后是除号，
+/// 运算符、左括号、逗号以及 return 等关键字之后是正则表达式
+fn regex_allowed(previous: Option<&Token>) -> bool {
+    match previous.map(|token| &token.kind) {
+        None => true,
+        Some(TokenKind::Punct(c)) => !matches!(c, ')' | ']' | '}'),
+        Some(TokenKind::Spread | TokenKind::Arrow) => true,
+        Some(TokenKind::Ident(word)) => matches!(
+            word.as_str(),
+            "return"
+                | "typeof"
+                | "instanceof"
+                | "in"
+                | "of"
+                | "new"
+                | "delete"
+                | "void"
+                | "throw"
+                | "case"
+                | "do"
+                | "else"
+                | "yield"
+                | "await"
+        ),
+        Some(_) => false,
+    }
+}
+
+/// 读取正则表达式字面量（含标志），返回结束位置；字符类 [...] 中的 `/` 不结束正则
+fn read_regex(source: &str, start: usize) -> Result<usize, JsSyntaxError> {
+    let mut pos = start + 1;
+    let mut in_class = false;
+
+    loop {
+        let Some(c) = source[pos..].chars().next() else {
+            return Err(syntax_error(source, start, "正则表达式没有结束"));
+        };
+        match c {
+            '\\' => {
+                pos += 1;
+                match source[pos..].chars().next() {
+                    Some('\n') | None => {
+                        return Err(syntax_error(source, start, "正则表达式没有结束"))
+                    }
+                    Some(escaped) => pos += escaped.len_utf8(),
+                }
+                continue;
+            }
+            '\n' => return Err(syntax_error(source, start, "正则表达式没有结束")),
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => return Ok(read_ident(source, pos + 1)),
+            _ => {}
+        }
+        pos += c.len_utf8();
+    }
+}
+
+fn is_ident_start(source: &str, pos: usize) -> bool {
+    source[pos..]
+        .chars()
+        .next()
+        .is_some_and(|c| c == '_' || c == '$' || c.is_alphabetic())
+}
+
+fn read_ident(source: &str, pos: usize) -> usize {
+    source[pos..]
+        .char_indices()
+        .find(|(_, c)| !(*c == '_' || *c == '$' || c.is_alphanumeric()))
+        .map_or(source.len(), |(i, _)| pos + i)
+}
+
+fn read_number(source: &str, pos: usize) -> Result<(f64, usize), JsSyntaxError> {
+    let bytes = source.as_bytes();
+    let mut end = pos;
+
+    let radix = match (bytes[pos], bytes.get(pos + 1).map(u8::to_ascii_lowercase)) {
+        (b'0', Some(b'x')) => 16,
+        (b'0', Some(b'o')) => 8,
+        (b'0', Some(b'b')) => 2,
+        _ => 10,
+    };
+
+    if radix != 10 {
+        end += 2;
+        while end < bytes.len() && (bytes[end].is_ascii_hexdigit() || bytes[end] == b'_') {
+            end += 1;
+        }
+        let digits: String = source[pos + 2..end].chars().filter(|c| *c != '_').collect();
+        let value = i64::from_str_radix(&digits, radix)
+            .map_err(|_| syntax_error(source, pos, "无效的数字"))?;
+        return Ok((value as f64, end));
+    }
+
+    while end < bytes.len() {
+        let c = bytes[end];
+        let is_exponent_sign =
+            (c == b'+' || c == b'-') && end > pos && matches!(bytes[end - 1], b'e' | b'E');
+        if c.is_ascii_digit()
+            || c == b'.'
+            || c == b'_'
+            || c == b'e'
+            || c == b'E'
+            || is_exponent_sign
+        {
+            end += 1;
+        } else {
+            break;
+        }
+    }
+
+    let digits: String = source[pos..end].chars().filter(|c| *c != '_').collect();
+    let value = digits
+        .parse::<f64>()
+        .map_err(|_| syntax_error(source, pos, "无效的数字"))?;
+    // BigInt 后缀
+    if bytes.get(end) == Some(&b'n') {
+        end += 1;
+    }
+    Ok((value, end))
+}
+
+fn read_escape(source: &str, pos: usize, out: &mut String) -> Result<usize, JsSyntaxError> {
+    let mut chars = source[pos..].chars();
+    let Some(c) = chars.next() else {
+        return Err(syntax_error(source, pos, "字符串没有结束"));
+    };
+
+    let hex = |from: usize, len: usize| -> Result<char, JsSyntaxError> {
+        source
+            .get(from..from + len)
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .and_then(char::from_u32)
+            .ok_or_else(|| syntax_error(source, pos, "无效的转义序列"))
+    };
+
+    let next = pos + c.len_utf8();
+    match c {
+        'n' => out.push('\n'),
+        't' => out.push('\t'),
+        'r' => out.push('\r'),
+        'b' => out.push('\u{8}'),
+        'f' => out.push('\u{c}'),
+        'v' => out.push('\u{b}'),
+        '0' => out.push('\0'),
+        'x' => {
+            out.push(hex(next, 2)?);
+            return Ok(next + 2);
+        }
+        'u' if source[next..].starts_with('{') => {
+            let close = source[next..]
+                .find('}')
+                .ok_or_else(|| syntax_error(source, pos, "无效的转义序列"))?;
+            out.push(hex(next + 1, close - 1)?);
+            return Ok(next + close + 1);
+        }
+        'u' => {
+            out.push(hex(next, 4)?);
+            return Ok(next + 4);
+        }
+        // 行尾续行
+        '\n' => {}
+        '\r' => {
+            if source[next..].starts_with('\n') {
+                return Ok(next + 1);
+            }
+        }
+        other => out.push(other),
+    }
+    Ok(next)
+}
+
+fn read_string(source: &str, start: usize) -> Result<(String, usize), JsSyntaxError> {
+    let quote = source.as_bytes()[start] as char;
+    let mut value = String::new();
+    let mut pos = start + 1;
+
+    loop {
+        let Some(c) = source[pos..].chars().next() else {
+            return Err(syntax_error(source, start, "字符串没有结束"));
+        };
+        match c {
+            '\\' => pos = read_escape(source, pos + 1, &mut value)?,
+            '\n' => return Err(syntax_error(source, start, "字符串没有结束")),
+            c if c == quote => return Ok((value, pos + 1)),
+            c => {
+                value.push(c);
+                pos += c.len_utf8();
+            }
+        }
+    }
+}
+
+fn read_template(source: &str, start: usize) -> Result<(String, bool, usize), JsSyntaxError> {
+    let mut cooked = String::new();
+    let mut interpolated = false;
+    let mut pos = start + 1;
+
+    loop {
+        let Some(c) = source[pos..].chars().next() else {
+            return Err(syntax_error(source, start, "模板字符串没有结束"));
+        };
+        match c {
+            '\\' => pos = read_escape(source, pos + 1, &mut cooked)?,
+            '`' => return Ok((cooked, interpolated, pos + 1)),
+            '$' if source[pos + 1..].starts_with('{') => {
+                interpolated = true;
+                pos = skip_interpolation(source, pos + 2)?;
+            }
+            c => {
+                cooked.push(c);
+                pos += c.len_utf8();
+            }
+        }
+    }
+}
+
+/// 跳过 ${ ... }，返回右大括号之后的位置
+fn skip_interpolation(source: &str, start: usize) -> Result<usize, JsSyntaxError> {
+    let mut depth = 1;
+    let mut pos = start;
+
+    while let Some(c) = source[pos..].chars().next() {
+        match c {
+            '\'' | '"' => {
+                pos = read_string(source, pos)?.1;
+                continue;
+            }
+            '`' => {
+                pos = read_template(source, pos)?.2;
+                continue;
+            }
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(pos + 1);
+                }
+            }
+            _ => {}
+        }
+        pos += c.len_utf8();
+    }
+
+    Err(syntax_error(source, start, "模板字符串插值没有结束"))
+}
+
+// ---------------------------------------------------------------------------
+// 语法分析
+// ---------------------------------------------------------------------------
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: &'a [Token],
+    pos: usize,
+    /// 已解析的顶层 const/let/var 绑定
+    bindings: HashMap<String, JsNode>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str, tokens: &'a [Token]) -> Self {
+        Self {
+            source,
+            tokens,
+            pos: 0,
+            bindings: HashMap::new(),
+        }
+    }
+
+    fn error_at(&self, offset: usize, message: &str) -> JsSyntaxError {
+        syntax_error(self.source, offset, message)
+    }
+
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&'a Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        matches!(self.peek(), Some(Token { kind: TokenKind::Punct(p), .. }) if *p == c)
+    }
+
+    fn is_ident(&self, name: &str) -> bool {
+        self.ident_at(0) == Some(name)
+    }
+
+    fn ident_at(&self, offset: usize) -> Option<&'a str> {
+        match self.peek_at(offset) {
+            Some(Token {
+                kind: TokenKind::Ident(name),
+                ..
+            }) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<&'a Token, JsSyntaxError> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Punct(c) => {
+                self.pos += 1;
+                Ok(token)
+            }
+            Some(token) => Err(self.error_at(token.span.start, &format!("此处应为 `{}`", c))),
+            None => Err(self.error_at(self.source.len(), &format!("缺少 `{}`", c))),
+        }
+    }
+
+    fn last_end(&self) -> usize {
+        self.pos
+            .checked_sub(1)
+            .and_then(|i| self.tokens.get(i))
+            .map_or(0, |t| t.span.end)
+    }
+
+    /// 扫描顶层语句，收集 const 绑定，返回默认导出
+    fn parse_module(&mut self) -> Result<Option<JsNode>, JsSyntaxError> {
+        let mut default_export = None;
+        let mut commonjs_export = None;
+
+        while let Some(token) = self.peek() {
+            match &token.kind {
+                TokenKind::Ident(word) if word == "export" => {
+                    self.pos += 1;
+                    if self.is_ident("default") {
+                        self.pos += 1;
+                        default_export = Some(self.parse_config_value()?);
+                    }
+                }
+                TokenKind::Ident(word) if matches!(word.as_str(), "const" | "let" | "var") => {
+                    self.pos += 1;
+                    self.parse_declarations()?;
+                }
+                TokenKind::Ident(word)
+                    if word == "module"
+                        && self.peek_at(1).map(|t| &t.kind) == Some(&TokenKind::Punct('.'))
+                        && self.ident_at(2) == Some("exports")
+                        && self.peek_at(3).map(|t| &t.kind) == Some(&TokenKind::Punct('=')) =>
+                {
+                    self.pos += 4;
+                    commonjs_export = Some(self.parse_value()?);
+                }
+                TokenKind::Punct('{' | '(' | '[') => self.skip_balanced()?,
+                TokenKind::Punct(c @ ('}' | ')' | ']')) => {
+                    return Err(self.error_at(token.span.start, &format!("多余的 `{}`", c)));
+                }
+                _ => self.pos += 1,
+            }
+        }
+
+        Ok(default_export.or(commonjs_export))
+    }
+
+    /// `const a = ..., b: Type = ...`
+    fn parse_declarations(&mut self) -> Result<(), JsSyntaxError> {
+        loop {
+            let Some(name) = self.ident_at(0) else {
+                // 解构等写法不做处理
+                return Ok(());
+            };
+            self.pos += 1;
+
+            if self.is_punct(':') {
+                self.pos += 1;
+                self.skip_type_annotation()?;
+            }
+            if !self.is_punct('=') {
+                return Ok(());
+            }
+            self.pos += 1;
+
+            let value = self.parse_value()?;
+            self.bindings.insert(name.to_string(), value);
+
+            if self.is_punct(',') {
+                self.pos += 1;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// 跳过类型标注，停在 `=`、`,`、`;` 或换行后的新语句
+    fn skip_type_annotation(&mut self) -> Result<(), JsSyntaxError> {
+        while let Some(token) = self.peek() {
+            match token.kind {
+                TokenKind::Punct('=' | ',' | ';' | ')' | ']' | '}') => return Ok(()),
+                TokenKind::Punct('{' | '(' | '[') => self.skip_balanced()?,
+                _ => self.pos += 1,
+            }
+        }
+        Ok(())
+    }
+
+    /// 跳过一对括号及其中的内容
+    fn skip_balanced(&mut self) -> Result<(), JsSyntaxError> {
+        let open = &self.tokens[self.pos];
+        let mut stack = Vec::new();
+
+        while let Some(token) = self.peek() {
+            self.pos += 1;
+            match token.kind {
+                TokenKind::Punct(c @ ('{' | '(' | '[')) => stack.push(c),
+                TokenKind::Punct(c @ ('}' | ')' | ']')) => {
+                    let expected = match stack.pop() {
+                        Some('{') => '}',
+                        Some('(') => ')',
+                        _ => ']',
+                    };
+                    if c != expected {
+                        return Err(self.error_at(
+                            token.span.start,
+                            &format!("括号不匹配，此处应为 `{}`", expected),
+                        ));
+                    }
+                    if stack.is_empty() {
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Err(self.error_at(open.span.start, "括号没有闭合"))
+    }
+
+    /// 跳过一个无法求值的表达式，停在同层的 `,`、`;`、右括号或换行后的新语句
+    fn skip_expression(&mut self) -> Result<(), JsSyntaxError> {
+        let start = self.pos;
+        while let Some(token) = self.peek() {
+            match token.kind {
+                TokenKind::Punct(',' | ';' | '}' | ')' | ']') => break,
+                TokenKind::Punct('{' | '(' | '[') => self.skip_balanced()?,
+                _ if self.pos > start && self.starts_new_statement(token) => break,
+                _ => self.pos += 1,
+            }
+        }
+        Ok(())
+    }
+
+    fn starts_new_statement(&self, token: &Token) -> bool {
+        token.newline_before
+            && matches!(
+                &token.kind,
+                TokenKind::Ident(word) if matches!(
+                    word.as_str(),
+                    "export" | "const" | "let" | "var" | "import" | "function" | "module" | "type" | "interface"
+                )
+            )
+    }
+
+    /// 值之后紧跟运算符、成员访问、调用等，说明是一个更长的表达式；
+    /// 紧跟标识符或字面量时不算（通常是漏写了逗号，由调用方报错）
+    fn is_continuation(&self) -> bool {
+        match self.peek().map(|t| &t.kind) {
+            Some(TokenKind::Punct(c)) => !matches!(c, ',' | ';' | '}' | ')' | ']' | ':'),
+            Some(TokenKind::Arrow) => true,
+            Some(TokenKind::Ident(word)) => matches!(word.as_str(), "in" | "instanceof"),
+            _ => false,
+        }
+    }
+
+    /// 解析一个值；遇到 `as const`、`satisfies T` 时跳过类型，
+    /// 后面跟运算符等无法求值的内容时整体作为表达式保留
+    fn parse_value(&mut self) -> Result<JsNode, JsSyntaxError> {
+        let start_pos = self.pos;
+        let mut node = self.parse_primary()?;
+
+        if self.is_ident("as") || self.is_ident("satisfies") {
+            self.pos += 1;
+            self.skip_type_annotation()?;
+        }
+
+        if self.is_continuation() {
+            self.pos = start_pos;
+            self.skip_expression()?;
+            node = self.expression_node(start_pos);
+        }
+
+        Ok(node)
+    }
+
+    /// 导出位置的值：`({ mode }) => ({...})` 或 `() => { return {...} }` 形式的箭头函数取返回的对象
+    fn parse_config_value(&mut self) -> Result<JsNode, JsSyntaxError> {
+        let start_pos = self.pos;
+        if self.is_ident("async") {
+            self.pos += 1;
+        }
+        if self.is_punct('(') {
+            self.skip_balanced()?;
+        } else if self.ident_at(0).is_some() {
+            self.pos += 1;
+        }
+        if self.is_punct(':') {
+            // 返回值类型标注
+            while !matches!(
+                self.peek().map(|t| &t.kind),
+                None | Some(TokenKind::Arrow | TokenKind::Punct(',' | ';' | ')'))
+            ) {
+                if self.is_punct('{') || self.is_punct('(') || self.is_punct('[') {
+                    self.skip_balanced()?;
+                } else {
+                    self.pos += 1;
+                }
+            }
+        }
+        if self.peek().map(|t| &t.kind) != Some(&TokenKind::Arrow) {
+            self.pos = start_pos;
+            return self.parse_value();
+        }
+        self.pos += 1;
+
+        if !self.is_punct('{') {
+            return self.parse_value();
+        }
+        let open = self.expect_punct('{')?;
+        let mut returned = None;
+        while let Some(token) = self.peek() {
+            match &token.kind {
+                TokenKind::Punct('}') => {
+                    self.pos += 1;
+                    return returned
+                        .ok_or_else(|| self.error_at(open.span.start, "函数没有返回配置对象"));
+                }
+                TokenKind::Ident(word) if word == "return" && returned.is_none() => {
+                    self.pos += 1;
+                    returned = Some(self.parse_value()?);
+                }
+                TokenKind::Ident(word) if matches!(word.as_str(), "const" | "let" | "var") => {
+                    self.pos += 1;
+                    self.parse_declarations()?;
+                }
+                // 条件分支中的 return 不参与
+                TokenKind::Punct('{' | '(' | '[') => self.skip_balanced()?,
+                _ => self.pos += 1,
+            }
+        }
+        Err(self.error_at(open.span.start, "函数体的大括号没有闭合"))
+    }
+
+    fn expression_node(&self, start_pos: usize) -> JsNode {
+        let start = self.tokens[start_pos].span.start;
+        let end = self.last_end().max(start);
+        JsNode {
+            value: JsValue::Expression(self.source[start..end].to_string()),
+            span: Span { start, end },
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<JsNode, JsSyntaxError> {
+        let Some(token) = self.peek() else {
+            return Err(self.error_at(self.source.len(), "缺少值"));
+        };
+        let start_pos = self.pos;
+        let span = token.span;
+
+        let value = match &token.kind {
+            TokenKind::Punct('{') => return self.parse_object(),
+            TokenKind::Punct('[') => return self.parse_array(),
+            TokenKind::Punct('(') => {
+                self.pos += 1;
+                let mut inner = self.parse_value()?;
+                if self.is_punct(')') {
+                    self.pos += 1;
+                } else {
+                    // 箭头函数参数列表、逗号表达式等
+                    self.pos = start_pos;
+                    self.skip_expression()?;
+                    inner = self.expression_node(start_pos);
+                }
+                return Ok(inner);
+            }
+            TokenKind::String(s) => {
+                self.pos += 1;
+                JsValue::String(s.clone())
+            }
+            TokenKind::Template {
+                cooked,
+                interpolated: false,
+            } => {
+                self.pos += 1;
+                JsValue::String(cooked.clone())
+            }
+            TokenKind::Number(n) => {
+                self.pos += 1;
+                JsValue::Number(*n)
+            }
+            TokenKind::Punct(sign @ ('-' | '+')) => {
+                let negative = *sign == '-';
+                match self.peek_at(1).map(|t| &t.kind) {
+                    Some(TokenKind::Number(n)) => {
+                        self.pos += 2;
+                        JsValue::Number(if negative { -n } else { *n })
+                    }
+                    _ => {
+                        self.skip_expression()?;
+                        return Ok(self.expression_node(start_pos));
+                    }
+                }
+            }
+            TokenKind::Ident(word) => return self.parse_identifier(word),
+            TokenKind::Punct(c @ ('}' | ')' | ']' | ',' | ';')) => {
+                return Err(self.error_at(span.start, &format!("此处应为值，实际为 `{}`", c)));
+            }
+            _ => {
+                self.skip_expression()?;
+                return Ok(self.expression_node(start_pos));
+            }
+        };
+
+        Ok(JsNode {
+            value,
+            span: Span {
+                start: span.start,
+                end: self.last_end(),
+            },
+        })
+    }
+
+    fn parse_identifier(&mut self, word: &str) -> Result<JsNode, JsSyntaxError> {
+        let start_pos = self.pos;
+        let span = self.tokens[self.pos].span;
+        self.pos += 1;
+
+        let literal = match word {
+            "true" => Some(JsValue::Bool(true)),
+            "false" => Some(JsValue::Bool(false)),
+            "null" | "undefined" => Some(JsValue::Null),
+            _ => None,
+        };
+        if let Some(value) = literal {
+            return Ok(JsNode { value, span });
+        }
+
+        // defineConfig({...}) 等包装函数取第一个参数
+        if self.is_punct('(') && word.starts_with("define") {
+            self.pos += 1;
+            if self.is_punct(')') {
+                self.pos += 1;
+                return Ok(self.expression_node(start_pos));
+            }
+            let inner = self.parse_config_value()?;
+            while !self.is_punct(')') {
+                if self.peek().is_none() {
+                    return Err(self.error_at(span.start, "函数调用的括号没有闭合"));
+                }
+                self.pos += 1;
+                self.skip_expression()?;
+            }
+            self.pos += 1;
+            return Ok(inner);
+        }
+
+        // 引用同文件的 const
+        if !self.is_continuation() {
+            if let Some(bound) = self.bindings.get(word) {
+                return Ok(bound.clone());
+            }
+            return Ok(self.expression_node(start_pos));
+        }
+
+        self.pos = start_pos;
+        self.skip_expression()?;
+        Ok(self.expression_node(start_pos))
+    }
+
+    fn parse_object(&mut self) -> Result<JsNode, JsSyntaxError> {
+        let open = self.expect_punct('{')?;
+        let mut properties: Vec<JsProperty> = Vec::new();
+
+        loop {
+            let Some(token) = self.peek() else {
+                return Err(self.error_at(open.span.start, "对象的大括号没有闭合"));
+            };
+
+            if token.kind == TokenKind::Punct('}') {
+                self.pos += 1;
+                break;
+            }
+
+            let mut own = false;
+            if token.kind == TokenKind::Spread {
+                self.pos += 1;
+                let spread = self.parse_value()?;
+                // 展开同文件 const 中的对象
+                if let JsValue::Object(inherited) = spread.value {
+                    properties.extend(inherited);
+                }
+            } else if let Some(property) = self.parse_property()? {
+                properties.push(property);
+                own = true;
+            }
+
+            match self.peek() {
+                Some(Token {
+                    kind: TokenKind::Punct(','),
+                    span,
+                    ..
+                }) => {
+                    if let Some(last) = properties.last_mut().filter(|_| own) {
+                        last.comma = Some(span.start);
+                    }
+                    self.pos += 1;
+                }
+                Some(Token {
+                    kind: TokenKind::Punct('}'),
+                    ..
+                }) => {}
+                Some(token) => {
+                    return Err(self.error_at(token.span.start, "对象属性之间缺少 `,`"));
+                }
+                None => return Err(self.error_at(open.span.start, "对象的大括号没有闭合")),
+            }
+        }
+
+        Ok(JsNode {
+            value: JsValue::Object(properties),
+            span: Span {
+                start: open.span.start,
+                end: self.last_end(),
+            },
+        })
+    }
+
+    /// 解析一个属性；计算属性名和方法等无法静态求值的属性返回 None
+    fn parse_property(&mut self) -> Result<Option<JsProperty>, JsSyntaxError> {
+        let token = &self.tokens[self.pos];
+        let key = match &token.kind {
+            TokenKind::Ident(name) => name.clone(),
+            TokenKind::String(s) => s.clone(),
+            TokenKind::Template {
+                cooked,
+                interpolated: false,
+            } => cooked.clone(),
+            TokenKind::Number(n) => format_number(*n),
+            TokenKind::Punct('[') => {
+                self.skip_expression()?;
+                return Ok(None);
+            }
+            _ => {
+                return Err(self.error_at(token.span.start, "无效的属性名"));
+            }
+        };
+        let key_span = token.span;
+        self.pos += 1;
+
+        if self.is_punct(':') {
+            self.pos += 1;
+            let value = self.parse_value()?;
+            let span = Span {
+                start: key_span.start,
+                end: value.span.end,
+            };
+            return Ok(Some(JsProperty {
+                key,
+                key_span,
+                value,
+                span,
+                comma: None,
+            }));
+        }
+
+        // 简写属性 { port }
+        if self.is_punct(',') || self.is_punct('}') {
+            let value = match self.bindings.get(&key) {
+                Some(bound) => bound.clone(),
+                None => JsNode {
+                    value: JsValue::Expression(key.clone()),
+                    span: key_span,
+                },
+            };
+            return Ok(Some(JsProperty {
+                key,
+                key_span,
+                value,
+                span: key_span,
+                comma: None,
+            }));
+        }
+
+        // 方法、getter/setter 等
+        self.skip_expression()?;
+        Ok(None)
+    }
+
+    fn parse_array(&mut self) -> Result<JsNode, JsSyntaxError> {
+        let open = self.expect_punct('[')?;
+        let mut items = Vec::new();
+
+        loop {
+            match self.peek().map(|t| &t.kind) {
+                None => return Err(self.error_at(open.span.start, "数组的方括号没有闭合")),
+                Some(TokenKind::Punct(']')) => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(TokenKind::Punct(',')) => {
+                    self.pos += 1;
+                    continue;
+                }
+                Some(TokenKind::Spread) => {
+                    self.pos += 1;
+                    let spread = self.parse_value()?;
+                    if let JsValue::Array(inherited) = spread.value {
+                        items.extend(inherited);
+                    }
+                }
+                Some(_) => items.push(self.parse_value()?),
+            }
+
+            match self.peek() {
+                Some(Token {
+                    kind: TokenKind::Punct(',' | ']'),
+                    ..
+                }) => {}
+                Some(token) => {
+                    return Err(self.error_at(token.span.start, "数组元素之间缺少 `,`"));
+                }
+                None => return Err(self.error_at(open.span.start, "数组的方括号没有闭合")),
+            }
+        }
+
+        Ok(JsNode {
+            value: JsValue::Array(items),
+            span: Span {
+                start: open.span.start,
+                end: self.last_end(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_only_top_level_default_export() {
+        let source = r#"
+import { defineConfig } from 'zebras';
+// port: 1234
+const note = "name: 'fake'";
+
+export default defineConfig({
+    name: 'order', /* port: 9999 */
+    'type': `app`,
+    devServer: { port: 3000, proxy: { '/api': { target: 'http://x' } } },
+    port: 8003,
+    externals: ['react', 'react-dom',],
+});
+"#;
+
+        let config = parse_config_module(source).unwrap();
+        assert_eq!(config.get("name").unwrap().as_str(), Some("order"));
+        assert_eq!(config.get("type").unwrap().as_str(), Some("app"));
+        assert_eq!(config.get("port").unwrap().value, JsValue::Number(8003.0));
+        assert_eq!(
+            config
+                .get("devServer")
+                .and_then(|d| d.get("proxy"))
+                .and_then(|p| p.get("/api"))
+                .and_then(|a| a.get("target"))
+                .unwrap()
+                .as_str(),
+            Some("http://x")
+        );
+        assert!(matches!(
+            config.get("externals").unwrap().value,
+            JsValue::Array(ref items) if items.len() == 2
+        ));
+    }
+
+    #[test]
+    fn resolves_const_references_and_spreads() {
+        let source = r#"
+const base = { platform: 'web', port: 8000 };
+const domain = 'order';
+const config: UserConfig = {
+    ...base,
+    domain,
+    port: 8010,
+    publicPath: process.env.PUBLIC_PATH || '/',
+    title: `订单 ${domain}`,
+    setup() { return 1; },
+} satisfies UserConfig;
+
+export default config
+"#;
+
+        let config = parse_config_module(source).unwrap();
+        assert_eq!(config.get("platform").unwrap().as_str(), Some("web"));
+        assert_eq!(config.get("domain").unwrap().as_str(), Some("order"));
+        assert_eq!(
+            config.get("port").unwrap().as_scalar_string().as_deref(),
+            Some("8010")
+        );
+        assert!(matches!(
+            config.get("publicPath").unwrap().value,
+            JsValue::Expression(ref raw) if raw == "process.env.PUBLIC_PATH || '/'"
+        ));
+        assert!(matches!(
+            config.get("title").unwrap().value,
+            JsValue::Expression(_)
+        ));
+        assert!(config.get("setup").is_none());
+    }
+
+    #[test]
+    fn property_spans_point_into_source() {
+        let source = "export default {\n  name: 'order',\n  port: 8003\n}\n";
+        let config = parse_config_module(source).unwrap();
+
+        let name = config.property("name").unwrap();
+        assert_eq!(&source[name.span.start..name.span.end], "name: 'order'");
+        assert_eq!(name.comma, Some(name.span.end));
+        assert_eq!(line_col(source, name.key_span.start), (2, 3));
+
+        let port = config.property("port").unwrap();
+        assert_eq!(port.comma, None);
+        assert_eq!(
+            &source[config.span.start..config.span.end],
+            source.trim_start_matches("export default ").trim_end()
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_position() {
+        let err = parse_config_module("export default {\n  name: 'order\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 9));
+
+        let err = parse_config_module("export default {\n  name: 'a'\n  port: 1\n}").unwrap_err();
+        assert_eq!(err.line, 3);
+
+        assert!(parse_config_module("const a = 1;").is_err());
+    }
+
+    #[test]
+    fn regex_literals_do_not_start_comments_or_strings() {
+        let source = r#"
+export default {
+    port: 8001,
+    test: /^https?:\/\//,
+    quotes: /['"]/g,
+    proxy: {
+        '/api': { target: 'http://x', bypass: /\.(png|svg)$/i },
+    },
+    rules: [{ test: /\.tsx?$/, exclude: [/node_modules/, /[/]vendor/] }],
+    ratio: 4 / 2 / 1,
+    name: 'order',
+};
+"#;
+
+        let config = parse_config_module(source).unwrap();
+        assert_eq!(config.get("name").unwrap().as_str(), Some("order"));
+        assert_eq!(config.get("port").unwrap().value, JsValue::Number(8001.0));
+        assert!(matches!(
+            config.get("test").unwrap().value,
+            JsValue::Expression(ref raw) if raw == r"/^https?:\/\//"
+        ));
+        assert!(matches!(
+            config.get("quotes").unwrap().value,
+            JsValue::Expression(ref raw) if raw == r#"/['"]/g"#
+        ));
+        assert_eq!(
+            config
+                .get("proxy")
+                .and_then(|p| p.get("/api"))
+                .and_then(|a| a.get("target"))
+                .and_then(JsNode::as_str),
+            Some("http://x")
+        );
+        assert!(matches!(
+            config.get("rules").unwrap().value,
+            JsValue::Array(ref rules) if rules.len() == 1
+        ));
+        assert!(matches!(
+            config.get("ratio").unwrap().value,
+            JsValue::Expression(ref raw) if raw == "4 / 2 / 1"
+        ));
+
+        assert!(parse_config_module("export default { test: /abc\n }").is_err());
+    }
+
+    #[test]
+    fn unwraps_arrow_functions_returning_the_config() {
+        let source = r#"
+export default defineConfig(({ mode }) => ({
+    name: 'order',
+    port: mode === 'dev' ? 8001 : 80,
+    devServer: { port: 3000 },
+}));
+"#;
+        let config = parse_config_module(source).unwrap();
+        assert_eq!(config.get("name").unwrap().as_str(), Some("order"));
+        let object = &source[config.span.start..config.span.end];
+        assert!(object.starts_with("{\n    name") && object.ends_with('}'));
+
+        let source = r#"
+export default defineConfig(async (env): Promise<UserConfig> => {
+    const port = 8002;
+    if (env.mode === 'test') {
+        return { name: 'test' };
+    }
+    return { name: 'order', port };
+});
+"#;
+        let config = parse_config_module(source).unwrap();
+        assert_eq!(config.get("name").unwrap().as_str(), Some("order"));
+        assert_eq!(config.get("port").unwrap().value, JsValue::Number(8002.0));
+    }
+}
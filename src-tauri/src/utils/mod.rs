@@ -1,5 +1,11 @@
+pub mod config_edit;
+pub mod hash;
+pub mod js_object;
 pub mod port_checker;
+pub mod port_inspector;
 pub mod process_killer;
+#[cfg(test)]
+pub mod test_dir;
 pub mod ts_parser;
 
 #[cfg(not(target_os = "windows"))]
@@ -1,13 +1,132 @@
-use std::net::TcpListener;
+use crate::models::{AddressCheck, AddressStatus, PortAvailability, PortCheckMode, PortProbe};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream};
+use std::time::Duration;
+
+/// 开发服务器常见的监听地址：IPv4 回环、IPv4 通配、IPv6 回环、IPv6 通配（双栈）
+const BIND_ADDRESSES: [IpAddr; 4] = [
+    IpAddr::V4(Ipv4Addr::LOCALHOST),
+    IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+    IpAddr::V6(Ipv6Addr::LOCALHOST),
+    IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+];
+
+const CONNECT_ADDRESSES: [IpAddr; 2] = [
+    IpAddr::V4(Ipv4Addr::LOCALHOST),
+    IpAddr::V6(Ipv6Addr::LOCALHOST),
+];
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(200);
 
 /// 检查指定端口是否可用
 pub fn is_port_available(port: u16) -> bool {
-    TcpListener::bind(("127.0.0.1", port)).is_ok()
+    check_port(port, PortCheckMode::Bind).available
+}
+
+/// 按指定方式检测端口，返回每个地址的详细结果
+pub fn check_port(port: u16, mode: PortCheckMode) -> PortAvailability {
+    let mut checks = Vec::new();
+
+    if matches!(mode, PortCheckMode::Bind | PortCheckMode::Both) {
+        checks.extend(BIND_ADDRESSES.iter().map(|ip| bind_probe(*ip, port)));
+    }
+
+    if matches!(mode, PortCheckMode::Connect | PortCheckMode::Both) {
+        checks.extend(CONNECT_ADDRESSES.iter().map(|ip| connect_probe(*ip, port)));
+    }
+
+    let available = checks.iter().all(|check| {
+        matches!(
+            check.status,
+            AddressStatus::Available | AddressStatus::Unsupported
+        )
+    });
+
+    PortAvailability {
+        port,
+        available,
+        checks,
+    }
+}
+
+/// 以不带 SO_REUSEADDR 的方式绑定并监听，避免被系统的地址复用规则“放行”
+fn bind_probe(ip: IpAddr, port: u16) -> AddressCheck {
+    let addr = SocketAddr::new(ip, port);
+
+    let result = (|| -> io::Result<()> {
+        let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+        if ip.is_ipv6() {
+            // Node 等开发服务器默认监听双栈的 `::`，这里保持一致
+            socket.set_only_v6(!ip.is_unspecified())?;
+        }
+        socket.bind(&addr.into())?;
+        socket.listen(1)?;
+        Ok(())
+    })();
+
+    let (status, message) = match result {
+        Ok(()) => (AddressStatus::Available, None),
+        Err(err) => (classify_bind_error(&err), Some(err.to_string())),
+    };
+
+    AddressCheck {
+        address: addr.to_string(),
+        probe: PortProbe::Bind,
+        status,
+        message,
+    }
+}
+
+fn classify_bind_error(err: &io::Error) -> AddressStatus {
+    match err.kind() {
+        io::ErrorKind::AddrInUse => AddressStatus::InUse,
+        io::ErrorKind::AddrNotAvailable | io::ErrorKind::Unsupported => AddressStatus::Unsupported,
+        _ if is_address_family_unsupported(err) => AddressStatus::Unsupported,
+        _ => AddressStatus::Error,
+    }
+}
+
+#[cfg(unix)]
+fn is_address_family_unsupported(err: &io::Error) -> bool {
+    const EAFNOSUPPORT_LINUX: i32 = 97;
+    const EAFNOSUPPORT_BSD: i32 = 47;
+    matches!(
+        err.raw_os_error(),
+        Some(EAFNOSUPPORT_LINUX) | Some(EAFNOSUPPORT_BSD)
+    )
+}
+
+#[cfg(windows)]
+fn is_address_family_unsupported(err: &io::Error) -> bool {
+    const WSAEAFNOSUPPORT: i32 = 10047;
+    err.raw_os_error() == Some(WSAEAFNOSUPPORT)
+}
+
+/// 尝试连接：能连上说明已有服务在监听
+fn connect_probe(ip: IpAddr, port: u16) -> AddressCheck {
+    let addr = SocketAddr::new(ip, port);
+
+    let (status, message) = match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+        Ok(_) => (AddressStatus::InUse, None),
+        Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => {
+            (AddressStatus::Available, None)
+        }
+        Err(err) => (AddressStatus::Unsupported, Some(err.to_string())),
+    };
+
+    AddressCheck {
+        address: addr.to_string(),
+        probe: PortProbe::Connect,
+        status,
+        message,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::net::TcpListener;
 
     fn find_next_available_port(start: u16, end: u16) -> Option<u16> {
         for port in start..=end {
@@ -30,4 +149,40 @@ mod tests {
         let result = find_next_available_port(58000, 59000);
         assert!(result.is_some());
     }
+
+    #[test]
+    fn wildcard_listener_is_detected() {
+        let listener = TcpListener::bind(("0.0.0.0", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let report = check_port(port, PortCheckMode::Bind);
+        assert!(!report.available);
+        assert!(report
+            .checks
+            .iter()
+            .any(|c| c.address == format!("0.0.0.0:{}", port) && c.status == AddressStatus::InUse));
+    }
+
+    #[test]
+    fn connect_probe_detects_loopback_listener() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let report = check_port(port, PortCheckMode::Connect);
+        assert!(!report.available);
+        assert_eq!(report.checks.len(), CONNECT_ADDRESSES.len());
+
+        drop(listener);
+        let report = check_port(port, PortCheckMode::Connect);
+        assert!(report.available);
+    }
+
+    #[test]
+    fn both_mode_reports_every_address() {
+        let report = check_port(59998, PortCheckMode::Both);
+        assert_eq!(
+            report.checks.len(),
+            BIND_ADDRESSES.len() + CONNECT_ADDRESSES.len()
+        );
+    }
 }
@@ -0,0 +1,342 @@
+use std::path::PathBuf;
+
+#[cfg(not(target_os = "linux"))]
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+/// 进程的基本信息
+#[derive(Debug, Clone)]
+pub struct ProcessDetails {
+    pub parent_pid: Option<u32>,
+    pub command_line: Option<String>,
+    pub cwd: Option<PathBuf>,
+}
+
+/// /proc/net/tcp* 中的一条 socket 记录
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SocketEntry {
+    local_port: u16,
+    listening: bool,
+    inode: u64,
+}
+
+/// 解析 /proc/net/tcp 或 /proc/net/tcp6 的内容
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_net_tcp(content: &str) -> Vec<SocketEntry> {
+    const TCP_LISTEN: &str = "0A";
+
+    content
+        .lines()
+        .skip(1) // 表头
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+
+            let (_, port_hex) = fields[1].rsplit_once(':')?;
+            let local_port = u16::from_str_radix(port_hex, 16).ok()?;
+            let inode = fields[9].parse::<u64>().ok()?;
+
+            Some(SocketEntry {
+                local_port,
+                listening: fields[3].eq_ignore_ascii_case(TCP_LISTEN),
+                inode,
+            })
+        })
+        .collect()
+}
+
+/// 从 /proc/<pid>/stat 中解析父进程 ID
+#[cfg(any(target_os = "linux", test))]
+fn parse_parent_pid_from_stat(stat: &str) -> Option<u32> {
+    // 进程名可能包含空格或括号，因此从最后一个 ')' 之后开始解析
+    let after_name = &stat[stat.rfind(')')? + 1..];
+    let mut fields = after_name.split_whitespace();
+    let _state = fields.next()?;
+    fields.next()?.parse::<u32>().ok()
+}
+
+/// 查找正在监听指定 TCP 端口的进程 ID
+#[cfg(target_os = "linux")]
+pub fn find_listening_pids(port: u16) -> Vec<u32> {
+    use std::collections::HashSet;
+    use std::fs;
+
+    let mut inodes = HashSet::new();
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(content) = fs::read_to_string(table) {
+            inodes.extend(
+                parse_proc_net_tcp(&content)
+                    .into_iter()
+                    .filter(|entry| entry.listening && entry.local_port == port && entry.inode != 0)
+                    .map(|entry| entry.inode),
+            );
+        }
+    }
+
+    if inodes.is_empty() {
+        return Vec::new();
+    }
+
+    let Ok(proc_entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    let mut pids = Vec::new();
+    for entry in proc_entries.filter_map(|e| e.ok()) {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|s| s.parse::<u32>().ok())
+        else {
+            continue;
+        };
+
+        // 没有权限读取的进程会直接跳过
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        let owns_socket = fds.filter_map(|fd| fd.ok()).any(|fd| {
+            fs::read_link(fd.path())
+                .ok()
+                .and_then(|target| {
+                    let target = target.to_string_lossy().to_string();
+                    target
+                        .strip_prefix("socket:[")
+                        .and_then(|rest| rest.strip_suffix(']'))
+                        .and_then(|inode| inode.parse::<u64>().ok())
+                })
+                .is_some_and(|inode| inodes.contains(&inode))
+        });
+
+        if owns_socket {
+            pids.push(pid);
+        }
+    }
+
+    pids.sort_unstable();
+    pids
+}
+
+/// 查找正在监听指定 TCP 端口的进程 ID（通过 lsof）
+#[cfg(all(not(target_os = "linux"), not(target_os = "windows")))]
+pub fn find_listening_pids(port: u16) -> Vec<u32> {
+    let output = Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-t"])
+        .output();
+
+    let mut pids: Vec<u32> = match output {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.trim().parse::<u32>().ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    pids.sort_unstable();
+    pids.dedup();
+    pids
+}
+
+/// 查找正在监听指定 TCP 端口的进程 ID（通过 netstat）
+#[cfg(target_os = "windows")]
+pub fn find_listening_pids(port: u16) -> Vec<u32> {
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let output = Command::new("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+
+    let mut pids: Vec<u32> = match output {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 5 || fields[3] != "LISTENING" {
+                    return None;
+                }
+                let (_, local_port) = fields[1].rsplit_once(':')?;
+                if local_port.parse::<u16>().ok()? != port {
+                    return None;
+                }
+                fields[4].parse::<u32>().ok()
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    pids.sort_unstable();
+    pids.dedup();
+    pids
+}
+
+/// 读取进程的命令行、工作目录和父进程
+#[cfg(target_os = "linux")]
+pub fn process_details(pid: u32) -> Option<ProcessDetails> {
+    use std::fs;
+
+    let proc_dir = PathBuf::from("/proc").join(pid.to_string());
+    if !proc_dir.exists() {
+        return None;
+    }
+
+    let command_line = fs::read(proc_dir.join("cmdline")).ok().and_then(|raw| {
+        let args: Vec<String> = raw
+            .split(|b| *b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).to_string())
+            .collect();
+        if args.is_empty() {
+            None
+        } else {
+            Some(args.join(" "))
+        }
+    });
+
+    let parent_pid = fs::read_to_string(proc_dir.join("stat"))
+        .ok()
+        .and_then(|stat| parse_parent_pid_from_stat(&stat));
+
+    Some(ProcessDetails {
+        parent_pid,
+        command_line,
+        cwd: fs::read_link(proc_dir.join("cwd")).ok(),
+    })
+}
+
+/// 读取进程的命令行、工作目录和父进程（通过 ps / lsof）
+#[cfg(all(not(target_os = "linux"), not(target_os = "windows")))]
+pub fn process_details(pid: u32) -> Option<ProcessDetails> {
+    let ps_field = |field: &str| -> Option<String> {
+        let output = Command::new("ps")
+            .args(["-o", field, "-p", &pid.to_string()])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if value.is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    };
+
+    let command_line = ps_field("command=");
+    let parent_pid = ps_field("ppid=").and_then(|value| value.parse::<u32>().ok());
+    if command_line.is_none() && parent_pid.is_none() {
+        return None;
+    }
+
+    let cwd = Command::new("lsof")
+        .args(["-a", "-p", &pid.to_string(), "-d", "cwd", "-Fn"])
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .find_map(|line| line.strip_prefix('n').map(PathBuf::from))
+        });
+
+    Some(ProcessDetails {
+        parent_pid,
+        command_line,
+        cwd,
+    })
+}
+
+/// Windows 下暂不读取命令行和工作目录
+#[cfg(target_os = "windows")]
+pub fn process_details(_pid: u32) -> Option<ProcessDetails> {
+    Some(ProcessDetails {
+        parent_pid: None,
+        command_line: None,
+        cwd: None,
+    })
+}
+
+/// 获取进程的祖先链（不含自身，由近及远）
+pub fn process_ancestors(pid: u32) -> Vec<u32> {
+    let mut ancestors = Vec::new();
+    let mut current = pid;
+
+    // 限制深度，避免异常数据导致死循环
+    for _ in 0..32 {
+        let Some(parent) = process_details(current).and_then(|d| d.parent_pid) else {
+            break;
+        };
+        if parent <= 1 || ancestors.contains(&parent) {
+            break;
+        }
+        ancestors.push(parent);
+        current = parent;
+    }
+
+    ancestors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_proc_net_tcp_reads_listening_sockets() {
+        let content = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F40 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 123456 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:D2F0 0100007F:1F40 01 00000000:00000000 00:00000000 00000000  1000        0 654321 1 0000000000000000 20 4 30 10 -1
+";
+
+        let entries = parse_proc_net_tcp(content);
+        assert_eq!(
+            entries,
+            vec![
+                SocketEntry {
+                    local_port: 8000,
+                    listening: true,
+                    inode: 123456,
+                },
+                SocketEntry {
+                    local_port: 54000,
+                    listening: false,
+                    inode: 654321,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_proc_net_tcp_handles_ipv6_addresses() {
+        let content = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:1F41 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 777 1 0000000000000000 100 0 0 10 0
+";
+
+        let entries = parse_proc_net_tcp(content);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].local_port, 8001);
+        assert!(entries[0].listening);
+    }
+
+    #[test]
+    fn parse_parent_pid_handles_spaces_in_process_name() {
+        let stat = "4242 (node dev (vite)) S 4200 4242 4200 0 -1 4194560";
+        assert_eq!(parse_parent_pid_from_stat(stat), Some(4200));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn find_listening_pids_finds_current_process() {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let pids = find_listening_pids(port);
+        assert!(pids.contains(&std::process::id()));
+
+        let details = process_details(std::process::id()).unwrap();
+        assert_eq!(details.cwd, std::env::current_dir().ok());
+    }
+}
@@ -0,0 +1,55 @@
+//! 测试用的临时目录，离开作用域时删除，断言失败导致 panic 时同样会清理
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// 在系统临时目录下创建 zl-<prefix>-<uuid>
+    pub fn new(prefix: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("zl-{}-{}", prefix, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    /// 创建临时目录并写入 files，常用于构造只有几个配置文件的项目
+    pub fn with_files(prefix: &str, files: &[(&str, &str)]) -> Self {
+        let dir = Self::new(prefix);
+        for (path, content) in files {
+            dir.write(path, content);
+        }
+        dir
+    }
+
+    /// 写入相对于临时目录的文件，自动创建上级目录
+    pub fn write(&self, path: &str, content: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.0.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&path, content).unwrap();
+        path
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,10 @@
+import { defineConfig } from 'zebras';
+
+export default defineConfig({
+  name: '{{name}}',
+  platform: 'web',
+  type: 'app',
+  domain: '{{domain}}',
+  port: {{port}},
+  framework: 'react',
+});
@@ -6,6 +6,10 @@ import { WorkspaceManager } from './components/workspace/WorkspaceManager';
 import { ProjectGrid } from './components/workspace/ProjectGrid';
 import { SettingsPanel } from './components/settings/SettingsPanel';
 import { DependencyGraphModal } from './components/workspace/DependencyGraphModal';
+import { CaptureModal } from './components/workspace/CaptureModal';
+import { MockRulesModal } from './components/workspace/MockRulesModal';
+import { NewProjectModal } from './components/workspace/NewProjectModal';
+import { ScanSettingsModal } from './components/workspace/ScanSettingsModal';
 import {
   getWorkspaceList,
   loadWorkspace,
@@ -15,31 +19,57 @@ import {
   stopProject,
   repairProjectInstance,
   rebuildProjectLinks,
+  startGateway,
+  stopGateway,
+  startAppRegistry,
+  stopAppRegistry,
 } from './services/tauri';
-import type { CreateProjectInstanceInput, PortChange, WorkspaceRef, ProcessInfo } from './types';
+import type {
+  CreateProjectInstanceInput,
+  PortChangeBackup,
+  PortChangeReason,
+  WorkspaceRef,
+  ProcessInfo,
+} from './types';
+import type { PortChangePlan } from './hooks/useWorkspace';
+
+const PORT_CHANGE_REASON_LABELS: Record<PortChangeReason, string> = {
+  os_occupied: '端口被其他进程占用',
+  claimed_by_other_workspace: '端口已被其他工作区使用',
+  duplicate_in_workspace: '与工作区内其他项目重复',
+  hashed_preference: '调整为首选端口',
+};
 
 function App() {
   const {
     workspace,
     loading,
     error,
+    rescanReport,
+    setRescanReport,
     selectAndCreateWorkspace,
     createManagedProject,
     rescanProjects,
-    resolveConflicts,
+    planConflicts,
+    applyPortPlan,
+    rollbackPortPlan,
     addFolder,
     removeFolder,
     setError,
     setWorkspace,
   } = useWorkspace();
 
-  const [portChanges, setPortChanges] = useState<PortChange[]>([]);
-  const [showPortChanges, setShowPortChanges] = useState(false);
+  const [portPlan, setPortPlan] = useState<PortChangePlan | null>(null);
+  const [portBackup, setPortBackup] = useState<PortChangeBackup | null>(null);
   const [workspaceList, setWorkspaceList] = useState<WorkspaceRef[]>([]);
   const [loadingList, setLoadingList] = useState(false);
   const [runningProcesses, setRunningProcesses] = useState<Map<string, ProcessInfo>>(new Map());
   const [showSettings, setShowSettings] = useState(false);
   const [showDependencyGraph, setShowDependencyGraph] = useState(false);
+  const [showCapture, setShowCapture] = useState(false);
+  const [showMocks, setShowMocks] = useState(false);
+  const [showNewProject, setShowNewProject] = useState(false);
+  const [showScanSettings, setShowScanSettings] = useState(false);
 
   const { settings, updateSettings, resetSettings } = useAppSettings();
   const { gitStatuses, gitBusyByProjectId, gitDisabledReason, fetchProject, pullProject, refreshProject } = useGitStatus(
@@ -52,6 +82,20 @@ function App() {
     loadWorkspaceList();
   }, []);
 
+  // 按设置启动或停止本地开发网关
+  useEffect(() => {
+    const sync = settings.gatewayEnabled
+      ? startGateway(settings.gatewayPort, settings.gatewayHttpsEnabled ? settings.gatewayHttpsPort : undefined)
+      : stopGateway();
+    sync.catch((err) => setError(`开发网关启动失败: ${String(err)}`));
+  }, [settings.gatewayEnabled, settings.gatewayPort, settings.gatewayHttpsEnabled, settings.gatewayHttpsPort]);
+
+  // 按设置启动或停止子应用注册表
+  useEffect(() => {
+    const sync = settings.registryEnabled ? startAppRegistry(settings.registryPort) : stopAppRegistry();
+    sync.catch((err) => setError(`子应用注册表启动失败: ${String(err)}`));
+  }, [settings.registryEnabled, settings.registryPort]);
+
   const loadWorkspaceList = async () => {
     try {
       const list = await getWorkspaceList();
@@ -114,15 +158,34 @@ function App() {
   };
 
   const handleResolveConflicts = async () => {
-    const changes = await resolveConflicts();
-    if (changes.length > 0) {
-      setPortChanges(changes);
-      setShowPortChanges(true);
+    const plan = await planConflicts();
+    if (!plan) return;
+    setPortBackup(null);
+    if (plan.changes.length > 0) {
+      setPortPlan(plan);
     } else {
+      setPortPlan(null);
       alert('未发现端口冲突！');
     }
   };
 
+  const handleApplyPortPlan = async () => {
+    if (!portPlan) return;
+    const backup = await applyPortPlan(portPlan);
+    if (backup) {
+      setPortPlan(null);
+      setPortBackup(backup);
+    }
+  };
+
+  const handleRollbackPortChanges = async () => {
+    if (!portBackup) return;
+    if (await rollbackPortPlan(portBackup.id)) {
+      setPortBackup(null);
+      alert('已恢复调整前的端口配置。');
+    }
+  };
+
   const handleStartAll = async () => {
     if (!workspace) return;
     const runnableProjects = workspace.projects.filter(
@@ -204,7 +267,7 @@ function App() {
     }
 
     const startResults = await Promise.allSettled(
-      running.map(({ project }) => startProject(project.id, project.name, project.path))
+      running.map(({ project }) => startProject(project, workspace.id))
     );
 
     const started: ProcessInfo[] = [];
@@ -362,6 +425,24 @@ function App() {
             </div>
           )}
 
+          {workspace && workspace.source_type !== 'managed_project' && (
+            <button className="btn btn-secondary" onClick={() => setShowNewProject(true)}>
+              新建项目
+            </button>
+          )}
+
+          {workspace && (
+            <button className="btn btn-secondary" onClick={() => setShowCapture(true)}>
+              抓包
+            </button>
+          )}
+
+          {workspace && (
+            <button className="btn btn-secondary" onClick={() => setShowMocks(true)}>
+              Mock
+            </button>
+          )}
+
           <button className="btn btn-secondary" onClick={() => setShowSettings(true)}>
             设置
           </button>
@@ -414,8 +495,39 @@ function App() {
         </div>
       )}
 
+      {/* 端口调整方案预览 */}
+      {portPlan && (
+        <div className="card mb-lg"
+             style={{ backgroundColor: 'rgba(245, 158, 11, 0.1)', borderColor: 'rgba(245, 158, 11, 0.2)' }}>
+          <div className="flex justify-between items-start mb-sm">
+            <div>
+              <strong className="text-warning">发现端口冲突</strong>
+              <p className="mt-sm text-warning text-sm">
+                建议按以下方案调整，确认后才会写入本地配置文件：
+              </p>
+            </div>
+          </div>
+          <ul className="pl-lg text-warning">
+            {portPlan.changes.map((change, idx) => (
+              <li key={idx} className="mb-xs">
+                <strong>{change.project_name}</strong>: {change.old_port} → {change.new_port}
+                <span className="text-sm">（{PORT_CHANGE_REASON_LABELS[change.reason]}）</span>
+              </li>
+            ))}
+          </ul>
+          <div className="flex gap-sm mt-sm">
+            <button onClick={handleApplyPortPlan} className="btn-primary" disabled={loading}>
+              应用
+            </button>
+            <button onClick={() => setPortPlan(null)} className="btn-ghost">
+              取消
+            </button>
+          </div>
+        </div>
+      )}
+
       {/* 端口变更提示 */}
-      {showPortChanges && portChanges.length > 0 && (
+      {portBackup && portBackup.changes.length > 0 && (
         <div className="card mb-lg"
              style={{ backgroundColor: 'rgba(16, 185, 129, 0.1)', borderColor: 'rgba(16, 185, 129, 0.2)' }}>
           <div className="flex justify-between items-start mb-sm">
@@ -426,7 +538,7 @@ function App() {
               </p>
             </div>
             <button
-              onClick={() => setShowPortChanges(false)}
+              onClick={() => setPortBackup(null)}
               className="btn-ghost text-success"
               style={{ fontSize: '1.25rem', padding: '0.25rem 0.5rem' }}
             >
@@ -434,12 +546,57 @@ function App() {
             </button>
           </div>
           <ul className="pl-lg text-success">
-            {portChanges.map((change, idx) => (
+            {portBackup.changes.map((change, idx) => (
               <li key={idx} className="mb-xs">
                 <strong>{change.project_name}</strong>: {change.old_port} → {change.new_port}
               </li>
             ))}
           </ul>
+          <button onClick={handleRollbackPortChanges} className="btn-ghost text-success mt-sm" disabled={loading}>
+            撤销
+          </button>
+        </div>
+      )}
+
+      {/* 重新扫描结果 */}
+      {rescanReport && (
+        <div className="card mb-lg">
+          <div className="flex justify-between items-start mb-sm">
+            <strong>
+              重新扫描完成：新增 {rescanReport.added.length}，移除 {rescanReport.removed.length}，配置变化{' '}
+              {rescanReport.changed.length}
+            </strong>
+            {rescanReport.stats && rescanReport.stats.dirs_visited > 0 && (
+              <span className="text-sm text-secondary">
+                扫描 {rescanReport.stats.dirs_visited} 个目录，缓存命中 {rescanReport.stats.cache_hits}/
+                {rescanReport.stats.projects}，耗时 {rescanReport.stats.elapsed_ms}ms
+              </span>
+            )}
+            <button
+              onClick={() => setRescanReport(null)}
+              className="btn-ghost"
+              style={{ fontSize: '1.25rem', padding: '0.25rem 0.5rem' }}
+            >
+              ×
+            </button>
+          </div>
+          <ul className="pl-lg text-sm">
+            {rescanReport.added.map((entry) => (
+              <li key={`added-${entry.id}`} className="mb-xs text-success" title={entry.path}>
+                + {entry.name}
+              </li>
+            ))}
+            {rescanReport.removed.map((entry) => (
+              <li key={`removed-${entry.id}`} className="mb-xs text-danger" title={entry.path}>
+                - {entry.name}
+              </li>
+            ))}
+            {rescanReport.changed.map((entry) => (
+              <li key={`changed-${entry.id}`} className="mb-xs text-warning" title={entry.path}>
+                ~ {entry.name}: {entry.fields?.join(', ')}
+              </li>
+            ))}
+          </ul>
         </div>
       )}
 
@@ -454,6 +611,7 @@ function App() {
         onOpenDependencyGraph={() => setShowDependencyGraph(true)}
         onAddFolder={addFolder}
         onRemoveFolder={removeFolder}
+        onOpenScanSettings={() => setShowScanSettings(true)}
         onStartAll={handleStartAll}
         onStopAll={handleStopAll}
         onRepairManagedProject={handleRepairManagedProject}
@@ -466,12 +624,48 @@ function App() {
         <DependencyGraphModal
           projects={workspace.projects}
           runningProcesses={runningProcesses}
+          strictJson={workspace.settings.strict_json}
           onClose={() => setShowDependencyGraph(false)}
           onDebugConfigSaved={handleDebugConfigChange}
           onRestartRunningProjects={restartRunningProjects}
         />
       )}
 
+      {showCapture && workspace && (
+        <CaptureModal
+          workspace={workspace}
+          onWorkspaceUpdated={handleWorkspaceUpdate}
+          onClose={() => setShowCapture(false)}
+        />
+      )}
+
+      {showNewProject && workspace && (
+        <NewProjectModal
+          workspace={workspace}
+          onWorkspaceUpdated={handleWorkspaceUpdate}
+          onClose={() => setShowNewProject(false)}
+        />
+      )}
+
+      {showScanSettings && workspace && (
+        <ScanSettingsModal
+          workspace={workspace}
+          onSaved={(result) => {
+            handleWorkspaceUpdate(result.workspace);
+            setRescanReport(result.report);
+          }}
+          onClose={() => setShowScanSettings(false)}
+        />
+      )}
+
+      {showMocks && workspace && (
+        <MockRulesModal
+          workspace={workspace}
+          onWorkspaceUpdated={handleWorkspaceUpdate}
+          onClose={() => setShowMocks(false)}
+        />
+      )}
+
       {/* 项目网格 */}
       {workspace && (
         <ProjectGrid
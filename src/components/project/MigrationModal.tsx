@@ -0,0 +1,118 @@
+import { useEffect, useState } from 'react';
+import type { MigrationChangeKind, MigrationPlan, ProjectInfo, Workspace } from '../../types';
+import { applyV2Migration, previewV2Migration } from '../../services/tauri';
+
+const KIND_LABELS: Record<MigrationChangeKind, string> = {
+  create: '新建',
+  modify: '修改',
+  delete: '删除',
+};
+
+function diffLineColor(line: string) {
+  if (line.startsWith('+')) return 'var(--color-success)';
+  if (line.startsWith('-')) return 'var(--color-danger)';
+  return 'var(--color-text-muted)';
+}
+
+interface MigrationModalProps {
+  project: ProjectInfo;
+  workspace: Workspace;
+  onWorkspaceUpdate: (workspace: Workspace) => void;
+  onClose: () => void;
+}
+
+export function MigrationModal({ project, workspace, onWorkspaceUpdate, onClose }: MigrationModalProps) {
+  const [plan, setPlan] = useState<MigrationPlan | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [applying, setApplying] = useState(false);
+
+  useEffect(() => {
+    setError(null);
+    previewV2Migration(project.path)
+      .then(setPlan)
+      .catch((err) => setError(String(err)));
+  }, [project.path]);
+
+  const handleApply = async () => {
+    if (!plan) return;
+    setApplying(true);
+    try {
+      onWorkspaceUpdate(await applyV2Migration(workspace, project.id, plan));
+      onClose();
+    } catch (err) {
+      setError(`迁移失败: ${String(err)}`);
+    } finally {
+      setApplying(false);
+    }
+  };
+
+  return (
+    <div
+      role="dialog"
+      aria-modal="true"
+      style={{
+        position: 'fixed',
+        inset: 0,
+        backgroundColor: 'rgba(0,0,0,0.5)',
+        display: 'flex',
+        alignItems: 'center',
+        justifyContent: 'center',
+        zIndex: 1001,
+        padding: '24px',
+      }}
+      onMouseDown={(e) => {
+        if (e.target === e.currentTarget && !applying) onClose();
+      }}
+    >
+      <div
+        className="card"
+        style={{ width: '760px', maxWidth: '100%', height: '80vh', display: 'flex', flexDirection: 'column', gap: '12px' }}
+      >
+        <div className="flex justify-between items-center">
+          <div style={{ display: 'flex', flexDirection: 'column', gap: 4 }}>
+            <h2 className="m-0" style={{ fontSize: '1.1rem' }}>{project.name} 迁移到 Zebras v3</h2>
+            <div className="text-xs text-muted">
+              确认以下改动后才会写入文件：main→base、sub→app、component→lib，debug 写入 zebras.config.local.ts。
+            </div>
+          </div>
+          <div className="flex gap-sm items-center">
+            <button className="btn btn-primary" onClick={handleApply} disabled={!plan || applying}>
+              {applying ? '迁移中...' : '确认迁移'}
+            </button>
+            <button className="btn btn-secondary" onClick={onClose} disabled={applying}>取消</button>
+          </div>
+        </div>
+
+        {error && <div className="text-xs text-danger">{error}</div>}
+
+        {plan && plan.notes.length > 0 && (
+          <div className="text-xs" style={{ color: 'var(--color-warning)' }}>
+            {plan.notes.map((note) => (
+              <div key={note}>⚠️ {note}</div>
+            ))}
+          </div>
+        )}
+
+        {plan && (
+          <div style={{ flex: 1, overflow: 'auto', display: 'flex', flexDirection: 'column', gap: '12px' }}>
+            {plan.changes.map((change) => (
+              <div key={change.path} style={{ border: '1px solid var(--color-border)', borderRadius: '6px' }}>
+                <div className="text-xs" style={{ padding: '6px 8px', borderBottom: '1px solid var(--color-border)' }}>
+                  <strong>{KIND_LABELS[change.kind]}</strong>{' '}
+                  <span style={{ fontFamily: 'monospace' }}>{change.path}</span>
+                </div>
+                <pre className="text-xs m-0" style={{ padding: '6px 8px', fontFamily: 'monospace', whiteSpace: 'pre-wrap' }}>
+                  {change.diff.split('\n').map((line, index) => (
+                    <div key={index} style={{ color: diffLineColor(line) }}>
+                      {line}
+                    </div>
+                  ))}
+                </pre>
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -1,7 +1,19 @@
 import { useState, useEffect, useMemo, useRef } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { open } from '@tauri-apps/api/shell';
-import type { ProjectInfo, ProcessInfo, LogMessage, Workspace, GitBranch, GitPullResult, GitStatus } from '../../types';
+import type {
+  ProjectInfo,
+  ProcessInfo,
+  LogMessage,
+  Workspace,
+  GitBranch,
+  GitPullResult,
+  GitStatus,
+  HealthState,
+  HealthStatus,
+  HealthTransition,
+  ZebrasVersion,
+} from '../../types';
 import {
   gitSwitchBranch,
   listGitBranches,
@@ -9,8 +21,16 @@ import {
   stopProject,
   updateDebugConfig,
   updateProjectEnabled,
+  updateProjectVersionPin,
 } from '../../services/tauri';
 import { TerminalPanel } from './TerminalPanel';
+import { ProjectConfigModal } from './ProjectConfigModal';
+
+const HEALTH_LABELS: Record<HealthState, string> = {
+  healthy: '健康',
+  degraded: '降级',
+  unhealthy: '异常',
+};
 
 interface ProjectCardProps {
   project: ProjectInfo;
@@ -49,6 +69,7 @@ export function ProjectCard({
   const [followLogs, setFollowLogs] = useState(true);
   const [showDebugConfig, setShowDebugConfig] = useState(false);
   const [showTerminal, setShowTerminal] = useState(false);
+  const [showConfig, setShowConfig] = useState(false);
   const [debugConfig, setDebugConfig] = useState<Record<string, string>>(project.debug || {});
   const [selectedProject, setSelectedProject] = useState<string>('');
   const logContainerRef = useRef<HTMLDivElement>(null);
@@ -59,6 +80,7 @@ export function ProjectCard({
   const [branchSwitchingName, setBranchSwitchingName] = useState<string | null>(null);
   const [branchListError, setBranchListError] = useState<string | null>(null);
   const [branches, setBranches] = useState<GitBranch[]>([]);
+  const [health, setHealth] = useState<HealthStatus | null>(null);
 
   useEffect(() => {
     setDebugConfig(project.debug || {});
@@ -77,6 +99,21 @@ export function ProjectCard({
     };
   }, [project.id]);
 
+  useEffect(() => {
+    setHealth(processInfo?.health ?? null);
+    if (!processInfo) return;
+
+    const unlisten = listen<HealthTransition>('project_health_changed', (event) => {
+      if (event.payload.process_id === processInfo.process_id) {
+        setHealth(event.payload.current);
+      }
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [processInfo?.process_id]);
+
   useEffect(() => {
     if (!showLogs || !followLogs) return;
     const container = logContainerRef.current;
@@ -116,7 +153,7 @@ export function ProjectCard({
     try {
       setIsStarting(true);
       setLogs([]);
-      const info = await startProject(project.id, project.name, project.path);
+      const info = await startProject(project, workspace.id);
       onProcessStart(project.id, info);
     } catch (err) {
       alert(`启动失败: ${err}`);
@@ -145,7 +182,7 @@ export function ProjectCard({
       setLogs([]);
       await stopProject(processInfo.process_id);
       onProcessStop(project.id);
-      const info = await startProject(project.id, project.name, project.path);
+      const info = await startProject(project, workspace.id);
       onProcessStart(project.id, info);
       setShowLogs(true);
       setFollowLogs(true);
@@ -165,7 +202,7 @@ export function ProjectCard({
     const newDebugConfig = { ...debugConfig, [selectedProject]: url };
 
     try {
-      await updateDebugConfig(project.path, project.version, newDebugConfig);
+      await updateDebugConfig(project.path, project.version, newDebugConfig, workspace.settings.strict_json);
       setDebugConfig(newDebugConfig);
       setSelectedProject('');
     } catch (err) {
@@ -178,7 +215,7 @@ export function ProjectCard({
     delete newDebugConfig[depName];
 
     try {
-      await updateDebugConfig(project.path, project.version, newDebugConfig);
+      await updateDebugConfig(project.path, project.version, newDebugConfig, workspace.settings.strict_json);
       setDebugConfig(newDebugConfig);
     } catch (err) {
       alert(`移除调试依赖失败: ${err}`);
@@ -195,6 +232,15 @@ export function ProjectCard({
     }
   };
 
+  const handlePinVersion = async (version: ZebrasVersion | null) => {
+    try {
+      const updatedWorkspace = await updateProjectVersionPin(workspace, project.id, version);
+      onWorkspaceUpdate(updatedWorkspace);
+    } catch (err) {
+      alert(`固定版本失败: ${err}`);
+    }
+  };
+
   const handleLogsScroll = () => {
     const container = logContainerRef.current;
     if (!container) return;
@@ -269,7 +315,8 @@ export function ProjectCard({
   };
 
   const isRunning = processInfo && processInfo.status === 'running';
-  const isManagedProject = project.source_type === 'managed_project';
+  // 项目实例和自动识别的项目都没有 Zebras 配置
+  const isZebrasProject = project.source_type === 'zebras';
   const canControlProcess = project.is_valid && project.runnable;
   const canOpenByPort = project.port > 0;
   const availableProjects = allProjects.filter(
@@ -294,6 +341,20 @@ export function ProjectCard({
     };
   }, [branchSearch, branches]);
 
+  const getHealthBadgeStyle = (state: HealthState) => {
+    const color =
+      state === 'healthy'
+        ? 'var(--color-success)'
+        : state === 'degraded'
+          ? 'var(--color-warning)'
+          : 'var(--color-danger)';
+    return {
+      backgroundColor: 'rgba(255, 255, 255, 0.05)',
+      color,
+      border: `1px solid ${color}`,
+    };
+  };
+
   const getVersionBadgeStyle = (version: string) => {
     if (version === 'managed') {
       return {
@@ -489,7 +550,7 @@ export function ProjectCard({
           <div className="project-card__meta mt-xs">
             <div className="project-card__badges">
               <span className="badge" style={getVersionBadgeStyle(project.version)}>
-              {project.version.toUpperCase()}
+              {project.source_type === 'detected' ? project.framework ?? 'DETECTED' : project.version.toUpperCase()}
               </span>
               <span className="badge" style={getTypeBadgeStyle(project.type)}>
               {getTypeDisplayName(project.version, project.type)}
@@ -499,6 +560,16 @@ export function ProjectCard({
                   {project.provision_status}
                 </span>
               )}
+              {isRunning && health && (
+                <span
+                  className="badge"
+                  style={getHealthBadgeStyle(health.state)}
+                  title={health.message ?? `HTTP ${health.status_code ?? '-'}`}
+                >
+                  {HEALTH_LABELS[health.state]}
+                  {health.latency_ms !== undefined && ` · ${health.latency_ms}ms`}
+                </span>
+              )}
             </div>
 
             {gitStatus ? (
@@ -703,6 +774,54 @@ export function ProjectCard({
               ⚠️ {project.error}
             </div>
           )}
+          {project.version_candidates && project.version_candidates.length > 0 && (
+            <div className="mt-sm text-xs" style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+              {project.version_candidates.map((candidate) => (
+                <div key={candidate.version} style={{ display: 'flex', alignItems: 'center', gap: '6px' }}>
+                  <span style={{ fontFamily: 'monospace' }}>{candidate.config_file}</span>
+                  <span className="text-muted" style={{ flex: 1 }}>
+                    {candidate.error
+                      ? candidate.error
+                      : `${candidate.name} · ${candidate.type} · :${candidate.port}`}
+                  </span>
+                  <button
+                    className="btn btn-secondary btn-sm"
+                    onClick={() => handlePinVersion(candidate.version)}
+                    disabled={!!candidate.error}
+                  >
+                    固定为 {candidate.version}
+                  </button>
+                </div>
+              ))}
+            </div>
+          )}
+          {workspace.settings.version_pins?.[project.path] && (
+            <div className="mt-sm text-xs text-muted" style={{ display: 'flex', alignItems: 'center', gap: '6px' }}>
+              已固定为 {workspace.settings.version_pins[project.path]}
+              <button className="btn btn-secondary btn-sm" onClick={() => handlePinVersion(null)}>
+                取消固定
+              </button>
+            </div>
+          )}
+          {project.diagnostics && project.diagnostics.length > 0 && (
+            <div className="mt-sm" style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+              {project.diagnostics.map((diagnostic, index) => (
+                <div
+                  key={index}
+                  className="text-xs"
+                  style={{ color: diagnostic.severity === 'error' ? 'var(--color-danger)' : 'var(--color-warning)' }}
+                  title={diagnostic.suggestion}
+                >
+                  <span style={{ fontFamily: 'monospace' }}>
+                    {diagnostic.file}
+                    {diagnostic.line !== undefined && `:${diagnostic.line}:${diagnostic.column ?? 1}`}
+                  </span>{' '}
+                  {diagnostic.message}
+                  {diagnostic.suggestion && <div className="text-muted">{diagnostic.suggestion}</div>}
+                </div>
+              ))}
+            </div>
+          )}
        </div>
 
 	      {/* Actions Footer */}
@@ -739,7 +858,7 @@ export function ProjectCard({
 
 	          <div className="project-card__actions-tools">
 	            <div className="project-card__actions-icons">
-	              {!isManagedProject && (
+	              {isZebrasProject && (
                 <button
 	                onClick={() => setShowDebugConfig(!showDebugConfig)}
 	                className={`btn project-card__icon-btn ${showDebugConfig ? 'btn-primary' : 'btn-secondary'}`}
@@ -749,6 +868,17 @@ export function ProjectCard({
 	                <svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round"><path d="M12.22 2h-.44a2 2 0 0 0-2 2v.18a2 2 0 0 1-1 1.73l-.43.25a2 2 0 0 1-2 0l-.15-.08a2 2 0 0 0-2.73.73l-.22.38a2 2 0 0 0 .73 2.73l.15.1a2 2 0 0 1 1 1.72v.51a2 2 0 0 1-1 1.74l-.15.09a2 2 0 0 0-.73 2.73l.22.38a2 2 0 0 0 2.73.73l.15-.08a2 2 0 0 1 2 0l.43.25a2 2 0 0 1 1 1.73V20a2 2 0 0 0 2 2h.44a2 2 0 0 0 2-2v-.18a2 2 0 0 1 1-1.73l.43-.25a2 2 0 0 1 2 0l.15.08a2 2 0 0 0 2.73-.73l.22-.39a2 2 0 0 0-.73-2.73l-.15-.1a2 2 0 0 1-1-1.74v-.47a2 2 0 0 1 1-1.74l.15-.09a2 2 0 0 0 .73-2.73l-.22-.38a2 2 0 0 0-2.73-.73l-.15.08a2 2 0 0 1-2 0l-.43-.25a2 2 0 0 1-1-1.73V4a2 2 0 0 0-2-2z"/><circle cx="12" cy="12" r="3"/></svg>
 	              </button>
                 )}
+
+	              {isZebrasProject && (
+	                <button
+	                  onClick={() => setShowConfig(true)}
+	                  className="btn project-card__icon-btn btn-secondary"
+	                  title="查看完整配置"
+	                  aria-label="查看完整配置"
+	                >
+	                  <svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round"><path d="M14 2H6a2 2 0 0 0-2 2v16a2 2 0 0 0 2 2h12a2 2 0 0 0 2-2V8z"/><polyline points="14 2 14 8 20 8"/><line x1="8" y1="13" x2="16" y2="13"/><line x1="8" y1="17" x2="13" y2="17"/></svg>
+	                </button>
+	              )}
 	             
 	              {(isRunning || logs.length > 0) && (
 	                <button
@@ -806,7 +936,7 @@ export function ProjectCard({
 	          )}
 
 	          {/* Debug Configuration Panel */}
-	          {showDebugConfig && !isManagedProject && (
+	          {showDebugConfig && isZebrasProject && (
 	             <div style={{ 
 	               backgroundColor: 'rgba(0,0,0,0.2)', 
                borderRadius: 'var(--radius-md)', 
@@ -873,7 +1003,7 @@ export function ProjectCard({
             <TerminalPanel
               projectId={project.id}
               projectPath={project.path}
-              showQuickCommands={!isManagedProject}
+              showQuickCommands={isZebrasProject}
             />
           )}
 
@@ -945,6 +1075,15 @@ export function ProjectCard({
           )}
         </div>
       )}
+
+      {showConfig && (
+        <ProjectConfigModal
+          project={project}
+          workspace={workspace}
+          onWorkspaceUpdate={onWorkspaceUpdate}
+          onClose={() => setShowConfig(false)}
+        />
+      )}
     </div>
   );
 }
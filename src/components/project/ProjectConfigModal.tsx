@@ -0,0 +1,207 @@
+import { useEffect, useState } from 'react';
+import type { ConfigSource, ConfigTreeEntry, ConfigTreeNode, ProjectConfig, ProjectInfo, Workspace } from '../../types';
+import { getProjectConfig, listProjectConfigEnvs, saveWorkspace, updateProjectConfigEnv } from '../../services/tauri';
+import { MigrationModal } from './MigrationModal';
+
+const SOURCE_LABELS: Record<ConfigSource, string> = {
+  main: 'main',
+  env: 'env',
+  local: 'local',
+  default: '默认值',
+};
+
+const SOURCE_COLORS: Record<ConfigSource, string> = {
+  main: 'var(--color-text-secondary)',
+  env: 'var(--color-primary)',
+  local: 'var(--color-warning)',
+  default: 'var(--color-text-muted)',
+};
+
+interface ProjectConfigModalProps {
+  project: ProjectInfo;
+  workspace: Workspace;
+  onWorkspaceUpdate: (workspace: Workspace) => void;
+  onClose: () => void;
+}
+
+function formatValue(node: Extract<ConfigTreeNode, { kind: 'value' }>) {
+  if (node.expression !== undefined) return node.expression;
+  return JSON.stringify(node.value);
+}
+
+function ConfigEntryRow({ entry, depth }: { entry: ConfigTreeEntry; depth: number }) {
+  const { node } = entry;
+  return (
+    <>
+      <div
+        className="text-xs"
+        style={{
+          display: 'flex',
+          gap: '8px',
+          padding: '3px 8px',
+          paddingLeft: `${8 + depth * 16}px`,
+          fontFamily: 'monospace',
+          borderBottom: '1px solid var(--color-border)',
+        }}
+      >
+        <span style={{ flexShrink: 0 }}>{entry.key}:</span>
+        {node.kind === 'value' && (
+          <span
+            style={{
+              flex: 1,
+              overflow: 'hidden',
+              textOverflow: 'ellipsis',
+              whiteSpace: 'nowrap',
+              fontStyle: node.expression !== undefined ? 'italic' : undefined,
+            }}
+            title={formatValue(node)}
+          >
+            {formatValue(node)}
+          </span>
+        )}
+        {node.kind === 'object' && <span style={{ flex: 1 }} />}
+        <span style={{ color: SOURCE_COLORS[node.source], flexShrink: 0 }}>
+          {SOURCE_LABELS[node.source]}
+          {node.line !== undefined && `:${node.line}`}
+        </span>
+      </div>
+      {node.kind === 'object' &&
+        node.entries.map((child) => <ConfigEntryRow key={child.key} entry={child} depth={depth + 1} />)}
+    </>
+  );
+}
+
+export function ProjectConfigModal({ project, workspace, onWorkspaceUpdate, onClose }: ProjectConfigModalProps) {
+  const [config, setConfig] = useState<ProjectConfig | null>(null);
+  const [envs, setEnvs] = useState<string[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [showMigration, setShowMigration] = useState(false);
+
+  useEffect(() => {
+    listProjectConfigEnvs(project.path, project.version)
+      .then(setEnvs)
+      .catch(() => setEnvs([]));
+  }, [project.path, project.version]);
+
+  useEffect(() => {
+    setError(null);
+    getProjectConfig(project.path, project.version, project.config_env)
+      .then(setConfig)
+      .catch((err) => setError(String(err)));
+  }, [project.path, project.version, project.config_env]);
+
+  const handleEnvChange = async (env: string) => {
+    try {
+      onWorkspaceUpdate(await updateProjectConfigEnv(workspace, project.id, env || null));
+    } catch (err) {
+      setError(`切换环境失败: ${String(err)}`);
+    }
+  };
+
+  const handleStrictJsonChange = async (strictJson: boolean) => {
+    const updated = { ...workspace, settings: { ...workspace.settings, strict_json: strictJson } };
+    try {
+      onWorkspaceUpdate(await saveWorkspace(updated));
+    } catch (err) {
+      setError(`保存设置失败: ${String(err)}`);
+    }
+  };
+
+  // 已选择但文件被删除的环境也保留在列表中，方便切回
+  const envOptions = project.config_env && !envs.includes(project.config_env) ? [...envs, project.config_env] : envs;
+
+  return (
+    <div
+      role="dialog"
+      aria-modal="true"
+      style={{
+        position: 'fixed',
+        inset: 0,
+        backgroundColor: 'rgba(0,0,0,0.5)',
+        display: 'flex',
+        alignItems: 'center',
+        justifyContent: 'center',
+        zIndex: 1000,
+        padding: '24px',
+      }}
+      onMouseDown={(e) => {
+        if (e.target === e.currentTarget) onClose();
+      }}
+    >
+      <div
+        className="card"
+        style={{ width: '760px', maxWidth: '100%', height: '80vh', display: 'flex', flexDirection: 'column', gap: '12px' }}
+      >
+        <div className="flex justify-between items-center">
+          <div style={{ display: 'flex', flexDirection: 'column', gap: 4 }}>
+            <h2 className="m-0" style={{ fontSize: '1.1rem' }}>{project.name} 完整配置</h2>
+            <div className="text-xs text-muted">
+              main、环境覆盖层与 local 依次合并后的结果，右侧标注每个值来自哪个文件及所在行。
+            </div>
+          </div>
+          <div className="flex gap-sm items-center">
+            <select
+              className="input"
+              value={project.config_env ?? ''}
+              onChange={(e) => handleEnvChange(e.target.value)}
+              disabled={envOptions.length === 0}
+              title="选择参与合并的环境配置"
+            >
+              <option value="">不使用环境配置</option>
+              {envOptions.map((env) => (
+                <option key={env} value={env}>
+                  环境: {env}
+                </option>
+              ))}
+            </select>
+            {project.version === 'v2' && (
+              <button className="btn btn-secondary" onClick={() => setShowMigration(true)}>
+                迁移到 v3
+              </button>
+            )}
+            <button className="btn btn-secondary" onClick={onClose}>关闭</button>
+          </div>
+        </div>
+
+        {error && <div className="text-xs text-danger">{error}</div>}
+
+        {project.version === 'v2' && (
+          <label className="flex items-center gap-sm text-xs text-muted">
+            <input
+              type="checkbox"
+              checked={workspace.settings.strict_json ?? false}
+              onChange={(e) => handleStrictJsonChange(e.target.checked)}
+              style={{ accentColor: 'var(--color-primary)' }}
+            />
+            <span>写回 zebra.local.json 时输出严格 JSON（去掉注释和尾随逗号，对当前工作区生效）</span>
+          </label>
+        )}
+
+        {config && (
+          <>
+            <div className="text-xs text-muted" style={{ fontFamily: 'monospace' }}>
+              {config.files.map((file) => (
+                <div key={file.source}>
+                  {SOURCE_LABELS[file.source]}: {file.path}
+                  {!file.exists && '（不存在）'}
+                </div>
+              ))}
+            </div>
+            <div style={{ flex: 1, overflow: 'auto', border: '1px solid var(--color-border)', borderRadius: '6px' }}>
+              {config.tree.kind === 'object' &&
+                config.tree.entries.map((entry) => <ConfigEntryRow key={entry.key} entry={entry} depth={0} />)}
+            </div>
+          </>
+        )}
+      </div>
+      {showMigration && (
+        <MigrationModal
+          project={project}
+          workspace={workspace}
+          onWorkspaceUpdate={onWorkspaceUpdate}
+          onClose={() => setShowMigration(false)}
+        />
+      )}
+    </div>
+  );
+}
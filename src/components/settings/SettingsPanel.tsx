@@ -1,4 +1,7 @@
+import { useState } from 'react';
+import { save } from '@tauri-apps/api/dialog';
 import type { AppSettings } from '../../types';
+import { exportGatewayCa } from '../../services/tauri';
 
 interface SettingsPanelProps {
   settings: AppSettings;
@@ -8,6 +11,22 @@ interface SettingsPanelProps {
 }
 
 export function SettingsPanel({ settings, onChange, onReset, onClose }: SettingsPanelProps) {
+  const [caMessage, setCaMessage] = useState<string | null>(null);
+
+  const handleExportCa = async () => {
+    try {
+      const dest = await save({
+        defaultPath: 'zebras-launcher-ca.pem',
+        filters: [{ name: 'PEM 证书', extensions: ['pem', 'crt'] }],
+      });
+      if (!dest) return;
+      const path = await exportGatewayCa(dest);
+      setCaMessage(`已导出到 ${path}，导入系统钥匙串或浏览器并设为信任即可。`);
+    } catch (err) {
+      setCaMessage(`导出失败: ${String(err)}`);
+    }
+  };
+
   return (
     <div
       style={{
@@ -55,6 +74,101 @@ export function SettingsPanel({ settings, onChange, onReset, onClose }: Settings
             />
             <div className="text-xs text-muted mt-xs">默认每 15 分钟自动 fetch 并检查远程更新。</div>
           </div>
+
+          <label className="flex items-center gap-sm text-sm">
+            <input
+              type="checkbox"
+              checked={settings.gatewayEnabled}
+              onChange={(e) => onChange({ gatewayEnabled: e.target.checked })}
+              style={{ accentColor: 'var(--color-primary)' }}
+            />
+            <span>启用本地开发网关</span>
+          </label>
+
+          <div>
+            <label className="block mb-sm text-secondary text-sm">网关端口</label>
+            <input
+              type="number"
+              className="input"
+              min={1}
+              max={65535}
+              value={settings.gatewayPort}
+              onChange={(e) => {
+                const next = Number(e.target.value);
+                onChange({ gatewayPort: Number.isInteger(next) && next > 0 && next < 65536 ? next : 7000 });
+              }}
+            />
+            <div className="text-xs text-muted mt-xs">
+              运行中的项目可通过 http://&lt;domain&gt;.localhost:{settings.gatewayPort} 或 /&lt;domain&gt; 路径访问，支持 HMR 的 WebSocket。
+            </div>
+          </div>
+
+          <label className="flex items-center gap-sm text-sm">
+            <input
+              type="checkbox"
+              checked={settings.gatewayHttpsEnabled}
+              onChange={(e) => onChange({ gatewayHttpsEnabled: e.target.checked })}
+              style={{ accentColor: 'var(--color-primary)' }}
+            />
+            <span>网关同时提供 HTTPS</span>
+          </label>
+
+          {settings.gatewayHttpsEnabled && (
+            <div>
+              <label className="block mb-sm text-secondary text-sm">HTTPS 端口</label>
+              <input
+                type="number"
+                className="input"
+                min={1}
+                max={65535}
+                value={settings.gatewayHttpsPort}
+                onChange={(e) => {
+                  const next = Number(e.target.value);
+                  onChange({ gatewayHttpsPort: Number.isInteger(next) && next > 0 && next < 65536 ? next : 7443 });
+                }}
+              />
+              <div className="text-xs text-muted mt-xs">
+                证书由本地 CA 为各项目域名和 *.localhost 签发，保存在 ~/.zebras-launcher/certs，域名变化时自动补签。
+              </div>
+              <div className="flex items-center gap-sm mt-sm">
+                <button className="btn btn-secondary" onClick={handleExportCa}>
+                  导出 CA 证书
+                </button>
+                {caMessage && <span className="text-xs text-muted">{caMessage}</span>}
+              </div>
+            </div>
+          )}
+
+          <label className="flex items-center gap-sm text-sm">
+            <input
+              type="checkbox"
+              checked={settings.registryEnabled}
+              onChange={(e) => onChange({ registryEnabled: e.target.checked })}
+              style={{ accentColor: 'var(--color-primary)' }}
+            />
+            <span>启用子应用注册表</span>
+          </label>
+
+          {settings.registryEnabled && (
+            <div>
+              <label className="block mb-sm text-secondary text-sm">注册表端口</label>
+              <input
+                type="number"
+                className="input"
+                min={1}
+                max={65535}
+                value={settings.registryPort}
+                onChange={(e) => {
+                  const next = Number(e.target.value);
+                  onChange({ registryPort: Number.isInteger(next) && next > 0 && next < 65536 ? next : 7100 });
+                }}
+              />
+              <div className="text-xs text-muted mt-xs">
+                基座应用可从 http://localhost:{settings.registryPort}/registry.json 读取运行中的子应用（名称、类型、版本、域名、入口地址），
+                或订阅 /events（SSE）在子应用启停时收到最新列表。
+              </div>
+            </div>
+          )}
         </div>
 
         <div className="flex gap-sm mt-lg">
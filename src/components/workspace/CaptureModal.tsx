@@ -0,0 +1,269 @@
+import { useCallback, useEffect, useMemo, useState } from 'react';
+import { save } from '@tauri-apps/api/dialog';
+import type { CaptureFilter, CaptureSession, CapturedBody, CapturedExchange, Workspace } from '../../types';
+import {
+  clearCaptures,
+  exportCapturesHar,
+  getCaptureSessions,
+  queryCaptures,
+  updateProjectCapture,
+} from '../../services/tauri';
+
+const DEFAULT_MAX_BODY_BYTES = 64 * 1024;
+const REFRESH_INTERVAL_MS = 2000;
+
+interface CaptureModalProps {
+  workspace: Workspace;
+  onWorkspaceUpdated: (workspace: Workspace) => void;
+  onClose: () => void;
+}
+
+function getStatusColor(status?: number) {
+  if (status === undefined) return 'var(--color-danger)';
+  if (status >= 500) return 'var(--color-danger)';
+  if (status >= 400) return 'var(--color-warning)';
+  return 'var(--color-success)';
+}
+
+function formatBody(body: CapturedBody) {
+  if (body.size === 0) return '（空）';
+  if (body.text === undefined) return `（二进制内容，${body.size} 字节）`;
+  return body.truncated ? `${body.text}\n…（已截断，共 ${body.size} 字节）` : body.text;
+}
+
+export function CaptureModal({ workspace, onWorkspaceUpdated, onClose }: CaptureModalProps) {
+  const [sessions, setSessions] = useState<CaptureSession[]>([]);
+  const [captures, setCaptures] = useState<CapturedExchange[]>([]);
+  const [projectId, setProjectId] = useState('');
+  const [method, setMethod] = useState('');
+  const [urlContains, setUrlContains] = useState('');
+  const [errorsOnly, setErrorsOnly] = useState(false);
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  const filter = useMemo<CaptureFilter>(
+    () => ({
+      project_id: projectId || undefined,
+      method: method || undefined,
+      url_contains: urlContains.trim() || undefined,
+      min_status: errorsOnly ? 400 : undefined,
+    }),
+    [projectId, method, urlContains, errorsOnly]
+  );
+
+  const refresh = useCallback(async () => {
+    try {
+      const [nextSessions, nextCaptures] = await Promise.all([getCaptureSessions(), queryCaptures(filter)]);
+      setSessions(nextSessions);
+      setCaptures(nextCaptures);
+    } catch (err) {
+      setMessage(`加载抓包记录失败: ${String(err)}`);
+    }
+  }, [filter]);
+
+  useEffect(() => {
+    refresh();
+    const timer = window.setInterval(refresh, REFRESH_INTERVAL_MS);
+    return () => window.clearInterval(timer);
+  }, [refresh]);
+
+  const toggleCapture = async (id: string, enabled: boolean) => {
+    try {
+      const updated = await updateProjectCapture(
+        workspace,
+        id,
+        enabled ? { max_body_bytes: DEFAULT_MAX_BODY_BYTES } : null
+      );
+      onWorkspaceUpdated(updated);
+      await refresh();
+    } catch (err) {
+      setMessage(`切换抓包失败: ${String(err)}`);
+    }
+  };
+
+  const handleClear = async () => {
+    try {
+      await clearCaptures(projectId || undefined);
+      setSelectedId(null);
+      await refresh();
+    } catch (err) {
+      setMessage(`清空失败: ${String(err)}`);
+    }
+  };
+
+  const handleExport = async () => {
+    try {
+      const dest = await save({
+        defaultPath: `${workspace.name}-captures.har`,
+        filters: [{ name: 'HAR', extensions: ['har'] }],
+      });
+      if (!dest) return;
+      const count = await exportCapturesHar(filter, dest);
+      setMessage(`已导出 ${count} 条记录到 ${dest}`);
+    } catch (err) {
+      setMessage(`导出失败: ${String(err)}`);
+    }
+  };
+
+  const selected = captures.find((c) => c.id === selectedId) ?? null;
+  const sessionsByProject = new Map(sessions.map((s) => [s.project_id, s]));
+
+  return (
+    <div
+      role="dialog"
+      aria-modal="true"
+      style={{
+        position: 'fixed',
+        inset: 0,
+        backgroundColor: 'rgba(0,0,0,0.5)',
+        display: 'flex',
+        alignItems: 'center',
+        justifyContent: 'center',
+        zIndex: 1000,
+        padding: '24px',
+      }}
+      onMouseDown={(e) => {
+        if (e.target === e.currentTarget) onClose();
+      }}
+    >
+      <div
+        className="card"
+        style={{ width: '1100px', maxWidth: '100%', height: '85vh', display: 'flex', flexDirection: 'column', gap: '12px' }}
+      >
+        <div className="flex justify-between items-center">
+          <div style={{ display: 'flex', flexDirection: 'column', gap: 4 }}>
+            <h2 className="m-0" style={{ fontSize: '1.1rem' }}>请求抓包</h2>
+            <div className="text-xs text-muted">
+              开启后项目启动时会在端口前挂一层代理，经由代理端口或开发网关的请求都会被记录。
+            </div>
+          </div>
+          <div className="flex gap-sm">
+            <button className="btn btn-secondary" onClick={handleClear}>清空</button>
+            <button className="btn btn-primary" onClick={handleExport} disabled={captures.length === 0}>
+              导出 HAR
+            </button>
+            <button className="btn btn-secondary" onClick={onClose}>关闭</button>
+          </div>
+        </div>
+
+        {message && <div className="text-xs text-muted">{message}</div>}
+
+        <div className="flex gap-sm" style={{ flexWrap: 'wrap' }}>
+          {workspace.projects
+            .filter((p) => p.port > 0)
+            .map((p) => {
+              const session = sessionsByProject.get(p.id);
+              return (
+                <label key={p.id} className="flex items-center gap-sm text-sm" style={{ marginRight: '12px' }}>
+                  <input
+                    type="checkbox"
+                    checked={!!p.capture}
+                    onChange={(e) => toggleCapture(p.id, e.target.checked)}
+                    style={{ accentColor: 'var(--color-primary)' }}
+                  />
+                  <span>{p.name}</span>
+                  {session && (
+                    <span className="text-xs text-muted" style={{ fontFamily: 'monospace' }}>
+                      :{session.listen_port} → :{session.target_port}
+                    </span>
+                  )}
+                </label>
+              );
+            })}
+        </div>
+
+        <div className="flex gap-sm items-center">
+          <select className="select" value={projectId} onChange={(e) => setProjectId(e.target.value)}>
+            <option value="">全部项目</option>
+            {workspace.projects.map((p) => (
+              <option key={p.id} value={p.id}>
+                {p.name}
+              </option>
+            ))}
+          </select>
+          <select className="select" value={method} onChange={(e) => setMethod(e.target.value)}>
+            <option value="">全部方法</option>
+            {['GET', 'POST', 'PUT', 'PATCH', 'DELETE', 'OPTIONS'].map((m) => (
+              <option key={m} value={m}>
+                {m}
+              </option>
+            ))}
+          </select>
+          <input
+            className="input"
+            placeholder="URL 包含..."
+            value={urlContains}
+            onChange={(e) => setUrlContains(e.target.value)}
+            style={{ flex: 1 }}
+          />
+          <label className="flex items-center gap-sm text-sm">
+            <input
+              type="checkbox"
+              checked={errorsOnly}
+              onChange={(e) => setErrorsOnly(e.target.checked)}
+              style={{ accentColor: 'var(--color-primary)' }}
+            />
+            <span>只看错误</span>
+          </label>
+        </div>
+
+        <div style={{ flex: 1, display: 'flex', gap: '12px', minHeight: 0 }}>
+          <div style={{ flex: 1, overflow: 'auto', border: '1px solid var(--color-border)', borderRadius: '6px' }}>
+            {captures.length === 0 ? (
+              <div className="text-sm text-muted" style={{ padding: '16px' }}>暂无记录</div>
+            ) : (
+              [...captures].reverse().map((c) => (
+                <div
+                  key={c.id}
+                  onClick={() => setSelectedId(c.id)}
+                  className="text-xs"
+                  style={{
+                    display: 'flex',
+                    gap: '8px',
+                    padding: '6px 10px',
+                    cursor: 'pointer',
+                    fontFamily: 'monospace',
+                    borderBottom: '1px solid var(--color-border)',
+                    backgroundColor: c.id === selectedId ? 'rgba(255,255,255,0.06)' : undefined,
+                  }}
+                >
+                  <span style={{ width: '36px', color: getStatusColor(c.status) }}>{c.status ?? 'ERR'}</span>
+                  <span style={{ width: '56px' }}>{c.method}</span>
+                  <span style={{ flex: 1, overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }} title={c.url}>
+                    {c.url}
+                  </span>
+                  <span className="text-muted">{c.project_name}</span>
+                  <span className="text-muted" style={{ width: '60px', textAlign: 'right' }}>{c.duration_ms}ms</span>
+                </div>
+              ))
+            )}
+          </div>
+
+          {selected && (
+            <div style={{ flex: 1, overflow: 'auto', display: 'flex', flexDirection: 'column', gap: '8px' }}>
+              <div className="text-sm" style={{ wordBreak: 'break-all' }}>
+                <strong>{selected.method}</strong> {selected.url}
+              </div>
+              {selected.error && <div className="text-xs text-danger">{selected.error}</div>}
+              {[
+                { title: '请求头', headers: selected.request_headers, body: selected.request_body, bodyTitle: '请求体' },
+                { title: '响应头', headers: selected.response_headers, body: selected.response_body, bodyTitle: '响应体' },
+              ].map((section) => (
+                <div key={section.title}>
+                  <div className="text-xs text-secondary mb-sm">{section.title}</div>
+                  <pre className="text-xs" style={{ margin: 0, whiteSpace: 'pre-wrap', wordBreak: 'break-all' }}>
+                    {section.headers.map((h) => `${h.name}: ${h.value}`).join('\n')}
+                  </pre>
+                  <div className="text-xs text-secondary mb-sm mt-sm">{section.bodyTitle}</div>
+                  <pre className="text-xs" style={{ margin: 0, whiteSpace: 'pre-wrap', wordBreak: 'break-all' }}>
+                    {formatBody(section.body)}
+                  </pre>
+                </div>
+              ))}
+            </div>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
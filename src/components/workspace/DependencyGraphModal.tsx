@@ -40,6 +40,7 @@ type NodeData = (ProjectNodeData | ExternalNodeData) & { label?: ReactNode };
 interface DependencyGraphModalProps {
   projects: ProjectInfo[];
   runningProcesses: Map<string, ProcessInfo>;
+  strictJson?: boolean; // 写回 zebra.local.json 时输出严格 JSON
   onClose: () => void;
   onDebugConfigSaved: () => Promise<void>;
   onRestartRunningProjects: (projectIds: string[]) => Promise<void>;
@@ -184,6 +185,7 @@ function buildInitialNodes(projects: ProjectInfo[], externalNodeIds: Set<string>
 export function DependencyGraphModal({
   projects,
   runningProcesses,
+  strictJson,
   onClose,
   onDebugConfigSaved,
   onRestartRunningProjects,
@@ -393,13 +395,13 @@ export function DependencyGraphModal({
         const project = projectsById.get(projectId);
         if (!project) continue;
         const debugMap = draftByProjectId[projectId] ?? {};
-        await updateDebugConfig(project.path, project.version, debugMap);
+        await updateDebugConfig(project.path, project.version, debugMap, strictJson);
       }
       setDirtyProjectIds(new Set());
     } finally {
       setSaving(false);
     }
-  }, [dirtyProjectIds, draftByProjectId, projectsById, saving]);
+  }, [dirtyProjectIds, draftByProjectId, projectsById, saving, strictJson]);
 
   const saveDraft = useCallback(async () => {
     await persistDraft();
@@ -0,0 +1,175 @@
+import { useCallback, useEffect, useState } from 'react';
+import type { MockRuleSet, MockServerStatus, Workspace } from '../../types';
+import {
+  getMockRules,
+  getMockServerStatus,
+  startMockServer,
+  stopMockServer,
+  updateMockRules,
+} from '../../services/tauri';
+
+const EXAMPLE_RULE_SETS: MockRuleSet[] = [
+  {
+    id: 'shared',
+    name: '公共接口',
+    port: 9100,
+    upstream: 'http://127.0.0.1:8080',
+    rules: [
+      {
+        method: 'GET',
+        path: '/api/user/current',
+        action: { type: 'static', body: { name: 'dev' }, status: 200, delay_ms: 0 },
+        enabled: true,
+      },
+      {
+        method: 'GET',
+        path: '/api/orders/:id',
+        action: { type: 'static', file: 'mocks/order-detail.json', status: 200, delay_ms: 300 },
+        enabled: true,
+      },
+      {
+        path: '/api/**',
+        action: { type: 'pass_through' },
+        enabled: true,
+      },
+    ],
+  },
+];
+
+interface MockRulesModalProps {
+  workspace: Workspace;
+  onWorkspaceUpdated: (workspace: Workspace) => void;
+  onClose: () => void;
+}
+
+export function MockRulesModal({ workspace, onWorkspaceUpdated, onClose }: MockRulesModalProps) {
+  const [draft, setDraft] = useState('');
+  const [status, setStatus] = useState<MockServerStatus | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  const isRunning = status?.workspace_id === workspace.id;
+  const isManaged = workspace.source_type === 'managed_project';
+
+  const refreshStatus = useCallback(async () => {
+    try {
+      setStatus(await getMockServerStatus());
+    } catch (err) {
+      setMessage(`获取 Mock 服务状态失败: ${String(err)}`);
+    }
+  }, []);
+
+  useEffect(() => {
+    getMockRules(workspace)
+      .then((ruleSets) => setDraft(JSON.stringify(ruleSets.length > 0 ? ruleSets : EXAMPLE_RULE_SETS, null, 2)))
+      .catch((err) => setMessage(`加载 Mock 规则失败: ${String(err)}`));
+    refreshStatus();
+  }, [workspace.id]);
+
+  const parseDraft = (): MockRuleSet[] | null => {
+    try {
+      const parsed = JSON.parse(draft);
+      if (!Array.isArray(parsed)) {
+        setMessage('规则需要是规则组数组');
+        return null;
+      }
+      return parsed as MockRuleSet[];
+    } catch (err) {
+      setMessage(`JSON 格式错误: ${String(err)}`);
+      return null;
+    }
+  };
+
+  const handleSave = async () => {
+    const ruleSets = parseDraft();
+    if (!ruleSets) return;
+
+    setSaving(true);
+    try {
+      const updated = await updateMockRules(workspace, ruleSets);
+      onWorkspaceUpdated(updated);
+      setMessage(isRunning ? '已保存，Mock 服务会自动重新加载规则。' : '已保存。');
+      await refreshStatus();
+    } catch (err) {
+      setMessage(`保存失败: ${String(err)}`);
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleToggleServer = async () => {
+    try {
+      const next = isRunning ? await stopMockServer() : await startMockServer(workspace);
+      setStatus(next);
+      setMessage(null);
+    } catch (err) {
+      setMessage(`${isRunning ? '停止' : '启动'} Mock 服务失败: ${String(err)}`);
+    }
+  };
+
+  return (
+    <div
+      role="dialog"
+      aria-modal="true"
+      style={{
+        position: 'fixed',
+        inset: 0,
+        backgroundColor: 'rgba(0,0,0,0.5)',
+        display: 'flex',
+        alignItems: 'center',
+        justifyContent: 'center',
+        zIndex: 1000,
+        padding: '24px',
+      }}
+      onMouseDown={(e) => {
+        if (e.target === e.currentTarget) onClose();
+      }}
+    >
+      <div
+        className="card"
+        style={{ width: '900px', maxWidth: '100%', height: '85vh', display: 'flex', flexDirection: 'column', gap: '12px' }}
+      >
+        <div className="flex justify-between items-center">
+          <div style={{ display: 'flex', flexDirection: 'column', gap: 4 }}>
+            <h2 className="m-0" style={{ fontSize: '1.1rem' }}>Mock 接口</h2>
+            <div className="text-xs text-muted">
+              每个规则组在自己的端口上提供服务，开发服务器把接口代理到该端口即可。
+              {isManaged ? '规则保存在 .zebras/mocks 目录，' : '规则保存在工作区配置中，'}
+              修改后自动重新加载。
+            </div>
+          </div>
+          <div className="flex gap-sm">
+            <button className={`btn ${isRunning ? 'btn-warning' : 'btn-secondary'}`} onClick={handleToggleServer}>
+              {isRunning ? '停止服务' : '启动服务'}
+            </button>
+            <button className="btn btn-primary" onClick={handleSave} disabled={saving}>
+              {saving ? '保存中...' : '保存'}
+            </button>
+            <button className="btn btn-secondary" onClick={onClose}>关闭</button>
+          </div>
+        </div>
+
+        {isRunning && status && (
+          <div className="text-xs text-muted" style={{ fontFamily: 'monospace' }}>
+            监听端口: {status.ports.map((port) => `http://127.0.0.1:${port}`).join('  ')}
+          </div>
+        )}
+        {status?.error && <div className="text-xs text-danger">{status.error}</div>}
+        {message && <div className="text-xs text-muted">{message}</div>}
+
+        <textarea
+          className="input"
+          value={draft}
+          onChange={(e) => setDraft(e.target.value)}
+          spellCheck={false}
+          style={{ flex: 1, fontFamily: 'monospace', fontSize: '12px', resize: 'none' }}
+        />
+
+        <div className="text-xs text-muted">
+          path 中 :id 或 * 匹配一段、** 匹配剩余路径；action.type 为 static（file 或 body、status、delay_ms）或
+          pass_through（透传到 upstream）。project_id 为空的规则组作为工作区级规则，项目规则组未命中时继续匹配。
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,140 @@
+import { useEffect, useState } from 'react';
+import type { ProjectTemplate, Workspace } from '../../types';
+import { createProjectFromTemplate, listProjectTemplates } from '../../services/tauri';
+
+interface NewProjectModalProps {
+  workspace: Workspace;
+  onWorkspaceUpdated: (workspace: Workspace) => void;
+  onClose: () => void;
+}
+
+const templateKey = (template: ProjectTemplate) => `${template.source}:${template.id}`;
+
+export function NewProjectModal({ workspace, onWorkspaceUpdated, onClose }: NewProjectModalProps) {
+  const [templates, setTemplates] = useState<ProjectTemplate[]>([]);
+  const [selected, setSelected] = useState('');
+  const [folder, setFolder] = useState(workspace.folders[0] ?? '');
+  const [name, setName] = useState('');
+  const [domain, setDomain] = useState('');
+  const [gitInit, setGitInit] = useState(true);
+  const [creating, setCreating] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    listProjectTemplates()
+      .then((list) => {
+        setTemplates(list);
+        if (list.length > 0) setSelected(templateKey(list[0]));
+      })
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  const template = templates.find((t) => templateKey(t) === selected);
+
+  const handleCreate = async () => {
+    if (!template) return;
+    setCreating(true);
+    setError(null);
+    try {
+      const result = await createProjectFromTemplate(workspace, {
+        template_id: template.id,
+        source: template.source,
+        folder,
+        name: name.trim(),
+        domain: domain.trim() || undefined,
+        git_init: gitInit,
+      });
+      onWorkspaceUpdated(result.workspace);
+      if (result.warning) alert(result.warning);
+      onClose();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setCreating(false);
+    }
+  };
+
+  return (
+    <div
+      role="dialog"
+      aria-modal="true"
+      style={{
+        position: 'fixed',
+        inset: 0,
+        backgroundColor: 'rgba(0,0,0,0.5)',
+        display: 'flex',
+        alignItems: 'center',
+        justifyContent: 'center',
+        zIndex: 1000,
+        padding: '24px',
+      }}
+      onMouseDown={(e) => {
+        if (e.target === e.currentTarget && !creating) onClose();
+      }}
+    >
+      <div className="card" style={{ width: '520px', maxWidth: '100%', display: 'flex', flexDirection: 'column', gap: '12px' }}>
+        <div style={{ display: 'flex', flexDirection: 'column', gap: 4 }}>
+          <h2 className="m-0" style={{ fontSize: '1.1rem' }}>从模板新建项目</h2>
+          <div className="text-xs text-muted">
+            自定义模板放在 ~/.zebras-launcher/templates 下，文件中的 {'{{name}}'}、{'{{domain}}'}、{'{{port}}'} 会被替换，端口自动分配。
+          </div>
+        </div>
+
+        <label className="text-xs text-muted">
+          模板
+          <select className="input" value={selected} onChange={(e) => setSelected(e.target.value)}>
+            {templates.map((t) => (
+              <option key={templateKey(t)} value={templateKey(t)}>
+                {t.name} ({t.version}{t.source === 'user' ? '，自定义' : ''})
+              </option>
+            ))}
+          </select>
+        </label>
+
+        <label className="text-xs text-muted">
+          创建到
+          <select className="input" value={folder} onChange={(e) => setFolder(e.target.value)}>
+            {workspace.folders.map((f) => (
+              <option key={f} value={f}>
+                {f}
+              </option>
+            ))}
+          </select>
+        </label>
+
+        <label className="text-xs text-muted">
+          项目名（同时作为目录名）
+          <input className="input" value={name} onChange={(e) => setName(e.target.value)} placeholder="order" />
+        </label>
+
+        <label className="text-xs text-muted">
+          domain（可选）
+          <input className="input" value={domain} onChange={(e) => setDomain(e.target.value)} />
+        </label>
+
+        <label className="flex items-center gap-sm text-xs text-muted">
+          <input
+            type="checkbox"
+            checked={gitInit}
+            onChange={(e) => setGitInit(e.target.checked)}
+            style={{ accentColor: 'var(--color-primary)' }}
+          />
+          <span>初始化 git 仓库</span>
+        </label>
+
+        {error && <div className="text-xs text-danger">{error}</div>}
+
+        <div className="flex gap-sm" style={{ justifyContent: 'flex-end' }}>
+          <button className="btn btn-secondary" onClick={onClose} disabled={creating}>取消</button>
+          <button
+            className="btn btn-primary"
+            onClick={handleCreate}
+            disabled={!template || !folder || !name.trim() || creating}
+          >
+            {creating ? '创建中...' : '创建'}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,134 @@
+import { useState } from 'react';
+import type { RescanResult, ScanSettings, Workspace } from '../../types';
+import { updateScanSettings } from '../../services/tauri';
+
+interface ScanSettingsModalProps {
+  workspace: Workspace;
+  onSaved: (result: RescanResult) => void;
+  onClose: () => void;
+}
+
+// 每行一个 glob，忽略空行
+const toLines = (patterns: string[]) => patterns.join('\n');
+const fromLines = (text: string) =>
+  text
+    .split('\n')
+    .map((line) => line.trim())
+    .filter(Boolean);
+
+export function ScanSettingsModal({ workspace, onSaved, onClose }: ScanSettingsModalProps) {
+  const scan = workspace.settings.scan;
+  const [maxDepth, setMaxDepth] = useState(String(scan?.max_depth ?? 3));
+  const [include, setInclude] = useState(toLines(scan?.include ?? []));
+  const [exclude, setExclude] = useState(
+    toLines(scan?.exclude ?? ['**/node_modules', '**/.git', '**/dist', '**/target'])
+  );
+  const [followSymlinks, setFollowSymlinks] = useState(scan?.follow_symlinks ?? false);
+  const [respectIgnoreFiles, setRespectIgnoreFiles] = useState(scan?.respect_ignore_files ?? true);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleSave = async () => {
+    const settings: ScanSettings = {
+      max_depth: Number(maxDepth),
+      include: fromLines(include),
+      exclude: fromLines(exclude),
+      follow_symlinks: followSymlinks,
+      respect_ignore_files: respectIgnoreFiles,
+    };
+    setSaving(true);
+    setError(null);
+    try {
+      const result = await updateScanSettings(workspace, settings);
+      onSaved(result);
+      onClose();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <div
+      role="dialog"
+      aria-modal="true"
+      style={{
+        position: 'fixed',
+        inset: 0,
+        backgroundColor: 'rgba(0,0,0,0.5)',
+        display: 'flex',
+        alignItems: 'center',
+        justifyContent: 'center',
+        zIndex: 1000,
+        padding: '24px',
+      }}
+      onMouseDown={(e) => {
+        if (e.target === e.currentTarget && !saving) onClose();
+      }}
+    >
+      <div className="card" style={{ width: '520px', maxWidth: '100%', display: 'flex', flexDirection: 'column', gap: '12px' }}>
+        <div style={{ display: 'flex', flexDirection: 'column', gap: 4 }}>
+          <h2 className="m-0" style={{ fontSize: '1.1rem' }}>扫描设置</h2>
+          <div className="text-xs text-muted">
+            glob 相对于监控文件夹匹配目录，例如 apps/*、**/dist，每行一个。保存后按新规则重新扫描。
+          </div>
+        </div>
+
+        <label className="text-xs text-muted">
+          最大深度
+          <input
+            className="input"
+            type="number"
+            min={1}
+            value={maxDepth}
+            onChange={(e) => setMaxDepth(e.target.value)}
+          />
+        </label>
+
+        <label className="text-xs text-muted">
+          只在这些目录中识别项目（留空表示全部）
+          <textarea className="input" rows={3} value={include} onChange={(e) => setInclude(e.target.value)} />
+        </label>
+
+        <label className="text-xs text-muted">
+          排除目录（连同子目录一起跳过）
+          <textarea className="input" rows={4} value={exclude} onChange={(e) => setExclude(e.target.value)} />
+        </label>
+
+        <label className="flex items-center gap-sm text-xs text-muted">
+          <input
+            type="checkbox"
+            checked={respectIgnoreFiles}
+            onChange={(e) => setRespectIgnoreFiles(e.target.checked)}
+            style={{ accentColor: 'var(--color-primary)' }}
+          />
+          <span>跳过 .gitignore / .ignore 中忽略的目录</span>
+        </label>
+
+        <label className="flex items-center gap-sm text-xs text-muted">
+          <input
+            type="checkbox"
+            checked={followSymlinks}
+            onChange={(e) => setFollowSymlinks(e.target.checked)}
+            style={{ accentColor: 'var(--color-primary)' }}
+          />
+          <span>进入符号链接指向的目录</span>
+        </label>
+
+        {error && <div className="text-xs text-danger">{error}</div>}
+
+        <div className="flex gap-sm" style={{ justifyContent: 'flex-end' }}>
+          <button className="btn btn-secondary" onClick={onClose} disabled={saving}>取消</button>
+          <button
+            className="btn btn-primary"
+            onClick={handleSave}
+            disabled={!(Number(maxDepth) >= 1) || saving}
+          >
+            {saving ? '扫描中...' : '保存并重新扫描'}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
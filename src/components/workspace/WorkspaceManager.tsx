@@ -28,6 +28,7 @@ interface WorkspaceManagerProps {
   onOpenDependencyGraph: () => void;
   onAddFolder: () => void;
   onRemoveFolder: (folderPath: string) => void;
+  onOpenScanSettings: () => void;
   onStartAll: () => void;
   onStopAll: () => void;
   onRepairManagedProject: () => void;
@@ -62,6 +63,7 @@ export function WorkspaceManager({
   onOpenDependencyGraph,
   onAddFolder,
   onRemoveFolder,
+  onOpenScanSettings,
   onStartAll,
   onStopAll,
   onRepairManagedProject,
@@ -671,9 +673,14 @@ export function WorkspaceManager({
               <h3 className="m-0 text-sm font-semibold text-secondary uppercase tracking-wider">
                 监控文件夹
               </h3>
-              <button onClick={onAddFolder} disabled={loading} className="btn btn-primary btn-sm">
-                + 添加文件夹
-              </button>
+              <div className="flex gap-sm">
+                <button onClick={onOpenScanSettings} disabled={loading} className="btn btn-secondary btn-sm">
+                  扫描设置
+                </button>
+                <button onClick={onAddFolder} disabled={loading} className="btn btn-primary btn-sm">
+                  + 添加文件夹
+                </button>
+              </div>
             </div>
 
             <div className="grid gap-sm" style={{ gridTemplateColumns: 'repeat(auto-fill, minmax(300px, 1fr))' }}>
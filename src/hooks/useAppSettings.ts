@@ -6,6 +6,12 @@ const STORAGE_KEY = 'zebras_launcher_settings';
 const DEFAULT_SETTINGS: AppSettings = {
   gitFetchIntervalMinutes: 15,
   gitNotificationsEnabled: true,
+  gatewayEnabled: false,
+  gatewayPort: 7000,
+  gatewayHttpsEnabled: false,
+  gatewayHttpsPort: 7443,
+  registryEnabled: false,
+  registryPort: 7100,
 };
 
 function loadSettings(): AppSettings {
@@ -22,6 +28,28 @@ function loadSettings(): AppSettings {
         typeof parsed.gitNotificationsEnabled === 'boolean'
           ? parsed.gitNotificationsEnabled
           : DEFAULT_SETTINGS.gitNotificationsEnabled,
+      gatewayEnabled:
+        typeof parsed.gatewayEnabled === 'boolean'
+          ? parsed.gatewayEnabled
+          : DEFAULT_SETTINGS.gatewayEnabled,
+      gatewayPort:
+        typeof parsed.gatewayPort === 'number' && parsed.gatewayPort > 0 && parsed.gatewayPort < 65536
+          ? parsed.gatewayPort
+          : DEFAULT_SETTINGS.gatewayPort,
+      gatewayHttpsEnabled:
+        typeof parsed.gatewayHttpsEnabled === 'boolean'
+          ? parsed.gatewayHttpsEnabled
+          : DEFAULT_SETTINGS.gatewayHttpsEnabled,
+      gatewayHttpsPort:
+        typeof parsed.gatewayHttpsPort === 'number' && parsed.gatewayHttpsPort > 0 && parsed.gatewayHttpsPort < 65536
+          ? parsed.gatewayHttpsPort
+          : DEFAULT_SETTINGS.gatewayHttpsPort,
+      registryEnabled:
+        typeof parsed.registryEnabled === 'boolean' ? parsed.registryEnabled : DEFAULT_SETTINGS.registryEnabled,
+      registryPort:
+        typeof parsed.registryPort === 'number' && parsed.registryPort > 0 && parsed.registryPort < 65536
+          ? parsed.registryPort
+          : DEFAULT_SETTINGS.registryPort,
     };
   } catch {
     return DEFAULT_SETTINGS;
@@ -1,21 +1,63 @@
-import { useState, useCallback } from 'react';
+import { useState, useCallback, useEffect } from 'react';
 import { open } from '@tauri-apps/api/dialog';
+import { listen } from '@tauri-apps/api/event';
 import {
   createWorkspace,
   createProjectInstance,
   loadProjectInstance,
-  scanWorkspaceProjects,
+  rescanWorkspace,
   saveWorkspace,
-  resolvePortConflicts,
+  planPortChanges,
+  applyPortChanges,
+  rollbackPortChanges,
   addWorkspaceFolder,
   removeWorkspaceFolder,
+  watchWorkspace,
+  unwatchWorkspace,
 } from '../services/tauri';
-import type { CreateProjectInstanceInput, Workspace, PortChange } from '../types';
+import type {
+  CreateProjectInstanceInput,
+  Workspace,
+  PortChange,
+  PortChangeBackup,
+  ProjectInfo,
+  RescanReport,
+  RescanResult,
+} from '../types';
+
+export interface PortChangePlan {
+  projects: ProjectInfo[];
+  changes: PortChange[];
+}
 
 export function useWorkspace() {
   const [workspace, setWorkspace] = useState<Workspace | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [rescanReport, setRescanReport] = useState<RescanReport | null>(null);
+
+  // 监听当前工作区的文件夹，文件夹或扫描设置变化时重新建立监听
+  const workspaceId = workspace?.id;
+  const folderKey = workspace ? JSON.stringify([workspace.folders, workspace.settings.scan]) : undefined;
+  useEffect(() => {
+    if (!workspaceId) return;
+    watchWorkspace(workspaceId).catch((err) => console.error('监听工作区失败:', err));
+    return () => {
+      unwatchWorkspace().catch(() => {});
+    };
+  }, [workspaceId, folderKey]);
+
+  // 后端检测到配置变化并重新扫描后同步到界面
+  useEffect(() => {
+    const unlisten = listen<RescanResult>('workspace_changed', (event) => {
+      const { workspace: changed, report } = event.payload;
+      setWorkspace((current) => (current && current.id === changed.id ? changed : current));
+      setRescanReport(report);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
 
   // 选择目录并创建工作区
   const selectAndCreateWorkspace = useCallback(async (name: string) => {
@@ -78,34 +120,15 @@ export function useWorkspace() {
       if (workspace.source_type === 'managed_project') {
         const refreshedWorkspace = await loadProjectInstance(workspace.root_path);
         setWorkspace(refreshedWorkspace);
-        await saveWorkspace(refreshedWorkspace);
+        setWorkspace(await saveWorkspace(refreshedWorkspace));
         setLoading(false);
         return;
       }
 
-      const projects = await scanWorkspaceProjects(workspace.folders);
-      const normalizePath = (p: string) => p.replace(/\\/g, '/');
-      const previousByPath = new Map(
-        workspace.projects.map((p) => [normalizePath(p.path), p])
-      );
-
-      const mergedProjects = projects.map((project) => {
-        const prev = previousByPath.get(normalizePath(project.path));
-        return {
-          ...project,
-          id: prev?.id ?? project.id,
-          enabled: prev?.enabled ?? project.enabled,
-        };
-      });
-
-      const updatedWorkspace = {
-        ...workspace,
-        projects: mergedProjects,
-        last_modified: new Date().toISOString(),
-      };
-
-      setWorkspace(updatedWorkspace);
-      await saveWorkspace(updatedWorkspace);
+      // 后端按路径合并扫描结果并保存，保留项目 ID 和用户设置
+      const result = await rescanWorkspace(workspace);
+      setWorkspace(result.workspace);
+      setRescanReport(result.report);
       setLoading(false);
     } catch (err) {
       const message = err instanceof Error ? err.message : String(err);
@@ -172,43 +195,112 @@ export function useWorkspace() {
     }
   }, [workspace]);
 
-  // 解决端口冲突
-  const resolveConflicts = useCallback(async (): Promise<PortChange[]> => {
-    if (!workspace) return [];
+  // 生成端口冲突调整方案（不修改配置文件）
+  const planConflicts = useCallback(async (): Promise<PortChangePlan | null> => {
+    if (!workspace) return null;
     if (workspace.source_type === 'managed_project') {
       setError('受管项目实例不支持自动解决端口冲突');
-      return [];
+      return null;
     }
 
     try {
       setLoading(true);
       setError(null);
 
-      const [updatedProjects, changes] = await resolvePortConflicts(
+      const [projects, changes] = await planPortChanges(
         workspace.id,
         workspace.projects,
         workspace.settings.port_range_start,
-        workspace.settings.port_range_end
+        workspace.settings.port_range_end,
+        undefined,
+        workspace.settings.port_strategy
       );
 
-      if (changes.length > 0) {
-        const updatedWorkspace = {
-          ...workspace,
-          projects: updatedProjects,
-          last_modified: new Date().toISOString(),
-        };
+      setLoading(false);
+      return { projects, changes };
+    } catch (err) {
+      const message = err instanceof Error ? err.message : String(err);
+      setError(message);
+      setLoading(false);
+      return null;
+    }
+  }, [workspace]);
+
+  // 应用端口调整方案，返回可用于回滚的备份
+  const applyPortPlan = useCallback(async (plan: PortChangePlan): Promise<PortChangeBackup | null> => {
+    if (!workspace) return null;
 
-        setWorkspace(updatedWorkspace);
-        await saveWorkspace(updatedWorkspace);
+    try {
+      setLoading(true);
+      setError(null);
+
+      const backup = await applyPortChanges(workspace.id, plan.projects, plan.changes, workspace.settings.strict_json);
+      // 后端已把自动识别项目的端口写入工作区设置，保存时保留
+      const portOverrides = { ...workspace.settings.port_overrides };
+      for (const override of backup.overrides ?? []) {
+        portOverrides[override.project_path] = override.port;
       }
+      const updatedWorkspace = {
+        ...workspace,
+        projects: plan.projects,
+        settings: { ...workspace.settings, port_overrides: portOverrides },
+        last_modified: new Date().toISOString(),
+      };
+
+      setWorkspace(updatedWorkspace);
+      setWorkspace(await saveWorkspace(updatedWorkspace));
+
+      setLoading(false);
+      return backup;
+    } catch (err) {
+      const message = err instanceof Error ? err.message : String(err);
+      setError(message);
+      setLoading(false);
+      return null;
+    }
+  }, [workspace]);
+
+  // 回滚端口变更，恢复本地配置文件和项目端口
+  const rollbackPortPlan = useCallback(async (backupId: string): Promise<boolean> => {
+    if (!workspace) return false;
+
+    try {
+      setLoading(true);
+      setError(null);
+
+      const backup = await rollbackPortChanges(backupId);
+      const projects = workspace.projects.map((project) => {
+        // 旧备份中没有 project_id，按项目名匹配
+        const change = backup.changes.find((c) =>
+          c.project_id ? c.project_id === project.id : c.project_name === project.name
+        );
+        return change ? { ...project, port: change.old_port } : project;
+      });
+      const portOverrides = { ...workspace.settings.port_overrides };
+      for (const override of [...(backup.overrides ?? [])].reverse()) {
+        if (override.original == null) {
+          delete portOverrides[override.project_path];
+        } else {
+          portOverrides[override.project_path] = override.original;
+        }
+      }
+      const updatedWorkspace = {
+        ...workspace,
+        projects,
+        settings: { ...workspace.settings, port_overrides: portOverrides },
+        last_modified: new Date().toISOString(),
+      };
+
+      setWorkspace(updatedWorkspace);
+      setWorkspace(await saveWorkspace(updatedWorkspace));
 
       setLoading(false);
-      return changes;
+      return true;
     } catch (err) {
       const message = err instanceof Error ? err.message : String(err);
       setError(message);
       setLoading(false);
-      return [];
+      return false;
     }
   }, [workspace]);
 
@@ -223,17 +315,21 @@ export function useWorkspace() {
     };
 
     setWorkspace(updatedWorkspace);
-    await saveWorkspace(updatedWorkspace);
+    setWorkspace(await saveWorkspace(updatedWorkspace));
   }, [workspace]);
 
   return {
     workspace,
     loading,
     error,
+    rescanReport,
+    setRescanReport,
     selectAndCreateWorkspace,
     createManagedProject,
     rescanProjects,
-    resolveConflicts,
+    planConflicts,
+    applyPortPlan,
+    rollbackPortPlan,
     updateWorkspace,
     addFolder,
     removeFolder,
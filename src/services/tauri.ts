@@ -6,7 +6,32 @@ import type {
   CreateProjectInstanceInput,
   ValidationResult,
   PortChange,
+  PortChangeBackup,
+  PortCheckMode,
+  PortAvailability,
+  PortInspection,
+  PortRegistry,
+  PortStrategy,
   ProcessInfo,
+  GatewayStatus,
+  CaptureConfig,
+  CaptureFilter,
+  CaptureSession,
+  CapturedExchange,
+  MockRuleSet,
+  MockServerStatus,
+  AppRegistryStatus,
+  ProjectConfig,
+  MigrationPlan,
+  ProjectTemplate,
+  RescanResult,
+  ScaffoldRequest,
+  ScaffoldResult,
+  ZebrasVersion,
+  WorkspaceSettings,
+  ScanSettings,
+  HealthCheckConfig,
+  HealthStatus,
   TerminalSession,
   GitStatus,
   GitPullResult,
@@ -47,14 +72,25 @@ export async function loadWorkspace(workspacePath: string): Promise<Workspace> {
   return invoke('load_workspace', { workspacePath });
 }
 
-export async function scanWorkspaceProjects(folders: string[]): Promise<ProjectInfo[]> {
-  return invoke('scan_workspace_projects', { folders });
+export async function scanWorkspaceProjects(
+  folders: string[],
+  settings?: WorkspaceSettings
+): Promise<ProjectInfo[]> {
+  return invoke('scan_workspace_projects', { folders, settings: settings ?? null });
 }
 
-export async function saveWorkspace(workspace: Workspace): Promise<void> {
+export async function rescanWorkspace(workspace: Workspace): Promise<RescanResult> {
+  return invoke('rescan_workspace', { workspace });
+}
+
+export async function saveWorkspace(workspace: Workspace): Promise<Workspace> {
   return invoke('save_workspace', { workspace });
 }
 
+export async function updateScanSettings(workspace: Workspace, scan: ScanSettings): Promise<RescanResult> {
+  return invoke('update_scan_settings', { workspace, scan });
+}
+
 export async function addWorkspaceFolder(workspace: Workspace, folderPath: string): Promise<Workspace> {
   return invoke('add_workspace_folder', { workspace, folderPath });
 }
@@ -63,6 +99,15 @@ export async function removeWorkspaceFolder(workspace: Workspace, folderPath: st
   return invoke('remove_workspace_folder', { workspace, folderPath });
 }
 
+// 监听工作区文件夹，配置变化时后端重新扫描并发送 workspace_changed 事件
+export async function watchWorkspace(workspaceId: string): Promise<void> {
+  return invoke('watch_workspace', { workspaceId });
+}
+
+export async function unwatchWorkspace(): Promise<void> {
+  return invoke('unwatch_workspace');
+}
+
 export async function deleteWorkspace(workspaceId: string, rootPath: string): Promise<void> {
   return invoke('delete_workspace', { workspaceId, rootPath });
 }
@@ -79,45 +124,148 @@ export async function updateProjectEnabled(
   return invoke('update_project_enabled', { workspace, projectId, enabled });
 }
 
+export async function updateProjectHealthCheck(
+  workspace: Workspace,
+  projectId: string,
+  healthCheck: HealthCheckConfig | null
+): Promise<Workspace> {
+  return invoke('update_project_health_check', { workspace, projectId, healthCheck });
+}
+
+export async function updateProjectConfigEnv(
+  workspace: Workspace,
+  projectId: string,
+  configEnv: string | null
+): Promise<Workspace> {
+  return invoke('update_project_config_env', { workspace, projectId, configEnv });
+}
+
+export async function updateProjectVersionPin(
+  workspace: Workspace,
+  projectId: string,
+  version: ZebrasVersion | null
+): Promise<Workspace> {
+  return invoke('update_project_version_pin', { workspace, projectId, version });
+}
+
 // Project APIs
 export async function getProjectDetails(projectPath: string): Promise<ProjectInfo> {
   return invoke('get_project_details', { projectPath });
 }
 
-export async function rescanProject(projectPath: string): Promise<ProjectInfo> {
-  return invoke('rescan_project', { projectPath });
+export async function rescanProject(
+  projectPath: string,
+  settings?: WorkspaceSettings
+): Promise<ProjectInfo> {
+  return invoke('rescan_project', { projectPath, settings: settings ?? null });
+}
+
+export async function previewV2Migration(projectPath: string): Promise<MigrationPlan> {
+  return invoke('preview_v2_migration', { projectPath });
+}
+
+export async function applyV2Migration(
+  workspace: Workspace,
+  projectId: string,
+  plan: MigrationPlan
+): Promise<Workspace> {
+  return invoke('apply_v2_migration', { workspace, projectId, plan });
 }
 
 export async function isZebrasProject(projectPath: string): Promise<boolean> {
   return invoke('is_zebras_project', { projectPath });
 }
 
+// Template APIs
+export async function listProjectTemplates(): Promise<ProjectTemplate[]> {
+  return invoke('list_project_templates');
+}
+
+export async function createProjectFromTemplate(
+  workspace: Workspace,
+  request: ScaffoldRequest
+): Promise<ScaffoldResult> {
+  return invoke('create_project_from_template', { workspace, request });
+}
+
 // Port APIs
 export async function checkPortAvailable(port: number): Promise<boolean> {
   return invoke('check_port_available', { port });
 }
 
-export async function resolvePortConflicts(
+export async function checkPortAvailability(
+  port: number,
+  mode?: PortCheckMode
+): Promise<PortAvailability> {
+  return invoke('check_port_availability', { port, mode });
+}
+
+export async function inspectPort(port: number): Promise<PortInspection> {
+  return invoke('inspect_port', { port });
+}
+
+// 结束占用端口的进程；expectedPid 为用户确认过的占用者
+export async function freePort(port: number, expectedPid: number): Promise<PortInspection> {
+  return invoke('free_port', { port, expectedPid });
+}
+
+export async function planPortChanges(
   currentWorkspaceId: string,
   projects: ProjectInfo[],
   portRangeStart: number,
-  portRangeEnd: number
+  portRangeEnd: number,
+  checkMode?: PortCheckMode,
+  portStrategy?: PortStrategy
 ): Promise<[ProjectInfo[], PortChange[]]> {
-  return invoke('resolve_port_conflicts', {
+  return invoke('plan_port_changes', {
     currentWorkspaceId,
     projects,
     portRangeStart,
     portRangeEnd,
+    checkMode,
+    portStrategy,
   });
 }
 
+export async function applyPortChanges(
+  currentWorkspaceId: string,
+  projects: ProjectInfo[],
+  changes: PortChange[],
+  strictJson?: boolean
+): Promise<PortChangeBackup> {
+  return invoke('apply_port_changes', { currentWorkspaceId, projects, changes, strictJson: strictJson ?? null });
+}
+
+export async function rollbackPortChanges(backupId: string): Promise<PortChangeBackup> {
+  return invoke('rollback_port_changes', { backupId });
+}
+
+export async function getPortRegistry(): Promise<PortRegistry> {
+  return invoke('get_port_registry');
+}
+
+export async function reservePortRange(
+  workspaceId: string,
+  start: number,
+  end: number
+): Promise<PortRegistry> {
+  return invoke('reserve_port_range', { workspaceId, start, end });
+}
+
+export async function releasePortRange(workspaceId: string): Promise<PortRegistry> {
+  return invoke('release_port_range', { workspaceId });
+}
+
 // Process APIs
 export async function startProject(
-  projectId: string,
-  projectName: string,
-  projectPath: string
+  project: ProjectInfo,
+  workspaceId?: string
 ): Promise<ProcessInfo> {
-  return invoke('start_project', { projectId, projectName, projectPath });
+  return invoke('start_project', { project, workspaceId });
+}
+
+export async function checkProjectHealth(project: ProjectInfo): Promise<HealthStatus> {
+  return invoke('check_project_health', { project });
 }
 
 export async function stopProject(processId: string): Promise<void> {
@@ -203,11 +351,102 @@ export async function gitSwitchBranch(
 export async function updateDebugConfig(
   projectPath: string,
   projectVersion: string,
-  debugMap: Record<string, string>
+  debugMap: Record<string, string>,
+  strictJson?: boolean
 ): Promise<void> {
   return invoke('update_debug_config', {
     projectPath,
     projectVersion,
     debugMap,
+    strictJson: strictJson ?? null,
   });
 }
+
+// Config APIs
+export async function getProjectConfig(
+  projectPath: string,
+  projectVersion: ZebrasVersion,
+  configEnv?: string
+): Promise<ProjectConfig> {
+  return invoke('get_project_config', { projectPath, projectVersion, configEnv: configEnv ?? null });
+}
+
+export async function listProjectConfigEnvs(projectPath: string, projectVersion: ZebrasVersion): Promise<string[]> {
+  return invoke('list_project_config_envs', { projectPath, projectVersion });
+}
+
+// Gateway APIs
+export async function startGateway(port: number, httpsPort?: number): Promise<GatewayStatus> {
+  return invoke('start_gateway', { port, httpsPort: httpsPort ?? null });
+}
+
+export async function stopGateway(): Promise<GatewayStatus> {
+  return invoke('stop_gateway');
+}
+
+export async function getGatewayStatus(): Promise<GatewayStatus> {
+  return invoke('get_gateway_status');
+}
+
+export async function exportGatewayCa(destPath: string): Promise<string> {
+  return invoke('export_gateway_ca', { destPath });
+}
+
+// Capture APIs
+export async function updateProjectCapture(
+  workspace: Workspace,
+  projectId: string,
+  capture: CaptureConfig | null
+): Promise<Workspace> {
+  return invoke('update_project_capture', { workspace, projectId, capture });
+}
+
+export async function getCaptureSessions(): Promise<CaptureSession[]> {
+  return invoke('get_capture_sessions');
+}
+
+export async function queryCaptures(filter?: CaptureFilter): Promise<CapturedExchange[]> {
+  return invoke('query_captures', { filter: filter ?? null });
+}
+
+export async function clearCaptures(projectId?: string): Promise<void> {
+  return invoke('clear_captures', { projectId: projectId ?? null });
+}
+
+export async function exportCapturesHar(filter: CaptureFilter | undefined, destPath: string): Promise<number> {
+  return invoke('export_captures_har', { filter: filter ?? null, destPath });
+}
+
+// Mock APIs
+export async function getMockRules(workspace: Workspace): Promise<MockRuleSet[]> {
+  return invoke('get_mock_rules', { workspace });
+}
+
+export async function updateMockRules(workspace: Workspace, ruleSets: MockRuleSet[]): Promise<Workspace> {
+  return invoke('update_mock_rules', { workspace, ruleSets });
+}
+
+export async function startMockServer(workspace: Workspace): Promise<MockServerStatus> {
+  return invoke('start_mock_server', { workspace });
+}
+
+export async function stopMockServer(): Promise<MockServerStatus> {
+  return invoke('stop_mock_server');
+}
+
+export async function getMockServerStatus(): Promise<MockServerStatus> {
+  return invoke('get_mock_server_status');
+}
+
+// Registry APIs
+export async function startAppRegistry(port: number): Promise<AppRegistryStatus> {
+  return invoke('start_app_registry', { port });
+}
+
+export async function stopAppRegistry(): Promise<AppRegistryStatus> {
+  return invoke('stop_app_registry');
+}
+
+export async function getAppRegistryStatus(): Promise<AppRegistryStatus> {
+  return invoke('get_app_registry_status');
+}
@@ -0,0 +1,48 @@
+export interface CaptureConfig {
+  listen_port?: number; // 代理监听端口，不填时自动分配
+  max_body_bytes: number; // 每个请求/响应体最多记录的字节数
+}
+
+export interface CaptureSession {
+  project_id: string;
+  project_name: string;
+  listen_port: number;
+  target_port: number;
+  started_at: string;
+}
+
+export interface CapturedHeader {
+  name: string;
+  value: string;
+}
+
+export interface CapturedBody {
+  size: number;
+  text?: string; // 非 UTF-8 内容不记录
+  truncated: boolean;
+}
+
+export interface CapturedExchange {
+  id: number;
+  project_id: string;
+  project_name: string;
+  started_at: string;
+  duration_ms: number;
+  method: string;
+  url: string;
+  http_version: string;
+  request_headers: CapturedHeader[];
+  request_body: CapturedBody;
+  status?: number;
+  response_headers: CapturedHeader[];
+  response_body: CapturedBody;
+  error?: string;
+}
+
+export interface CaptureFilter {
+  project_id?: string;
+  method?: string;
+  url_contains?: string;
+  min_status?: number; // 例如 400 只看错误
+  limit?: number;
+}
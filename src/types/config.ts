@@ -0,0 +1,63 @@
+import type { ZebrasVersion } from './project';
+
+export type ConfigSource = 'main' | 'env' | 'local' | 'default';
+
+export interface ConfigTreeEntry {
+  key: string;
+  node: ConfigTreeNode;
+}
+
+export type ConfigTreeNode =
+  | {
+      kind: 'object';
+      source: ConfigSource;
+      line?: number;
+      entries: ConfigTreeEntry[];
+    }
+  | {
+      kind: 'value';
+      value: unknown; // 数组作为整体
+      source: ConfigSource;
+      line?: number;
+      expression?: string; // 无法静态求值的表达式原文，此时 value 为 null
+    };
+
+export interface ConfigFile {
+  source: ConfigSource;
+  path: string;
+  exists: boolean;
+}
+
+export interface ProjectConfig {
+  version: ZebrasVersion;
+  files: ConfigFile[];
+  tree: ConfigTreeNode;
+  merged: Record<string, unknown>;
+}
+
+export type DiagnosticSeverity = 'error' | 'warning';
+
+/** 配置校验发现的问题，行号和列号从 1 开始 */
+export interface ConfigDiagnostic {
+  file: string;
+  line?: number;
+  column?: number;
+  severity: DiagnosticSeverity;
+  message: string;
+  suggestion?: string; // 建议的修改方式
+}
+
+export type MigrationChangeKind = 'create' | 'modify' | 'delete';
+
+export interface MigrationFileChange {
+  path: string;
+  kind: MigrationChangeKind;
+  diff: string; // 逐行对比，每行以 +、- 或空格开头
+  content?: string;
+}
+
+export interface MigrationPlan {
+  project_path: string;
+  changes: MigrationFileChange[];
+  notes: string[]; // 需要手动处理的内容
+}
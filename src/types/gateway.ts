@@ -0,0 +1,16 @@
+export interface GatewayRoute {
+  process_id: string;
+  project_id: string;
+  project_name: string;
+  host: string; // 例如 order.localhost
+  path_prefix: string; // 例如 /order
+  target_port: number;
+  is_default: boolean; // 基座项目，作为未匹配请求的默认路由
+}
+
+export interface GatewayStatus {
+  running: boolean;
+  port?: number;
+  https_port?: number; // 开启 HTTPS 时的监听端口
+  routes: GatewayRoute[];
+}
@@ -1,9 +1,16 @@
 export * from './workspace';
 export * from './workspace_ref';
 export * from './project';
+export * from './port';
 export * from './managed_project';
 export * from './process';
 export * from './log';
 export * from './terminal';
 export * from './git';
 export * from './settings';
+export * from './gateway';
+export * from './capture';
+export * from './mock';
+export * from './registry';
+export * from './config';
+export * from './template';
@@ -0,0 +1,37 @@
+export interface MockRuleSet {
+  id: string;
+  name: string;
+  project_id?: string; // 为空时是工作区级规则，项目规则未命中时继续匹配
+  port: number;
+  upstream?: string; // 未命中规则时透传的上游地址
+  rules: MockRule[];
+}
+
+export interface MockRule {
+  method?: string; // 为空时匹配所有方法
+  path: string; // :id 或 * 匹配一段，** 匹配剩余所有段
+  action: MockAction;
+  enabled: boolean;
+}
+
+export type MockAction =
+  | {
+      type: 'static';
+      file?: string; // 相对项目目录，托管实例相对 .zebras/mocks
+      body?: unknown;
+      status: number;
+      delay_ms: number;
+      headers?: Record<string, string>;
+    }
+  | {
+      type: 'pass_through';
+      upstream?: string; // 为空时使用规则组的 upstream
+    };
+
+export interface MockServerStatus {
+  workspace_id?: string;
+  source?: string;
+  rule_sets: MockRuleSet[];
+  ports: number[];
+  error?: string;
+}
@@ -0,0 +1,92 @@
+import type { PortChange } from './project';
+
+export interface PortInspection {
+  port: number;
+  available: boolean;
+  owners: PortOwner[];
+}
+
+export interface PortOwner {
+  pid: number;
+  parent_pid: number | null;
+  command_line: string | null;
+  cwd: string | null;
+  ownership: PortOwnership;
+}
+
+export type PortOwnership =
+  | {
+      kind: 'launcher_process';
+      process_id: string;
+      project_id: string;
+      project_name: string;
+    }
+  | {
+      kind: 'known_project';
+      workspace_id: string;
+      workspace_name: string;
+      project_id: string;
+      project_name: string;
+    }
+  | { kind: 'external' };
+
+export type PortCheckMode = 'bind' | 'connect' | 'both';
+export type PortProbe = 'bind' | 'connect';
+export type AddressStatus = 'available' | 'in_use' | 'unsupported' | 'error';
+
+export interface AddressCheck {
+  address: string;
+  probe: PortProbe;
+  status: AddressStatus;
+  message?: string;
+}
+
+export interface PortAvailability {
+  port: number;
+  available: boolean;
+  checks: AddressCheck[];
+}
+
+export type LeaseType = 'configured' | 'running';
+
+export interface PortLease {
+  port: number;
+  workspace_id: string;
+  project_id: string;
+  project_name: string;
+  lease_type: LeaseType;
+  pid?: number;
+  updated_at: string;
+  expires_at?: string;
+}
+
+export interface PortRangeReservation {
+  workspace_id: string;
+  start: number;
+  end: number;
+}
+
+export interface PortRegistry {
+  leases: PortLease[];
+  ranges: PortRangeReservation[];
+}
+
+export interface ConfigFileBackup {
+  path: string;
+  original?: string | null; // 为空表示应用前文件不存在
+}
+
+export interface PortOverrideBackup {
+  project_path: string;
+  port: number;
+  original?: number | null; // 为空表示应用前没有端口覆盖
+}
+
+export interface PortChangeBackup {
+  id: string;
+  workspace_id: string;
+  created_at: string;
+  changes: PortChange[];
+  files: ConfigFileBackup[];
+  overrides?: PortOverrideBackup[]; // 自动识别的项目写入工作区设置的端口
+}
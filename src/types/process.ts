@@ -5,6 +5,35 @@ export interface ProcessInfo {
   status: ProcessStatus;
   started_at: string;
   pid: number | null;
+  health?: HealthStatus; // 最近一次 HTTP 健康检查结果
 }
 
 export type ProcessStatus = "starting" | "running" | "stopped" | "crashed";
+
+export type HealthState = 'healthy' | 'degraded' | 'unhealthy';
+
+export interface HealthCheckConfig {
+  path: string;
+  expected_status?: number; // 未设置时接受任意 2xx
+  body_pattern?: string;
+  interval_secs: number;
+  base_url?: string; // 默认 http://127.0.0.1:<port>
+  timeout_ms: number;
+  slow_threshold_ms?: number;
+}
+
+export interface HealthStatus {
+  state: HealthState;
+  latency_ms?: number;
+  status_code?: number;
+  message?: string;
+  checked_at: string;
+}
+
+export interface HealthTransition {
+  process_id: string;
+  project_id: string;
+  project_name: string;
+  previous: HealthState | null;
+  current: HealthStatus;
+}
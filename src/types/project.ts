@@ -1,3 +1,8 @@
+import type { PortAvailability } from './port';
+import type { HealthCheckConfig } from './process';
+import type { CaptureConfig } from './capture';
+import type { ConfigDiagnostic } from './config';
+
 export interface ProjectInfo {
   id: string;
   path: string;
@@ -17,15 +22,68 @@ export interface ProjectInfo {
   debug?: Record<string, string>; // 调试依赖配置，key: 项目名, value: URL
   enabled?: boolean; // 是否在"全部启动"时启动此项目，默认为 true
   runnable: boolean;
+  health_check?: HealthCheckConfig; // HTTP 健康检查配置
+  capture?: CaptureConfig; // 抓包代理配置，为空时不抓包
+  diagnostics?: ConfigDiagnostic[]; // 配置校验结果
+  config_env?: string; // 参与合并的环境覆盖层，例如 dev 对应 zebras.config.dev.ts
+  version_candidates?: VersionCandidate[]; // v2/v3 配置同时存在且无法判断版本时，两种版本各自的解析结果
+  start_command?: StartCommand; // 为空时使用 npm run start
+}
+
+// 参数和环境变量中的 {port} 在启动时替换为项目端口
+export interface StartCommand {
+  program: string; // 以 ./ 开头时相对于项目目录，例如 ./mvnw
+  args: string[];
+  env?: Record<string, string>;
+}
+
+export interface VersionCandidate {
+  version: ZebrasVersion;
+  config_file: string;
+  name?: string;
+  type?: string;
+  port?: number;
+  domain?: string;
+  error?: string; // 按该版本解析失败的原因
+}
+
+export interface RescanEntry {
+  id: string;
+  name: string;
+  path: string;
+  fields?: string[]; // 发生变化的配置字段，仅 changed 中使用
+}
+
+export interface RescanReport {
+  added: RescanEntry[];
+  removed: RescanEntry[];
+  changed: RescanEntry[];
+  stats?: ScanStats;
+}
+
+export interface ScanStats {
+  dirs_visited: number;
+  projects: number;
+  cache_hits: number; // 配置未变化、直接使用缓存结果的项目数
+  elapsed_ms: number;
 }
 
 export type ZebrasVersion = 'v2' | 'v3' | 'managed';
-export type ProjectSourceType = 'zebras' | 'managed_project';
+export type ProjectSourceType = 'zebras' | 'managed_project' | 'detected'; // detected: Vite、Spring Boot 等自动识别的项目
 export type RepoRole = 'frontend_app' | 'backend_service' | 'frontend_package';
 export type ProvisionStatus = 'pending' | 'provisioning' | 'ready' | 'degraded';
 
+export type PortChangeReason =
+  | 'os_occupied'
+  | 'claimed_by_other_workspace'
+  | 'duplicate_in_workspace'
+  | 'hashed_preference';
+
 export interface PortChange {
+  project_id: string;
   project_name: string;
   old_port: number;
   new_port: number;
+  reason: PortChangeReason;
+  availability?: PortAvailability; // 原端口被系统占用时的检测详情
 }
@@ -0,0 +1,25 @@
+import type { ZebrasVersion } from './project';
+
+export interface RegistryEntry {
+  project_id: string;
+  process_id: string;
+  name: string;
+  type: string;
+  version: ZebrasVersion;
+  domain?: string;
+  port: number;
+  entry_url: string; // 网关运行时例如 https://order.localhost:8443/，否则 http://localhost:8003/
+  started_at: string;
+}
+
+// GET /registry.json 返回的内容，也是 SSE registry 事件的数据
+export interface RegistrySnapshot {
+  updated_at: string;
+  apps: RegistryEntry[];
+}
+
+export interface AppRegistryStatus {
+  running: boolean;
+  port?: number;
+  apps: RegistryEntry[];
+}
@@ -1,5 +1,11 @@
 export interface AppSettings {
   gitFetchIntervalMinutes: number;
   gitNotificationsEnabled: boolean;
+  gatewayEnabled: boolean;
+  gatewayPort: number;
+  gatewayHttpsEnabled: boolean;
+  gatewayHttpsPort: number;
+  registryEnabled: boolean;
+  registryPort: number;
 }
 
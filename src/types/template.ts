@@ -0,0 +1,26 @@
+import type { ZebrasVersion } from './project';
+import type { Workspace } from './workspace';
+
+export type TemplateSource = 'bundled' | 'user';
+
+export interface ProjectTemplate {
+  id: string;
+  name: string;
+  version: ZebrasVersion;
+  source: TemplateSource;
+  path?: string; // 用户模板所在目录
+}
+
+export interface ScaffoldRequest {
+  template_id: string;
+  source: TemplateSource;
+  folder: string; // 工作区中的文件夹，项目创建在 folder/name 下
+  name: string;
+  domain?: string;
+  git_init: boolean;
+}
+
+export interface ScaffoldResult {
+  workspace: Workspace;
+  warning?: string; // 项目已创建但 git init 失败
+}
@@ -1,5 +1,6 @@
-import type { ProvisionStatus } from "./project";
+import type { ProvisionStatus, RescanReport, ZebrasVersion } from "./project";
 import { ProjectInfo } from "./project";
+import type { MockRuleSet } from "./mock";
 
 export interface Workspace {
   id: string;
@@ -10,8 +11,10 @@ export interface Workspace {
   folders: string[];  // 包含的多个代码文件夹路径
   created_at: string;
   last_modified: string;
+  revision?: number;  // 每次保存加一，后端据此合并被文件监听更新过的项目列表
   projects: ProjectInfo[];
   settings: WorkspaceSettings;
+  mock_rule_sets?: MockRuleSet[]; // Mock 规则（托管实例保存在 .zebras/mocks）
 }
 
 export interface WorkspaceSettings {
@@ -19,7 +22,26 @@ export interface WorkspaceSettings {
   port_strategy: PortStrategy;
   port_range_start: number;
   port_range_end: number;
+  config_envs?: Record<string, string>; // 项目路径 → 所选的配置环境
+  strict_json?: boolean; // 写回 zebra*.json 时去掉注释和尾随逗号
+  version_pins?: Record<string, ZebrasVersion>; // 项目路径 → 固定的配置版本
+  port_overrides?: Record<string, number>; // 项目路径 → 端口，用于没有 Zebras 配置文件的自动识别项目
+  scan?: ScanSettings;
 }
 
-export type PortStrategy = 'sequential' | 'fixed';
+// glob 相对于监控文件夹匹配目录路径
+export interface ScanSettings {
+  max_depth: number;
+  include: string[]; // 非空时只在匹配的目录中识别项目
+  exclude: string[]; // 匹配的目录连同子目录一起跳过
+  follow_symlinks: boolean; // 同一目标只进入一次，避免链接成环
+  respect_ignore_files: boolean; // 跳过 .gitignore / .ignore 中忽略的目录
+}
+
+export interface RescanResult {
+  workspace: Workspace;
+  report: RescanReport;
+}
+
+export type PortStrategy = 'sequential' | 'fixed' | 'hashed';
 export type WorkspaceSourceType = 'folder_scan' | 'managed_project';